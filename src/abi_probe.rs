@@ -0,0 +1,167 @@
+use rune_parser::{RuneFileDescription, types::StructDefinition};
+
+use crate::{
+    c_utilities::{CConfigurations, CStructDefinition, MemberLayout, offset_member_name, output_base_name, output_disclaimer, pascal_to_snake_case},
+    compile_error::CompilerError,
+    output_file::OutputFile
+};
+
+/// Outputs a program printing the actual size and member offsets of every struct next to the ones estimated by the compiler
+pub fn output_abi_probe(file_descriptions: &Vec<RuneFileDescription>, configurations: &CConfigurations) -> Result<OutputFile, CompilerError> {
+    let mut probe_file: OutputFile = OutputFile::new(String::from("abi_probe.c"), &configurations.compiler_configurations);
+
+    // Disclaimers
+    // ————————————
+
+    output_disclaimer(&mut probe_file, &configurations.compiler_configurations);
+
+    // Description & includes
+    // ———————————————————————
+
+    // Comments are kept C89 compliant, as the probe is most useful on targets without static assertions
+    probe_file.add_line(String::from(
+        "/* Compile together with the generated sources and run it on the target, to compare the actual struct layouts against the ones"
+    ));
+    probe_file.add_line(String::from(" * estimated by the Rune C compiler. Exits with a non-zero value if any of them differ */"));
+    probe_file.add_newline();
+
+    probe_file.add_line(String::from("#include <stddef.h>"));
+    probe_file.add_line(String::from("#include <stdio.h>"));
+    probe_file.add_newline();
+
+    for file in file_descriptions {
+        if !file.definitions.structs.is_empty() {
+            probe_file.add_line(format!(
+                "#include \"{0}.{1}\"",
+                output_base_name(file, &configurations.compiler_configurations),
+                configurations.compiler_configurations.output_extension("h")
+            ));
+        }
+    }
+    probe_file.add_newline();
+
+    // Comparison helper
+    // ——————————————————
+
+    probe_file.add_line(String::from("static int mismatches = 0;"));
+    probe_file.add_newline();
+
+    probe_file.add_line(String::from("static void rune_abi_check(const char* name, unsigned long actual, unsigned long estimated) {"));
+    probe_file.add_indented_line(
+        1,
+        String::from("printf(\"    %-32s %8lu %10lu%s\\n\", name, actual, estimated, actual == estimated ? \"\" : \"  <-- mismatch\");")
+    );
+    probe_file.add_newline();
+    probe_file.add_indented_line(1, String::from("if (actual != estimated) {"));
+    probe_file.add_indented_line(2, String::from("mismatches++;"));
+    probe_file.add_indented_line(1, String::from("}"));
+    probe_file.add_line(String::from("}"));
+    probe_file.add_newline();
+
+    // Struct probes
+    // ——————————————
+
+    probe_file.add_line(String::from("int main(void) {"));
+    probe_file.add_indented_line(1, String::from("printf(\"%-36s %8s %10s\\n\", \"\", \"actual\", \"estimated\");"));
+
+    for file in file_descriptions {
+        for struct_definition in &file.definitions.structs {
+            output_struct_probe(&mut probe_file, configurations, struct_definition)?;
+        }
+    }
+
+    probe_file.add_newline();
+    probe_file.add_indented_line(1, String::from("printf(\"%d mismatches\\n\", mismatches);"));
+    probe_file.add_indented_line(1, String::from("return mismatches == 0 ? 0 : 1;"));
+    probe_file.add_line(String::from("}"));
+
+    Ok(probe_file)
+}
+
+fn output_struct_probe(probe_file: &mut OutputFile, configurations: &CConfigurations, struct_definition: &StructDefinition) -> Result<(), CompilerError> {
+    let struct_name: String = pascal_to_snake_case(&struct_definition.name);
+
+    let layout: Vec<MemberLayout> = struct_definition.estimate_layout(&configurations.compiler_configurations)?;
+
+    probe_file.add_newline();
+    probe_file.add_indented_line(1, format!("printf(\"{0}_t\\n\");", struct_name));
+    probe_file.add_indented_line(
+        1,
+        format!(
+            "rune_abi_check(\"sizeof\", (unsigned long)sizeof({0}_t), {1}UL);",
+            struct_name,
+            struct_definition.estimate_size(&configurations.compiler_configurations)?
+        )
+    );
+
+    for member_layout in &layout {
+        let member_name: String = pascal_to_snake_case(&member_layout.member.identifier);
+
+        probe_file.add_indented_line(
+            1,
+            format!(
+                "rune_abi_check(\".{0}\", (unsigned long)offsetof({1}_t, {2}), {3}UL);",
+                member_name,
+                struct_name,
+                offset_member_name(configurations, &struct_definition.name, &member_layout.member)?,
+                member_layout.offset
+            )
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rune_parser::RuneFileDescription;
+
+    use crate::{
+        generate_to_memory,
+        test_utilities::{compile_configurations, parse_schema}
+    };
+
+    /// Output the ABI probe generated from the given Rune files with the given command line arguments
+    fn abi_probe(files: &[(&str, &str)], arguments: &[&str]) -> String {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(files);
+        let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &compile_configurations(&[&["--emit-abi-probe"], arguments].concat())).unwrap();
+
+        output_files.into_iter().find(|(name, _)| name == "abi_probe.c").expect("ABI probe should be generated").1
+    }
+
+    #[test]
+    fn struct_sizes_and_member_offsets_are_checked_against_the_estimates() {
+        // Members are probed in the order they are placed in the struct
+        let probe: String = abi_probe(&[("frame.rune", "struct Frame {\n    Flag: u8 = 1;\n    Count: u32 = 2;\n    Level: u16 = 3\n}\n")], &[]);
+
+        assert!(probe.contains("#include <stddef.h>\n#include <stdio.h>\n\n#include \"frame.rune.h\"\n"));
+        assert!(probe.contains("static void rune_abi_check(const char* name, unsigned long actual, unsigned long estimated) {"));
+        assert!(probe.contains(
+            "    printf(\"frame_t\\n\");\n    rune_abi_check(\"sizeof\", (unsigned long)sizeof(frame_t), 8UL);\n    rune_abi_check(\".count\", (unsigned long)offsetof(frame_t, count), 0UL);\n    rune_abi_check(\".level\", (unsigned long)offsetof(frame_t, level), 4UL);\n    rune_abi_check(\".flag\", (unsigned long)offsetof(frame_t, flag), 6UL);\n"
+        ));
+        assert!(probe.contains("    return mismatches == 0 ? 0 : 1;\n}"));
+    }
+
+    #[test]
+    fn packed_layouts_are_probed_without_padding() {
+        let probe: String = abi_probe(&[("frame.rune", "struct Frame {\n    Flag: u8 = 1;\n    Level: u16 = 2\n}\n")], &["--pack-data"]);
+
+        // Without packing the struct would end in a padding byte
+        assert!(probe.contains("rune_abi_check(\"sizeof\", (unsigned long)sizeof(frame_t), 3UL);"));
+        assert!(probe.contains("rune_abi_check(\".flag\", (unsigned long)offsetof(frame_t, flag), 2UL);"));
+    }
+
+    #[test]
+    fn only_files_declaring_structs_are_included() {
+        let probe: String = abi_probe(
+            &[
+                ("mode.rune", "enum Mode: u8 {\n    Idle = 0\n}\n"),
+                ("frame.rune", "include \"mode.rune\";\n\nstruct Frame {\n    Current: Mode = 1\n}\n")
+            ],
+            &[]
+        );
+
+        assert!(probe.contains("#include \"frame.rune.h\""));
+        assert!(!probe.contains("#include \"mode.rune.h\""));
+    }
+}
@@ -0,0 +1,121 @@
+use rune_parser::{RuneFileDescription, types::Definitions};
+
+use crate::{c_utilities::is_included_file, output::*};
+
+/// Name of the amalgamated header and source, without extension
+pub const AMALGAMATED_NAME: &str = "rune_all";
+
+/// Add the files the file includes, and then the file itself, to the order, so every file comes after the files it depends on.
+/// Files already being ordered are skipped, as include cycles have no order satisfying every file
+fn order_with_includes<'a>(file: &'a RuneFileDescription, file_descriptions: &'a [RuneFileDescription], ordering: &mut Vec<&'a RuneFileDescription>, order: &mut Vec<&'a RuneFileDescription>) {
+    if order.iter().chain(ordering.iter()).any(|ordered| std::ptr::eq(*ordered, file)) {
+        return;
+    }
+
+    ordering.push(file);
+
+    for include_definition in &file.definitions.includes {
+        for included_file in file_descriptions {
+            if is_included_file(included_file, include_definition) {
+                order_with_includes(included_file, file_descriptions, ordering, order);
+            }
+        }
+    }
+
+    ordering.pop();
+    order.push(file);
+}
+
+/// Merge all files into a single file, with the definitions of every file following those of the files it includes. Enums and
+/// bitfields are output before all structs, and structs in the order they are merged, so every struct is declared after the types
+/// it uses, as long as the files themselves include what they use
+pub fn amalgamate(file_descriptions: &[RuneFileDescription]) -> RuneFileDescription {
+    let mut order: Vec<&RuneFileDescription> = Vec::with_capacity(file_descriptions.len());
+
+    for file in file_descriptions {
+        order_with_includes(file, file_descriptions, &mut Vec::with_capacity(0x10), &mut order);
+    }
+
+    let mut definitions: Definitions = Definitions::default();
+
+    for file in order {
+        debug!("Amalgamating {0}{1}.rune", file.relative_path, file.name);
+
+        definitions.defines.extend(file.definitions.defines.iter().cloned());
+        definitions.redefines.extend(file.definitions.redefines.iter().cloned());
+        definitions.enums.extend(file.definitions.enums.iter().cloned());
+        definitions.bitfields.extend(file.definitions.bitfields.iter().cloned());
+        definitions.structs.extend(file.definitions.structs.iter().cloned());
+        definitions.standalone_comments.extend(file.definitions.standalone_comments.iter().cloned());
+        definitions.extensions.enums.extend(file.definitions.extensions.enums.iter().cloned());
+        definitions.extensions.bitfields.extend(file.definitions.extensions.bitfields.iter().cloned());
+        definitions.extensions.structs.extend(file.definitions.extensions.structs.iter().cloned());
+    }
+
+    // Includes between the files are resolved by the merge itself
+    RuneFileDescription {
+        relative_path: String::new(),
+        name: String::from(AMALGAMATED_NAME),
+        definitions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rune_parser::RuneFileDescription;
+
+    use crate::{
+        amalgamation::{AMALGAMATED_NAME, amalgamate},
+        generate_to_memory,
+        test_utilities::{compile_configurations, parse_schema}
+    };
+
+    /// Files which sort before the files they include, so only ordering by includes declares every struct before its use
+    const SCHEMA: [(&str, &str); 3] = [
+        ("alpha.rune", "include \"mid.rune\";\n\nstruct Alpha {\n    Inner: Mid = 1\n}\n"),
+        ("mid.rune", "include \"zeta.rune\";\n\nstruct Mid {\n    Inner: Zeta = 1\n}\n"),
+        ("zeta.rune", "struct Zeta {\n    Value: u8 = 1\n}\n")
+    ];
+
+    /// Parse the schema, with the files in alphabetical order rather than the order they are found in
+    fn alphabetical_files() -> Vec<RuneFileDescription> {
+        let mut file_descriptions: Vec<RuneFileDescription> = parse_schema(&SCHEMA);
+        file_descriptions.sort_by(|left, right| left.name.cmp(&right.name));
+
+        file_descriptions
+    }
+
+    #[test]
+    fn included_files_are_merged_before_the_files_including_them() {
+        let file_descriptions: Vec<RuneFileDescription> = alphabetical_files();
+        let amalgamated: RuneFileDescription = amalgamate(&file_descriptions);
+
+        let struct_names: Vec<&str> = amalgamated.definitions.structs.iter().map(|definition| definition.name.as_str()).collect();
+        assert_eq!(struct_names, ["Zeta", "Mid", "Alpha"]);
+        assert_eq!(amalgamated.name, AMALGAMATED_NAME);
+    }
+
+    #[test]
+    fn amalgamated_header_declares_structs_before_their_use() {
+        let file_descriptions: Vec<RuneFileDescription> = alphabetical_files();
+        let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &compile_configurations(&["--amalgamate"])).unwrap();
+
+        let header: &str = &output_files.iter().find(|(name, _)| name == "rune_all.h").expect("Amalgamated header should be generated").1;
+        let position = |declaration: &str| header.find(declaration).unwrap_or_else(|| panic!("{0} should be declared", declaration));
+
+        assert!(position("} zeta_t;") < position("zeta_t inner;"));
+        assert!(position("} mid_t;") < position("mid_t inner;"));
+        assert!(!header.contains("#include \"zeta.rune.h\""));
+    }
+
+    #[test]
+    fn include_cycles_are_merged_once() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[
+            ("alpha.rune", "include \"beta.rune\";\n\nstruct Alpha {\n    X: u8 = 1\n}\n"),
+            ("beta.rune", "include \"alpha.rune\";\n\nstruct Beta {\n    Inner: Alpha = 1\n}\n")
+        ]);
+
+        let struct_names: Vec<String> = amalgamate(&file_descriptions).definitions.structs.into_iter().map(|definition| definition.name).collect();
+        assert_eq!(struct_names.len(), 2);
+    }
+}
@@ -3,7 +3,7 @@ use std::fmt::{Display, Formatter};
 use crate::{compile_error::CompilerError, output::*};
 
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
-pub enum CStandard {
+enum StandardVersion {
     // C90 is an alias for C89
     C89 = 0,
     C95 = 1,
@@ -13,66 +13,255 @@ pub enum CStandard {
     C23 = 5
 }
 
+/// C standard the output complies with, either the ISO standard itself or its GNU dialect, and whether the output may use GNU
+/// extensions such as __attribute__. The GNU dialects always allow them, while the ISO standards only do so unless disabled
+#[derive(Debug, Clone, PartialEq)]
+pub struct CStandard {
+    version:        StandardVersion,
+    gnu_dialect:    bool,
+    gnu_extensions: bool
+}
+
 impl CStandard {
-    pub fn from_string(string: &str) -> Result<CStandard, CompilerError> {
-        match string {
-            "c89" | "C89" | "c90" | "C90" => Ok(CStandard::C89),
-            "c95" | "C95" => Ok(CStandard::C95),
-            "c99" | "C99" => Ok(CStandard::C99),
-            "c11" | "C11" => Ok(CStandard::C11),
-            "c17" | "C17" => Ok(CStandard::C17),
-            "c23" | "C23" => Ok(CStandard::C23),
+    pub fn from_string(string: &str, gnu_extensions: bool) -> Result<CStandard, CompilerError> {
+        let (version, gnu_dialect): (StandardVersion, bool) = match string {
+            "c89" | "C89" | "c90" | "C90" => (StandardVersion::C89, false),
+            "c95" | "C95" => (StandardVersion::C95, false),
+            "c99" | "C99" => (StandardVersion::C99, false),
+            "c11" | "C11" => (StandardVersion::C11, false),
+            "c17" | "C17" => (StandardVersion::C17, false),
+            "c23" | "C23" => (StandardVersion::C23, false),
+            // GCC has no GNU dialect of C95
+            "gnu89" | "GNU89" | "gnu90" | "GNU90" => (StandardVersion::C89, true),
+            "gnu99" | "GNU99" => (StandardVersion::C99, true),
+            "gnu11" | "GNU11" => (StandardVersion::C11, true),
+            "gnu17" | "GNU17" => (StandardVersion::C17, true),
+            "gnu23" | "GNU23" => (StandardVersion::C23, true),
             _ => {
                 error!("Invalid C Standard passed. Got {0}, and valid values are: {1}", string, CStandard::valid_values());
-                Err(CompilerError::InvalidArgument)
+                return Err(CompilerError::InvalidArgument);
             }
+        };
+
+        if gnu_dialect && !gnu_extensions {
+            error!("GNU extensions cannot be disabled with the GNU dialect {0}. Pass the matching ISO standard instead", string);
+            return Err(CompilerError::InvalidArgument);
         }
+
+        Ok(CStandard { version, gnu_dialect, gnu_extensions })
     }
 
     fn valid_values() -> String {
-        String::from("C89/C90, C95, C99, C11, C17, C23")
+        String::from("C89/C90, C95, C99, C11, C17, C23, GNU89/GNU90, GNU99, GNU11, GNU17, GNU23")
+    }
+
+    /// The -std flag selecting the standard in GCC and Clang. Compilers released before C23 was published name it c2x and gnu2x
+    pub fn gcc_std_flag(&self) -> &'static str {
+        match (&self.version, self.gnu_dialect) {
+            (StandardVersion::C89, false) => "-std=c89",
+            (StandardVersion::C95, _) => "-std=iso9899:199409",
+            (StandardVersion::C99, false) => "-std=c99",
+            (StandardVersion::C11, false) => "-std=c11",
+            (StandardVersion::C17, false) => "-std=c17",
+            (StandardVersion::C23, false) => "-std=c23",
+            (StandardVersion::C89, true) => "-std=gnu89",
+            (StandardVersion::C99, true) => "-std=gnu99",
+            (StandardVersion::C11, true) => "-std=gnu11",
+            (StandardVersion::C17, true) => "-std=gnu17",
+            (StandardVersion::C23, true) => "-std=gnu23"
+        }
+    }
+
+    /// Value of __STDC_VERSION__ for the standard. C89 does not define it, so the C90 year is used
+    pub fn version_value(&self) -> &'static str {
+        match self.version {
+            StandardVersion::C89 => "199000L",
+            StandardVersion::C95 => "199409L",
+            StandardVersion::C99 => "199901L",
+            StandardVersion::C11 => "201112L",
+            StandardVersion::C17 => "201710L",
+            StandardVersion::C23 => "202311L"
+        }
+    }
+
+    /// Keywords of the standard, which cannot be used as identifiers. Before C23, bool, true and false are counted as well, as they are
+    /// defined as macros by <stdbool.h>, which the generated headers include from C99
+    pub fn keywords(&self) -> Vec<&'static str> {
+        let mut keywords: Vec<&'static str> = vec![
+            "auto", "break", "case", "char", "const", "continue", "default", "do", "double", "else", "enum", "extern", "float", "for", "goto", "if", "int", "long", "register", "return", "short",
+            "signed", "sizeof", "static", "struct", "switch", "typedef", "union", "unsigned", "void", "volatile", "while",
+        ];
+
+        // GNU dialects also reserve asm and typeof, and inline already before C99
+        if self.gnu_dialect {
+            keywords.extend(["asm", "typeof"]);
+
+            if self.version < StandardVersion::C99 {
+                keywords.push("inline");
+            }
+        }
+
+        if self.version >= StandardVersion::C99 {
+            keywords.extend(["inline", "restrict", "_Bool", "_Complex", "_Imaginary", "bool", "true", "false"]);
+        }
+
+        if self.version >= StandardVersion::C11 {
+            keywords.extend(["_Alignas", "_Alignof", "_Atomic", "_Generic", "_Noreturn", "_Static_assert", "_Thread_local"]);
+        }
+
+        if self.version >= StandardVersion::C23 {
+            keywords.extend([
+                "alignas",
+                "alignof",
+                "constexpr",
+                "nullptr",
+                "static_assert",
+                "thread_local",
+                "typeof",
+                "typeof_unqual",
+                "_BitInt",
+                "_Decimal32",
+                "_Decimal64",
+                "_Decimal128"
+            ]);
+        }
+
+        keywords
+    }
+
+    // GNU
+    // ————
+
+    /// Whether GNU extensions such as __attribute__ may be used. Without them, structs are packed with #pragma pack, which GCC, Clang
+    /// and MSVC all support, and the output builds with -pedantic
+    pub fn allows_gnu_extensions(&self) -> bool {
+        self.gnu_extensions
+    }
+
+    /// Whether binary literals may be used, which are a GNU extension before C23
+    pub fn allows_binary_literals(&self) -> bool {
+        self.gnu_extensions || self.version >= StandardVersion::C23
+    }
+
+    /// Whether flexible array members may be initialized statically, which is a GNU extension in every C standard
+    pub fn allows_flexible_array_initializers(&self) -> bool {
+        self.gnu_extensions && self.allows_flexible_array_members()
     }
 
     // C99
     // ————
 
     pub fn allows_boolean(&self) -> bool {
-        *self >= CStandard::C99
+        self.version >= StandardVersion::C99
     }
 
     pub fn allows_designated_initializers(&self) -> bool {
-        *self >= CStandard::C99
+        self.version >= StandardVersion::C99
     }
 
     pub fn allows_flexible_array_members(&self) -> bool {
-        *self >= CStandard::C99
+        self.version >= StandardVersion::C99
     }
 
     pub fn allows_inline(&self) -> bool {
-        *self >= CStandard::C99
+        self.version >= StandardVersion::C99
     }
 
     pub fn allows_integer_types(&self) -> bool {
-        *self >= CStandard::C99
+        self.version >= StandardVersion::C99
+    }
+
+    // C11
+    // ————
+
+    pub fn allows_static_assert(&self) -> bool {
+        self.version >= StandardVersion::C11
+    }
+
+    pub fn allows_anonymous_members(&self) -> bool {
+        self.version >= StandardVersion::C11
+    }
+
+    pub fn allows_alignas(&self) -> bool {
+        self.version >= StandardVersion::C11
     }
 
     // C23
     // ————
 
     pub fn allows_enum_backing_type(&self) -> bool {
-        *self >= CStandard::C23
+        self.version >= StandardVersion::C23
+    }
+
+    pub fn allows_static_assert_keyword(&self) -> bool {
+        self.version >= StandardVersion::C23
+    }
+
+    pub fn allows_alignas_keyword(&self) -> bool {
+        self.version >= StandardVersion::C23
+    }
+
+    pub fn allows_standard_attributes(&self) -> bool {
+        self.version >= StandardVersion::C23
     }
 }
 
 impl Display for CStandard {
     fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            CStandard::C89 => write!(formatter, "C89"),
-            CStandard::C95 => write!(formatter, "C95"),
-            CStandard::C99 => write!(formatter, "C99"),
-            CStandard::C11 => write!(formatter, "C11"),
-            CStandard::C17 => write!(formatter, "C17"),
-            CStandard::C23 => write!(formatter, "C23")
+        let year: &str = match self.version {
+            StandardVersion::C89 => "89",
+            StandardVersion::C95 => "95",
+            StandardVersion::C99 => "99",
+            StandardVersion::C11 => "11",
+            StandardVersion::C17 => "17",
+            StandardVersion::C23 => "23"
+        };
+
+        match self.gnu_dialect {
+            true => write!(formatter, "GNU{0}", year),
+            false => write!(formatter, "C{0}", year)
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iso_standards_allow_gnu_extensions_by_default() {
+        let c11: CStandard = CStandard::from_string("C11", true).unwrap();
+        assert!(c11.allows_gnu_extensions());
+        assert!(c11.allows_binary_literals());
+        assert!(c11.allows_flexible_array_initializers());
+        assert_eq!(c11.gcc_std_flag(), "-std=c11");
+        assert!(!c11.keywords().contains(&"asm"));
+        assert_eq!(c11.to_string(), "C11");
+    }
+
+    #[test]
+    fn iso_standards_without_gnu_extensions_are_strict() {
+        let c11: CStandard = CStandard::from_string("c11", false).unwrap();
+        assert!(!c11.allows_gnu_extensions());
+        assert!(!c11.allows_binary_literals());
+        assert!(!c11.allows_flexible_array_initializers());
+        assert!(CStandard::from_string("C23", false).unwrap().allows_binary_literals());
+    }
+
+    #[test]
+    fn gnu_dialects_reserve_gnu_keywords() {
+        let gnu11: CStandard = CStandard::from_string("gnu11", true).unwrap();
+        assert!(gnu11.allows_gnu_extensions());
+        assert_eq!(gnu11.gcc_std_flag(), "-std=gnu11");
+        assert!(gnu11.keywords().contains(&"asm"));
+        assert!(gnu11.keywords().contains(&"typeof"));
+        assert_eq!(gnu11.to_string(), "GNU11");
+        assert!(CStandard::from_string("GNU89", true).unwrap().keywords().contains(&"inline"));
+    }
+
+    #[test]
+    fn gnu_dialects_reject_disabling_gnu_extensions() {
+        assert!(matches!(CStandard::from_string("GNU11", false), Err(CompilerError::InvalidArgument)));
+        assert!(matches!(CStandard::from_string("C12", true), Err(CompilerError::InvalidArgument)));
+    }
+}
@@ -1,10 +1,19 @@
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    fmt::{Display, Formatter}
+};
+
 use rune_parser::{
     RuneFileDescription,
-    scanner::NumericLiteral,
-    types::{ArraySize, ArrayType, DefineValue, FieldIndex, FieldType, Primitive, StructDefinition, StructMember, UserDefinitionLink}
+    scanner::{NumeralSystem, NumericLiteral},
+    types::{ArraySize, ArrayType, DefineValue, FieldIndex, FieldType, IncludeDefinition, Primitive, StructDefinition, StructMember, UserDefinitionLink}
 };
 
-use crate::{architecture::Architecture, c_standard::CStandard, compile_error::CompilerError, output::*};
+use crate::{
+    architecture::Architecture, c_standard::CStandard, compile_error::CompilerError, endianness::Endianness, include_guard_style::IncludeGuardStyle, indentation::Indentation, line_ending::LineEnding,
+    output::*, output_file::OutputFile, target_compiler::TargetCompiler
+};
 
 // String helper functions
 // ————————————————————————
@@ -20,14 +29,28 @@ pub fn spaces(amount: usize) -> String {
     spaces
 }
 
+/// Whether the uppercase letter at the index starts a new word. A run of uppercase letters is an acronym forming one word, ended
+/// by the capital starting the next word, so HTTPServer splits into HTTP and Server, and DeviceID into Device and ID
+fn starts_word(characters: &[char], index: usize) -> bool {
+    if index == 0 || !characters[index].is_ascii_uppercase() {
+        return false;
+    }
+
+    let previous: char = characters[index - 1];
+
+    match previous.is_ascii_uppercase() {
+        true => characters.get(index + 1).is_some_and(|next| next.is_ascii_lowercase()),
+        false => previous.is_ascii_lowercase() || previous.is_ascii_digit()
+    }
+}
+
 /// Convert NamedVariable to named_variable
 pub fn pascal_to_snake_case(pascal: &str) -> String {
-    let mut snake: String = String::with_capacity(0x40);
+    let characters: Vec<char> = pascal.chars().collect();
+    let mut snake: String = String::with_capacity(pascal.len() + 0x10);
 
-    for i in 0..pascal.len() {
-        let letter: char = pascal.chars().nth(i).unwrap();
-
-        if i != 0 && letter.is_ascii_uppercase() {
+    for (i, letter) in characters.iter().enumerate() {
+        if starts_word(&characters, i) {
             snake.push('_');
         }
 
@@ -39,12 +62,11 @@ pub fn pascal_to_snake_case(pascal: &str) -> String {
 
 /// Convert NamedVariable to NAMED_VARIABLE
 pub fn pascal_to_uppercase(pascal: &str) -> String {
-    let mut uppecase: String = String::with_capacity(0x40);
-
-    for i in 0..pascal.len() {
-        let letter: char = pascal.chars().nth(i).unwrap();
+    let characters: Vec<char> = pascal.chars().collect();
+    let mut uppecase: String = String::with_capacity(pascal.len() + 0x10);
 
-        if i != 0 && letter.is_ascii_uppercase() {
+    for (i, letter) in characters.iter().enumerate() {
+        if starts_word(&characters, i) {
             uppecase.push('_');
         }
 
@@ -54,6 +76,329 @@ pub fn pascal_to_uppercase(pascal: &str) -> String {
     uppecase
 }
 
+// Output helper functions
+// ————————————————————————
+
+/// Start packing the following struct declaration, for compilers which do not support the packed attribute
+pub fn output_pack_push(output_file: &mut OutputFile, configurations: &CompileConfigurations, packed: bool) {
+    if packed && !configurations.allows_attributes() {
+        output_file.add_line(String::from("#pragma pack(push, 1)"));
+    }
+}
+
+/// Restore the packing of the compiler after a struct declaration started with output_pack_push
+pub fn output_pack_pop(output_file: &mut OutputFile, configurations: &CompileConfigurations, packed: bool) {
+    if packed && !configurations.allows_attributes() {
+        output_file.add_line(String::from("#pragma pack(pop)"));
+    }
+}
+
+/// Output precondition checks of a generated function, only compiled in when RUNE_DEBUG is defined
+pub fn output_debug_checks(output_file: &mut OutputFile, conditions: &[&str]) {
+    output_file.add_line(String::from("#ifdef RUNE_DEBUG"));
+
+    for condition in conditions {
+        output_file.add_indented_line(1, format!("RUNE_ASSERT({0});", condition));
+    }
+
+    output_file.add_line(String::from("#endif"));
+}
+
+/// Format the comment of a definition from a Rune file as the lines of a doc comment block. The leading '*' of `/** */` comments and
+/// the ones starting the lines of multi-line comments are dropped, and embedded comment terminators are escaped, as they would end
+/// the block early. With doxygen tags the first line is marked as the brief description
+pub fn format_doc_comment(comment: &str, doxygen: bool) -> Vec<String> {
+    let mut lines: Vec<String> = comment
+        .strip_prefix('*')
+        .unwrap_or(comment)
+        .lines()
+        .map(|line| {
+            let trimmed: &str = line.trim();
+            trimmed.strip_prefix('*').map(str::trim_start).unwrap_or(trimmed).replace("*/", "*\\/")
+        })
+        .collect();
+
+    // Drop the blank lines around the text of multi-line comments
+    while lines.last().is_some_and(String::is_empty) {
+        lines.pop();
+    }
+
+    let first_line: usize = lines.iter().position(|line| !line.is_empty()).unwrap_or(lines.len());
+    lines.drain(..first_line);
+
+    if lines.is_empty() {
+        return Vec::new();
+    }
+
+    if doxygen {
+        lines[0] = format!("@brief {0}", lines[0]);
+    }
+
+    if lines.len() == 1 {
+        return vec![format!("/** {0} */", lines[0])];
+    }
+
+    let mut comment_lines: Vec<String> = Vec::with_capacity(lines.len() + 2);
+    comment_lines.push(String::from("/**"));
+
+    for line in lines {
+        match line.is_empty() {
+            true => comment_lines.push(String::from(" *")),
+            false => comment_lines.push(format!(" * {0}", line))
+        }
+    }
+
+    comment_lines.push(String::from(" */"));
+
+    comment_lines
+}
+
+/// Wrap a banner text into a C comment block, or pass it through unchanged if it already is one
+pub fn banner_comment(text: &str, verbatim: bool) -> Result<String, CompilerError> {
+    let text: &str = text.trim_end();
+
+    if verbatim {
+        return Ok(String::from(text));
+    }
+
+    // The banner would end the comment block early
+    if text.contains("*/") {
+        error!("Banner text contains a \"*/\" comment terminator! Pass --banner-verbatim if it already contains its own comment markers");
+        return Err(CompilerError::InvalidArgument);
+    }
+
+    let mut comment: String = String::from("/*\n");
+
+    for line in text.lines() {
+        match line.is_empty() {
+            true => comment.push_str(" *\n"),
+            false => comment.push_str(format!(" * {0}\n", line).as_str())
+        }
+    }
+
+    comment.push_str(" */");
+
+    Ok(comment)
+}
+
+/// Output the disclaimers at the top of a generated file
+pub fn output_disclaimer(output_file: &mut OutputFile, configurations: &CompileConfigurations) {
+    if let Some(banner) = &configurations.banner {
+        for line in banner.lines() {
+            output_file.add_line(String::from(line));
+        }
+        output_file.add_newline();
+    }
+}
+
+// Definition helper functions
+// ————————————————————————————
+
+/// Collect the structs declared across all files, sorted alphabetically. The position in this list is the message index
+pub fn sorted_struct_definitions(file_descriptions: &Vec<RuneFileDescription>) -> Vec<StructDefinition> {
+    let mut struct_definitions: Vec<StructDefinition> = Vec::with_capacity(0x40);
+
+    for file in file_descriptions {
+        if !file.definitions.structs.is_empty() {
+            struct_definitions.append(&mut file.definitions.structs.clone());
+        }
+    }
+
+    struct_definitions.sort_by_key(|definition| definition.name.to_ascii_uppercase());
+
+    struct_definitions
+}
+
+/// Check whether a file declares an enum, bitfield or struct with the given name
+pub fn declares_type(file: &RuneFileDescription, name: &str) -> bool {
+    file.definitions.enums.iter().any(|definition| definition.name == name)
+        || file.definitions.bitfields.iter().any(|definition| definition.name == name)
+        || file.definitions.structs.iter().any(|definition| definition.name == name)
+}
+
+/// Whether the include refers to the file, either by name or by its path relative to the input folder
+pub fn is_included_file(file: &RuneFileDescription, include_definition: &IncludeDefinition) -> bool {
+    file.name == include_definition.file || format!("{0}{1}", file.relative_path, file.name) == include_definition.file
+}
+
+/// Path of the outputs of a file relative to the output folder, without extension. With --flatten all outputs are placed in the
+/// output folder itself, with the directories of the file joined into its name with underscores, so sub/dir/foo becomes sub_dir_foo
+pub fn output_base_name(file: &RuneFileDescription, configurations: &CompileConfigurations) -> String {
+    match configurations.flatten {
+        true => format!("{0}{1}", file.relative_path.replace(['/', '\\'], "_"), file.name),
+        false => format!("{0}{1}", file.relative_path, file.name)
+    }
+}
+
+/// Name the outputs of a file are included by from its other outputs, which are placed in the same directory
+pub fn sibling_base_name(file: &RuneFileDescription, configurations: &CompileConfigurations) -> String {
+    match configurations.flatten {
+        true => output_base_name(file, configurations),
+        false => file.name.clone()
+    }
+}
+
+/// Name an included file is included by, without extension. Includes are output as written in the Rune file, unless the outputs are
+/// flattened, which renames the outputs of files in directories
+pub fn include_base_name(include_definition: &IncludeDefinition, file_descriptions: &[RuneFileDescription], configurations: &CompileConfigurations) -> String {
+    let included_file: Option<&RuneFileDescription> = file_descriptions.iter().find(|file| is_included_file(file, include_definition));
+
+    match (configurations.flatten, included_file) {
+        (true, Some(included_file)) => output_base_name(included_file, configurations),
+        _ => include_definition.file.clone()
+    }
+}
+
+/// Collect the file and all files it transitively includes
+pub fn collect_dependencies<'a>(file: &'a RuneFileDescription, file_descriptions: &'a Vec<RuneFileDescription>, dependencies: &mut Vec<&'a RuneFileDescription>) {
+    if dependencies.iter().any(|dependency| std::ptr::eq(*dependency, file)) {
+        return;
+    }
+
+    dependencies.push(file);
+
+    for include_definition in &file.definitions.includes {
+        for included_file in file_descriptions {
+            if is_included_file(included_file, include_definition) {
+                collect_dependencies(included_file, file_descriptions, dependencies);
+            }
+        }
+    }
+}
+
+/// Find the definition of a user defined type, with the members of structs linked as well. The structs being linked are tracked,
+/// as a struct containing itself by value, directly or through other structs, would be linked, and sized, forever
+fn find_user_definition(name: &str, file_descriptions: &Vec<RuneFileDescription>, linking: &mut Vec<String>) -> Result<UserDefinitionLink, CompilerError> {
+    for file in file_descriptions {
+        if let Some(bitfield_definition) = file.definitions.bitfields.iter().find(|definition| definition.name == name) {
+            return Ok(UserDefinitionLink::BitfieldLink(bitfield_definition.clone()));
+        }
+
+        if let Some(enum_definition) = file.definitions.enums.iter().find(|definition| definition.name == name) {
+            return Ok(UserDefinitionLink::EnumLink(enum_definition.clone()));
+        }
+
+        if let Some(struct_definition) = file.definitions.structs.iter().find(|definition| definition.name == name) {
+            if let Some(position) = linking.iter().position(|linked_name| linked_name == name) {
+                error!("Struct {0} contains itself through {1} -> {0}, making its size infinite!", name, linking[position..].join(" -> "));
+                return Err(CompilerError::MalformedSource);
+            }
+
+            let mut definition_copy: StructDefinition = struct_definition.clone();
+
+            linking.push(String::from(name));
+            link_members(&mut definition_copy.members, file_descriptions, linking)?;
+            linking.pop();
+
+            return Ok(UserDefinitionLink::StructLink(definition_copy));
+        }
+    }
+
+    error!("Found no user definition for type {0}!", name);
+    Err(CompilerError::MalformedSource)
+}
+
+/// Link every user defined member, including arrays, to its definition
+fn link_members(members: &mut Vec<StructMember>, file_descriptions: &Vec<RuneFileDescription>, linking: &mut Vec<String>) -> Result<(), CompilerError> {
+    for member in members {
+        let name: &String = match &member.data_type {
+            FieldType::UserDefined(name) | FieldType::Array(ArrayType::UserDefined(name), _) => name,
+            _ => continue
+        };
+
+        member.user_definition_link = find_user_definition(name, file_descriptions, linking)?;
+    }
+
+    Ok(())
+}
+
+/// Link struct members to the definitions of their types. The parser only links single members, leaving arrays of enums, bitfields
+/// and structs unlinked, so all links are redone here
+pub fn link_user_definitions(file_descriptions: &mut Vec<RuneFileDescription>) -> Result<(), CompilerError> {
+    let immutable_reference: Vec<RuneFileDescription> = file_descriptions.clone();
+
+    for file in file_descriptions {
+        for struct_definition in &mut file.definitions.structs {
+            let mut linking: Vec<String> = vec![struct_definition.name.clone()];
+            link_members(&mut struct_definition.members, &immutable_reference, &mut linking)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Convert the relative path of a file to a Pascal case namespace, e.g. net/link_layer/ to NetLinkLayer
+pub fn path_namespace(relative_path: &str) -> String {
+    let mut namespace: String = String::with_capacity(0x40);
+
+    for word in relative_path.split(|character: char| !character.is_ascii_alphanumeric()) {
+        let mut characters = word.chars();
+
+        if let Some(first) = characters.next() {
+            namespace.push(first.to_ascii_uppercase());
+            namespace.extend(characters);
+        }
+    }
+
+    namespace
+}
+
+/// Prefix the enums, bitfields and structs of every file with the given namespace, along with every member referring to them
+fn apply_namespaces(file_descriptions: &mut Vec<RuneFileDescription>, file_namespace: impl Fn(&RuneFileDescription) -> String) {
+    // The parser guarantees type names are unique across all files, so references can be renamed without knowing their file
+    let mut renames: Vec<(String, String)> = Vec::with_capacity(0x40);
+
+    for file in file_descriptions.iter_mut() {
+        let namespace: String = file_namespace(file);
+
+        if namespace.is_empty() {
+            continue;
+        }
+
+        let names = file
+            .definitions
+            .enums
+            .iter_mut()
+            .map(|definition| &mut definition.name)
+            .chain(file.definitions.bitfields.iter_mut().map(|definition| &mut definition.name))
+            .chain(file.definitions.structs.iter_mut().map(|definition| &mut definition.name));
+
+        for name in names {
+            let namespaced: String = format!("{0}{1}", namespace, name);
+            renames.push((name.clone(), namespaced.clone()));
+            *name = namespaced;
+        }
+    }
+
+    for file in file_descriptions {
+        for struct_definition in &mut file.definitions.structs {
+            for member in &mut struct_definition.members {
+                let name: &mut String = match &mut member.data_type {
+                    FieldType::UserDefined(name) | FieldType::Array(ArrayType::UserDefined(name), _) => name,
+                    _ => continue
+                };
+
+                if let Some((_, namespaced)) = renames.iter().find(|(original, _)| original == name) {
+                    *name = namespaced.clone();
+                }
+            }
+        }
+    }
+}
+
+/// Prefix the enums, bitfields and structs of every file with the namespace of its directory, along with every member referring to
+/// them. Must run before linking, as the links are looked up by name
+pub fn apply_path_namespaces(file_descriptions: &mut Vec<RuneFileDescription>) {
+    apply_namespaces(file_descriptions, |file| path_namespace(&file.relative_path));
+}
+
+/// Prefix the enums, bitfields and structs of all files with the user given prefix, e.g. acme makes Header acme_header_t. Must run
+/// after the path namespaces, so the user prefix comes first, and before linking
+pub fn apply_prefix(file_descriptions: &mut Vec<RuneFileDescription>, prefix: &str) {
+    let namespace: String = path_namespace(prefix);
+    apply_namespaces(file_descriptions, |_| namespace.clone());
+}
+
 // C Configuration
 // ————————————————
 
@@ -62,6 +407,9 @@ pub struct CompileConfigurations {
     /// Which architecture to optimize for
     pub architecture: Architecture,
 
+    /// Byte order of the target - Defaults to native, detected by the C compiler
+    pub endianness: Endianness,
+
     /// Whether or not to pack message data structures
     pub pack_data: bool,
 
@@ -75,7 +423,240 @@ pub struct CompileConfigurations {
     pub sort: bool,
 
     /// Specifies which C standard the output source should comply with
-    pub c_standard: CStandard
+    pub c_standard: CStandard,
+
+    /// Specifies which compiler the output source is built with - Defaults to GCC
+    pub compiler: TargetCompiler,
+
+    /// How headers are guarded against multiple inclusion - Defaults to #ifndef guards
+    pub include_guard_style: IncludeGuardStyle,
+
+    /// Indentation of the generated code - Defaults to four spaces
+    pub indentation: Indentation,
+
+    /// Line endings of the generated files - Defaults to LF
+    pub line_ending: LineEnding,
+
+    /// Whether to include verifier parsing data in descriptors - Defaults to true
+    pub verifier_support: bool,
+
+    /// Whether to output the struct descriptors used by the Rune parser - Defaults to true
+    pub descriptors: bool,
+
+    /// Whether to store enum members as their backing integer type, guaranteeing their size at the cost of type safety - Defaults to false
+    pub strict_enum_width: bool,
+
+    /// Whether to check struct sizes against their estimates before C11, where static_assert is not available - Defaults to false
+    pub strict_size_checks: bool,
+
+    /// Whether to output memcpy based member accessors for packed structs - Defaults to false
+    pub safe_packed_access: bool,
+
+    /// Whether to output getters for all struct members - Defaults to false
+    pub getters: bool,
+
+    /// Whether to output precondition checks into generated functions, compiled in when RUNE_DEBUG is defined - Defaults to false
+    pub debug_checks: bool,
+
+    /// Whether to output tables of struct members by name, along with lookup functions - Defaults to false
+    pub named_fields: bool,
+
+    /// Whether to output serialize and deserialize functions for every struct - Defaults to false
+    pub codec: bool,
+
+    /// Whether to output checksum functions for every struct, and verifier functions for structs with a verifier field - Defaults to false
+    pub verifiers: bool,
+
+    /// Whether to output functions returning the Rune identifier of enum values - Defaults to false
+    pub enum_names: bool,
+
+    /// Whether to output a C++ header per Rune file, wrapping every struct in a class - Defaults to false
+    pub cpp: bool,
+
+    /// Struct members, written as <Struct>.<Member>, whose struct is output as an anonymous struct within the parent - Defaults to empty
+    pub inline_members: Vec<String>,
+
+    /// Struct members, written as <Struct>.<Member>=<Alignment>, aligned beyond their natural alignment - Defaults to empty
+    pub member_alignments: Vec<String>,
+
+    /// Integer struct members, written as <Struct>.<Member>=Q<Integer bits>.<Fractional bits>, holding fixed-point values - Defaults to empty
+    pub fixed_point_members: Vec<String>,
+
+    /// Whether to output padding as named members, instead of leaving it to the C compiler - Defaults to false
+    pub explicit_padding: bool,
+
+    /// Whether to output the padding of bitfields as an unnamed member, instead of a member named padding - Defaults to false
+    pub anonymous_bitfield_padding: bool,
+
+    /// Whether to adjust the output towards MISRA C compliance - Defaults to false
+    pub misra: bool,
+
+    /// Comment block to output at the top of every generated file - Defaults to None
+    pub banner: Option<String>,
+
+    /// Whether to output a program comparing the actual struct layouts against the estimated ones - Defaults to false
+    pub abi_probe: bool,
+
+    /// Whether to output a libFuzzer target calling the deserialize functions - Defaults to false
+    pub fuzz: bool,
+
+    /// Whether to output length-prefixed TLV descriptors and the functions using them - Defaults to false
+    pub tlv: bool,
+
+    /// Whether TLV encodes integer members as variable-length integers - Defaults to false
+    pub varint: bool,
+
+    /// Whether to output a header recording the generation options - Defaults to false
+    pub config_header: bool,
+
+    /// Whether to output a rune.h header defining the descriptor types - Defaults to false
+    pub runtime_header: bool,
+
+    /// Path of the runic definitions header, relative to the output folder - Defaults to runic_definitions.h
+    pub definitions_output: String,
+
+    /// Names of the structs to output, together with the structs they depend on. All structs are output if empty - Defaults to empty
+    pub only: Vec<String>,
+
+    /// Names of the structs to leave out of the output - Defaults to empty
+    pub exclude: Vec<String>,
+
+    /// Glob patterns of the Rune files to parse, but not output - Defaults to empty
+    pub exclude_files: Vec<String>,
+
+    /// Whether the comments of definitions are output with doxygen tags - Defaults to false
+    pub doxygen_comments: bool,
+
+    /// Whether all outputs are placed in the output folder itself, with the directories of their Rune file joined into their name - Defaults to false
+    pub flatten: bool,
+
+    /// Whether to prefix the type names and header guards of each file with the namespace of its directory - Defaults to false
+    pub namespace_from_path: bool,
+
+    /// Prefix added in front of every type name and header guard - Defaults to None
+    pub prefix: Option<String>,
+
+    /// Whether to output a single header and source for all Rune files - Defaults to false
+    pub amalgamate: bool,
+
+    /// Name of a TypeScript module to output, mirroring the structs - Defaults to None
+    pub typescript: Option<String>,
+
+    /// Name of a proto3 schema to output, mirroring the structs - Defaults to None
+    pub proto: Option<String>,
+
+    /// Whether to only output a header with protocol constants, and the name of said header - Defaults to None
+    pub constants_only: Option<String>,
+
+    /// Estimated sizes of the structs by name, filled in by CConfigurations::parse so nested structs are only sized once - Defaults to empty
+    pub struct_sizes: HashMap<String, u64>
+}
+
+impl CompileConfigurations {
+    /// Accessors are only needed when the data is packed, as members are otherwise aligned
+    pub fn safe_packed_access(&self) -> bool {
+        self.safe_packed_access && self.pack_data
+    }
+
+    /// Suffix of unsigned integer literals. MISRA C requires it on all unsigned constants (rule 7.2)
+    pub fn unsigned_suffix(&self) -> &'static str {
+        match self.misra {
+            true => "U",
+            false => ""
+        }
+    }
+
+    /// Whether the struct member is output as an anonymous struct, with its members accessed directly through the parent
+    pub fn is_inlined(&self, struct_name: &str, member: &StructMember) -> bool {
+        self.inline_members.iter().any(|inline_member| *inline_member == format!("{0}.{1}", struct_name, member.identifier))
+    }
+
+    /// Alignment forced onto the struct member, if any. The values are checked by validate_member_alignments
+    pub fn forced_alignment(&self, struct_name: &str, member: &StructMember) -> Option<u64> {
+        let prefix: String = format!("{0}.{1}=", struct_name, member.identifier);
+
+        self.member_alignments
+            .iter()
+            .find_map(|member_alignment| member_alignment.strip_prefix(&prefix))
+            .and_then(|alignment| alignment.parse().ok())
+    }
+
+    /// Q format of the struct member, if it holds fixed-point values. The values are checked by validate_fixed_point_members
+    pub fn fixed_point_format(&self, struct_name: &str, member: &StructMember) -> Option<FixedPointFormat> {
+        let prefix: String = format!("{0}.{1}=", struct_name, member.identifier);
+
+        self.fixed_point_members
+            .iter()
+            .find_map(|fixed_point_member| fixed_point_member.strip_prefix(&prefix))
+            .and_then(FixedPointFormat::from_string)
+    }
+
+    /// Extension of the output headers or sources, given as h or c. The amalgamated header and source do not mirror a single Rune
+    /// file, so they are not marked as one
+    pub fn output_extension(&self, extension: &str) -> String {
+        match self.amalgamate {
+            true => String::from(extension),
+            false => format!("rune.{0}", extension)
+        }
+    }
+
+    /// Whether structs are packed and placed in sections with GCC style __attribute__ declarations. This requires both a compiler
+    /// supporting them and a GNU dialect of C, as the ISO standards do not know them. Otherwise structs are packed with #pragma pack
+    pub fn allows_attributes(&self) -> bool {
+        self.compiler.allows_attributes() && self.c_standard.allows_gnu_extensions()
+    }
+
+    /// Whether any functions are generated into the headers
+    pub fn emits_functions(&self) -> bool {
+        self.safe_packed_access() || self.tlv || self.getters || self.named_fields || self.codec || self.verifiers
+    }
+}
+
+/// Q format of a fixed-point value, stored as an integer scaled by 2 to the power of its fractional bits. Written as
+/// Q<Integer bits>.<Fractional bits> for signed values, with the sign bit counted as an integer bit, or UQ for unsigned values
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedPointFormat {
+    pub signed:          bool,
+    pub integer_bits:    u64,
+    pub fractional_bits: u64
+}
+
+impl FixedPointFormat {
+    pub fn from_string(string: &str) -> Option<FixedPointFormat> {
+        let (signed, bits): (bool, &str) = match (string.strip_prefix("UQ"), string.strip_prefix('Q')) {
+            (Some(bits), _) => (false, bits),
+            (None, Some(bits)) => (true, bits),
+            (None, None) => return None
+        };
+
+        let (integer_bits, fractional_bits): (&str, &str) = bits.split_once('.')?;
+
+        Some(FixedPointFormat {
+            signed,
+            integer_bits: integer_bits.parse().ok()?,
+            fractional_bits: fractional_bits.parse().ok()?
+        })
+    }
+
+    /// Size of the integer storing the value, in bits
+    pub fn bit_size(&self) -> u64 {
+        self.integer_bits + self.fractional_bits
+    }
+}
+
+impl Display for FixedPointFormat {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            formatter,
+            "{0}{1}.{2}",
+            match self.signed {
+                true => "Q",
+                false => "UQ"
+            },
+            self.integer_bits,
+            self.fractional_bits
+        )
+    }
 }
 
 pub struct CConfigurations {
@@ -89,7 +670,10 @@ pub struct CConfigurations {
     pub parser_index_type_size: usize,
 
     // Largest encountered declared message index
-    pub largest_message_index: usize
+    pub largest_message_index: usize,
+
+    // Amount of declared structs, each with a descriptor
+    pub message_count: usize
 }
 
 impl CConfigurations {
@@ -97,6 +681,10 @@ impl CConfigurations {
         let mut amount_of_messages: usize = 0;
         let mut largest_message_size: usize = 0;
         let mut largest_message_index: usize = 0;
+        let mut uses_verifier: bool = false;
+
+        // Sizes are cached as they are estimated, so the sizes of nested structs are looked up instead of estimated again
+        let mut compiler_configurations: CompileConfigurations = configurations.clone();
 
         // Get the largest overall message size, and the amount of messages
         for file in file_descriptions {
@@ -104,7 +692,8 @@ impl CConfigurations {
             amount_of_messages += file.definitions.structs.len();
 
             for struct_definition in &file.definitions.structs {
-                let estimated_size: usize = struct_definition.estimate_size(configurations)? as usize;
+                let estimated_size: usize = struct_definition.estimate_size(&compiler_configurations)? as usize;
+                compiler_configurations.struct_sizes.insert(struct_definition.name.clone(), estimated_size as u64);
 
                 if estimated_size > largest_message_size {
                     largest_message_size = estimated_size;
@@ -114,10 +703,24 @@ impl CConfigurations {
                     if member.index.value() as usize > largest_message_index {
                         largest_message_index = member.index.value() as usize;
                     }
+
+                    if member.index.is_verifier() {
+                        // Omitting verifier support would silently drop the verifier field from the parsing metadata
+                        if !configurations.verifier_support {
+                            error!("Struct {0} declares verifier field {1}, but verifier support was disabled!", struct_definition.name, member.identifier);
+                            return Err(CompilerError::ConfigurationError);
+                        }
+
+                        uses_verifier = true;
+                    }
                 }
             }
         }
 
+        if configurations.verifier_support && !uses_verifier {
+            debug!("No struct declares a verifier field. Descriptors can be shrunk by passing --no-verifier-support");
+        }
+
         // Get the unsigned integer size needed to describe the number of messages
         let parser_index_type_size: usize = match amount_of_messages {
             0x00000000..=0x000000FF => 1,
@@ -129,6 +732,11 @@ impl CConfigurations {
 
         // Field size type and offset size type will be based on the largest message size
         let message_size_type_size: usize = match largest_message_size {
+            // Schemas with only enums, bitfields and defines have no messages to describe
+            0 if amount_of_messages == 0 => {
+                debug!("No structs declared. Outputting enums, bitfields and defines only");
+                1
+            },
             0 => {
                 error!("Largest message had size 0! Something went horribly wrong!");
                 return Err(CompilerError::ConfigurationError);
@@ -144,24 +752,110 @@ impl CConfigurations {
         let field_offset_type_size: usize = message_size_type_size;
 
         Ok(CConfigurations {
-            compiler_configurations: configurations.clone(),
+            compiler_configurations,
             field_size_type_size,
             field_offset_type_size,
             message_size_type_size,
             parser_index_type_size,
-            largest_message_index
+            largest_message_index,
+            message_count: amount_of_messages
         })
     }
 }
 
+// Struct member helper functions
+// ———————————————————————————————
+
+/// Get the struct members as they are stored in memory, in the order they are placed in the struct
+pub fn struct_members(configurations: &CConfigurations, struct_definition: &StructDefinition) -> Result<Vec<StructMember>, CompilerError> {
+    let members: Vec<StructMember> = match (configurations.compiler_configurations.explicit_padding, configurations.compiler_configurations.sort) {
+        (true, _) => struct_definition.padded_members(&configurations.compiler_configurations)?,
+        (false, true) => struct_definition.sort_members(&configurations.compiler_configurations)?,
+        (false, false) => struct_definition.members.clone()
+    };
+
+    Ok(members.iter().map(|member| member.storage_member(&configurations.compiler_configurations)).collect())
+}
+
+/// Get the definition of the struct output in place of an inlined member
+pub fn inlined_definition(member: &StructMember) -> Result<&StructDefinition, CompilerError> {
+    match &member.user_definition_link {
+        UserDefinitionLink::StructLink(struct_definition) => Ok(struct_definition),
+        _ => {
+            error!("Member {0} is inlined, but is not linked to a struct!", member.identifier);
+            Err(CompilerError::LogicError)
+        }
+    }
+}
+
+/// Give the padding members of an inlined struct a name unique within the parent, as every struct numbers its padding from 0
+pub fn prefix_inlined_padding(member: &StructMember, inlined_member: &mut StructMember) {
+    if inlined_member.identifier.starts_with('_') {
+        inlined_member.identifier = format!("_{0}{1}", pascal_to_snake_case(&member.identifier), inlined_member.identifier);
+    }
+}
+
+/// Get the members of an inlined member as they are stored in memory, with the members of nested inlined members in their place
+pub fn inlined_members(configurations: &CConfigurations, member: &StructMember) -> Result<Vec<StructMember>, CompilerError> {
+    let struct_definition: &StructDefinition = inlined_definition(member)?;
+    let mut members: Vec<StructMember> = Vec::with_capacity(struct_definition.members.len());
+
+    for mut inlined_member in struct_members(configurations, struct_definition)? {
+        prefix_inlined_padding(member, &mut inlined_member);
+
+        match configurations.compiler_configurations.is_inlined(&struct_definition.name, &inlined_member) {
+            true => members.extend(inlined_members(configurations, &inlined_member)?),
+            false => members.push(inlined_member)
+        }
+    }
+
+    Ok(members)
+}
+
+/// Get the C name to pass to offsetof for a struct member. An anonymous struct has no name, but starts at its first member
+pub fn offset_member_name(configurations: &CConfigurations, struct_name: &str, member: &StructMember) -> Result<String, CompilerError> {
+    match configurations.compiler_configurations.is_inlined(struct_name, member) {
+        true => match inlined_members(configurations, member)?.first() {
+            Some(first_member) => Ok(pascal_to_snake_case(&first_member.identifier)),
+            None => {
+                error!("Inlined member {0} of struct {1} has no members!", member.identifier, struct_name);
+                Err(CompilerError::LogicError)
+            }
+        },
+        false => Ok(pascal_to_snake_case(&member.identifier))
+    }
+}
+
 // Numeric value helper functions
 // ———————————————————————————————
 
+/// Format a float value as a C literal. Uses the shortest representation that round-trips, with the 'f' suffix for single precision to avoid implicit narrowing from double
+pub fn c_float_literal(value: f64, primitive: &Primitive) -> String {
+    match primitive {
+        Primitive::F32 => format!("{0:?}f", value as f32),
+        _ => format!("{0:?}", value)
+    }
+}
+
 pub trait CNumericValue {
     fn requires_size(&self) -> u64;
+    fn to_c_literal(&self, is_unsigned: bool, configurations: &CompileConfigurations) -> String;
 }
 
 impl CNumericValue for NumericLiteral {
+    /// Format the value as a C literal. Binary literals, a language extension before C23, are output as hexadecimal in MISRA mode (rule
+    /// 1.2) and for the ISO standards not allowing them. In MISRA mode unsigned values also get a 'U' suffix (rule 7.2)
+    fn to_c_literal(&self, is_unsigned: bool, configurations: &CompileConfigurations) -> String {
+        let binary_as_hexadecimal: bool = configurations.misra || !configurations.c_standard.allows_binary_literals();
+
+        match self {
+            NumericLiteral::PositiveInteger(value, NumeralSystem::Binary) if binary_as_hexadecimal => format!("0x{0:02X}{1}", value, configurations.unsigned_suffix()),
+            NumericLiteral::PositiveInteger(_, _) if is_unsigned && configurations.misra => format!("{0}{1}", self, configurations.unsigned_suffix()),
+            NumericLiteral::NegativeInteger(value, NumeralSystem::Binary) if binary_as_hexadecimal => format!("-0x{0:02X}", value.unsigned_abs()),
+            _ => self.to_string()
+        }
+    }
+
     fn requires_size(&self) -> u64 {
         let leading_zeroes = match self {
             NumericLiteral::Boolean(_) => return 1,
@@ -184,9 +878,10 @@ impl CNumericValue for NumericLiteral {
 
 pub trait CPrimitive {
     fn c_size(&self) -> u64;
-    fn c_initializer(&self, c_standard: &CStandard) -> String;
-    fn create_c_variable(&self, name: &str, spacing: usize, c_standard: &CStandard) -> Result<String, CompilerError>;
+    fn c_initializer(&self, configurations: &CompileConfigurations) -> String;
+    fn create_c_variable(&self, name: &str, spacing: usize, configurations: &CompileConfigurations) -> Result<String, CompilerError>;
     fn to_c_type(&self, c_standard: &CStandard) -> Result<String, CompilerError>;
+    fn c_array_suffix(&self, configurations: &CompileConfigurations) -> String;
 }
 
 impl CPrimitive for Primitive {
@@ -204,48 +899,55 @@ impl CPrimitive for Primitive {
         }
     }
 
-    fn c_initializer(&self, c_standard: &CStandard) -> String {
+    fn c_initializer(&self, configurations: &CompileConfigurations) -> String {
         match self {
-            Primitive::Bool => match c_standard.allows_boolean() {
+            Primitive::Bool => match configurations.c_standard.allows_boolean() {
                 true => String::from("false"),
+                false => String::from("RUNE_FALSE")
+            },
+
+            // MISRA C requires initializers of the same essential type (rule 10.3)
+            Primitive::Char => match configurations.misra {
+                true => String::from("'\\0'"),
                 false => String::from("0")
             },
 
-            Primitive::Char | Primitive::I8 | Primitive::U8 | Primitive::I16 | Primitive::U16 | Primitive::I32 | Primitive::U32 | Primitive::I64 | Primitive::U64 => String::from("0"),
+            Primitive::I8 | Primitive::I16 | Primitive::I32 | Primitive::I64 => String::from("0"),
 
-            Primitive::F32 | Primitive::F64 => String::from("0.0"),
+            Primitive::U8 | Primitive::U16 | Primitive::U32 | Primitive::U64 => format!("0{0}", configurations.unsigned_suffix()),
+
+            Primitive::F32 | Primitive::F64 => c_float_literal(0.0, self),
 
             // 128 bit integers are converted into 16 byte arrays in this implementation, due to lack of good 128 bit int support
-            Primitive::I128 | Primitive::U128 => String::from("{ 0 }")
+            Primitive::I128 | Primitive::U128 => format!("{{ 0{0} }}", configurations.unsigned_suffix())
         }
     }
 
-    fn create_c_variable(&self, name: &str, spacing: usize, c_standard: &CStandard) -> Result<String, CompilerError> {
-        match self {
-            Primitive::Bool
-            | Primitive::Char
-            | Primitive::I8
-            | Primitive::U8
-            | Primitive::I16
-            | Primitive::U16
-            | Primitive::F32
-            | Primitive::I32
-            | Primitive::U32
-            | Primitive::F64
-            | Primitive::I64
-            | Primitive::U64 => Ok(format!("{0} {1}{2}", self.to_c_type(c_standard)?, spaces(spacing), name)),
+    fn create_c_variable(&self, name: &str, spacing: usize, configurations: &CompileConfigurations) -> Result<String, CompilerError> {
+        Ok(format!(
+            "{0} {1}{2}{3}",
+            self.to_c_type(&configurations.c_standard)?,
+            spaces(spacing),
+            name,
+            self.c_array_suffix(configurations)
+        ))
+    }
 
-            // 128 bit integers get converted into a byte array
-            Primitive::I128 | Primitive::U128 => Ok(format!("{0} {1}{2}[{3}]", Primitive::U8.to_c_type(c_standard)?, spaces(spacing), name, self.c_size()))
+    /// Array suffix completing the type. 128 bit integers are stored as 16 byte arrays, for lack of good 128 bit integer support
+    fn c_array_suffix(&self, configurations: &CompileConfigurations) -> String {
+        match self {
+            Primitive::I128 | Primitive::U128 => format!("[{0}{1}]", self.c_size(), configurations.unsigned_suffix()),
+            _ => String::new()
         }
     }
 
     fn to_c_type(&self, c_standard: &CStandard) -> Result<String, CompilerError> {
         let string: String = match self {
             // 8 Bit
+            // rune_bool_t is defined in runic_definitions.h when <stdbool.h> is not available
             Primitive::Bool => String::from(match c_standard.allows_boolean() {
                 true => "bool",
-                false => "char"
+                false => "rune_bool_t"
             }),
             Primitive::Char => String::from("char"),
             Primitive::I8 => String::from(match c_standard.allows_integer_types() {
@@ -295,28 +997,53 @@ impl CPrimitive for Primitive {
                 }
             }),
 
-            // 128 Bit - Devolve into unsigned 16 Byte arrays
+            // 128 Bit - Devolve into unsigned 16 Byte arrays, completed by the array suffix
             Primitive::I128 | Primitive::U128 => String::from(match c_standard.allows_integer_types() {
-                true => "uint8_t[16]",
-                false => "unsigned char[16]"
+                true => "uint8_t",
+                false => "unsigned char"
             })
         };
         Ok(string)
     }
 }
 
-// Array Type
+// Array Type
+// ———————————
+
+pub trait CArrayType {
+    fn to_c_type(&self, c_standard: &CStandard) -> Result<String, CompilerError>;
+    fn c_array_suffix(&self, configurations: &CompileConfigurations) -> String;
+}
+
+impl CArrayType for ArrayType {
+    fn to_c_type(&self, c_standard: &CStandard) -> Result<String, CompilerError> {
+        match self {
+            ArrayType::Primitive(primitive) => primitive.to_c_type(c_standard),
+            ArrayType::UserDefined(definition) => Ok(format!("{0}_t", pascal_to_snake_case(definition)))
+        }
+    }
+
+    fn c_array_suffix(&self, configurations: &CompileConfigurations) -> String {
+        match self {
+            ArrayType::Primitive(primitive) => primitive.c_array_suffix(configurations),
+            ArrayType::UserDefined(_) => String::new()
+        }
+    }
+}
+
+// Array size
 // ———————————
 
-pub trait CArrayType {
-    fn to_c_type(&self, c_standard: &CStandard) -> Result<String, CompilerError>;
+pub trait CArraySize {
+    fn to_c_literal(&self, configurations: &CompileConfigurations) -> String;
 }
 
-impl CArrayType for ArrayType {
-    fn to_c_type(&self, c_standard: &CStandard) -> Result<String, CompilerError> {
+impl CArraySize for ArraySize {
+    fn to_c_literal(&self, configurations: &CompileConfigurations) -> String {
         match self {
-            ArrayType::Primitive(primitive) => primitive.to_c_type(c_standard),
-            ArrayType::UserDefined(definition) => Ok(format!("{0}_t", pascal_to_snake_case(definition)))
+            ArraySize::Integer(value, numeral_system) => NumericLiteral::PositiveInteger(*value, *numeral_system).to_c_literal(true, configurations),
+            // Defines carry their own suffix
+            ArraySize::UserDefinition(definition) => definition.name.clone()
         }
     }
 }
@@ -325,16 +1052,23 @@ impl CArrayType for ArrayType {
 // ———————————————————
 
 pub trait CFieldType {
-    fn c_initializer(&self, c_standard: &CStandard) -> Result<String, CompilerError>;
-    fn create_c_variable(&self, name: &str, spacing: usize, c_standard: &CStandard) -> Result<String, CompilerError>;
+    fn c_initializer(&self, configurations: &CompileConfigurations) -> Result<String, CompilerError>;
+    fn create_c_variable(&self, name: &str, spacing: usize, configurations: &CompileConfigurations) -> Result<String, CompilerError>;
 }
 
 impl CFieldType for FieldType {
-    fn create_c_variable(&self, name: &str, spacing: usize, c_standard: &CStandard) -> Result<String, CompilerError> {
+    fn create_c_variable(&self, name: &str, spacing: usize, configurations: &CompileConfigurations) -> Result<String, CompilerError> {
         match self {
-            FieldType::Primitive(primitive) => primitive.create_c_variable(name, spacing, c_standard),
+            FieldType::Primitive(primitive) => primitive.create_c_variable(name, spacing, configurations),
             FieldType::UserDefined(string) => Ok(format!("{0}_t {1}{2}", pascal_to_snake_case(string), spaces(spacing), name)),
-            FieldType::Array(field_type, field_size) => Ok(format!("{0} {1}{2}[{3}]", field_type.to_c_type(c_standard)?, spaces(spacing), name, field_size)),
+            FieldType::Array(field_type, field_size) => Ok(format!(
+                "{0} {1}{2}[{3}]{4}",
+                field_type.to_c_type(&configurations.c_standard)?,
+                spaces(spacing),
+                name,
+                field_size.to_c_literal(configurations),
+                field_type.c_array_suffix(configurations)
+            )),
             FieldType::Empty => {
                 error!("Cannot create an empty field!");
                 Err(CompilerError::LogicError)
@@ -342,18 +1076,18 @@ impl CFieldType for FieldType {
         }
     }
 
-    fn c_initializer(&self, c_standard: &CStandard) -> Result<String, CompilerError> {
+    fn c_initializer(&self, configurations: &CompileConfigurations) -> Result<String, CompilerError> {
         let string = match self {
-            FieldType::Primitive(primitive) => primitive.c_initializer(c_standard),
+            FieldType::Primitive(primitive) => primitive.c_initializer(configurations),
             FieldType::UserDefined(name) => format!("{0}_INIT", pascal_to_uppercase(name)),
             FieldType::Array(array_type, _) => format!(
                 "{{ {0} }}",
                 match array_type {
                     // Special 128 bit case
                     ArrayType::Primitive(primitive) if *primitive == Primitive::I128 || *primitive == Primitive::U128 => {
-                        String::from("0")
+                        format!("0{0}", configurations.unsigned_suffix())
                     },
-                    ArrayType::Primitive(primitive) => primitive.c_initializer(c_standard),
+                    ArrayType::Primitive(primitive) => primitive.c_initializer(configurations),
                     ArrayType::UserDefined(name) => format!("{0}_INIT", pascal_to_uppercase(name))
                 }
             ),
@@ -371,8 +1105,14 @@ impl CFieldType for FieldType {
 
 pub trait CStructMember {
     fn c_size(&self) -> Result<u64, CompilerError>;
-    fn c_size_definition(&self, c_standard: &CStandard) -> Result<String, CompilerError>;
+    fn c_size_definition(&self, configurations: &CompileConfigurations) -> Result<String, CompilerError>;
     fn index_empty(index: u64) -> Result<StructMember, CompilerError>;
+    fn storage_member(&self, configurations: &CompileConfigurations) -> StructMember;
+    fn padding(number: usize, size: u64) -> StructMember;
+    fn array_length(&self, array_size: &ArraySize) -> Result<u64, CompilerError>;
+    fn layout_size(&self, configurations: &CompileConfigurations) -> Result<u64, CompilerError>;
+    fn layout_alignment(&self, configurations: &CompileConfigurations) -> Result<u64, CompilerError>;
+    fn has_fixed_size(&self, configurations: &CompileConfigurations) -> bool;
 }
 
 impl CStructMember for StructMember {
@@ -380,10 +1120,10 @@ impl CStructMember for StructMember {
         // Check if value is positive and within the legal values (0 to and including 31)
         let field_index = match index {
             // Legal values
-            0..32 => FieldIndex::Numeric(index),
+            0..FieldIndex::LIMIT => FieldIndex::Numeric(index),
             // Higher than legal values
-            32.. => {
-                error!("Field index cannot have a value higher than 31!");
+            FieldIndex::LIMIT.. => {
+                error!("Field index cannot have a value higher than {0}!", FieldIndex::LIMIT - 1);
                 return Err(CompilerError::LogicError);
             }
         };
@@ -397,19 +1137,117 @@ impl CStructMember for StructMember {
         })
     }
 
-    fn c_size_definition(&self, c_standard: &CStandard) -> Result<String, CompilerError> {
+    /// Create a byte array member, filling the space the compiler would otherwise pad implicitly
+    fn padding(number: usize, size: u64) -> StructMember {
+        StructMember {
+            identifier:           format!("_pad{0}", number),
+            data_type:            FieldType::Array(ArrayType::Primitive(Primitive::U8), ArraySize::Integer(size, NumeralSystem::Decimal)),
+            index:                FieldIndex::Numeric(0), // Does not matter, as padding is not part of the descriptors
+            user_definition_link: UserDefinitionLink::NoLink,
+            comment:              None
+        }
+    }
+
+    /// Get the amount of elements of an array member
+    fn array_length(&self, array_size: &ArraySize) -> Result<u64, CompilerError> {
+        match array_size {
+            ArraySize::Integer(value, _) => Ok(*value),
+            ArraySize::UserDefinition(definition) => match &definition.value {
+                DefineValue::NumericLiteral(value) => match value {
+                    NumericLiteral::PositiveInteger(value, _) => Ok(*value),
+                    _ => {
+                        error!("Got \"{0:?}\" array size definition of an invalid type!", self.identifier);
+                        Err(CompilerError::MalformedSource)
+                    }
+                },
+                // Flag style defines cannot be used as dimensions
+                DefineValue::NoValue => {
+                    error!("Array size define {0} of member {1} has no value!", definition.name, self.identifier);
+                    Err(CompilerError::MalformedSource)
+                }
+            }
+        }
+    }
+
+    /// Get the size the member takes up in the struct. Nested structs take up their estimated size, including their own padding
+    fn layout_size(&self, configurations: &CompileConfigurations) -> Result<u64, CompilerError> {
+        let struct_definition: &StructDefinition = match &self.user_definition_link {
+            UserDefinitionLink::StructLink(struct_definition) => struct_definition,
+            _ => return self.c_size()
+        };
+
+        match &self.data_type {
+            FieldType::Array(_, array_size) => Ok(struct_definition.estimate_size(configurations)? * self.array_length(array_size)?),
+            _ => struct_definition.estimate_size(configurations)
+        }
+    }
+
+    /// Get the alignment of the member. Arrays align as their elements, and bitfields are always packed
+    fn layout_alignment(&self, configurations: &CompileConfigurations) -> Result<u64, CompilerError> {
+        let primitive_alignment = |primitive: &Primitive| match primitive {
+            // 128 bit values are stored as byte arrays
+            Primitive::I128 | Primitive::U128 => 1,
+            _ => estimate_alignment(primitive.c_size())
+        };
+
+        match (&self.data_type, &self.user_definition_link) {
+            (FieldType::Empty, _) => Ok(1),
+            (FieldType::Primitive(primitive), _) | (FieldType::Array(ArrayType::Primitive(primitive), _), _) => Ok(primitive_alignment(primitive)),
+            (_, UserDefinitionLink::NoLink) => {
+                error!("Found no definition link for item {0}!", self.identifier);
+                Err(CompilerError::MalformedSource)
+            },
+            (_, UserDefinitionLink::BitfieldLink(_)) => Ok(1),
+            (_, UserDefinitionLink::EnumLink(enum_definition)) => Ok(primitive_alignment(&enum_definition.backing_type)),
+            (_, UserDefinitionLink::StructLink(struct_definition)) => match configurations.pack_data {
+                true => Ok(1),
+                false => struct_definition.estimate_alignment(configurations)
+            }
+        }
+    }
+
+    /// Whether the size of the member is fixed by the generated code. Before C23, enums without strict enum width have an implementation defined size
+    fn has_fixed_size(&self, configurations: &CompileConfigurations) -> bool {
+        match &self.user_definition_link {
+            UserDefinitionLink::EnumLink(_) => configurations.c_standard.allows_enum_backing_type() || configurations.strict_enum_width,
+            UserDefinitionLink::StructLink(struct_definition) => struct_definition.has_fixed_layout(configurations),
+            _ => true
+        }
+    }
+
+    /// Get the member as it is stored in memory. With strict enum width, enum members are stored as their backing type to guarantee their size
+    fn storage_member(&self, configurations: &CompileConfigurations) -> StructMember {
+        let mut member: StructMember = self.clone();
+
+        if !configurations.strict_enum_width {
+            return member;
+        }
+
+        if let UserDefinitionLink::EnumLink(enum_definition) = &self.user_definition_link {
+            member.data_type = match &self.data_type {
+                FieldType::Array(_, array_size) => FieldType::Array(ArrayType::Primitive(enum_definition.backing_type.clone()), array_size.clone()),
+                _ => FieldType::Primitive(enum_definition.backing_type.clone())
+            };
+        }
+
+        member
+    }
+
+    fn c_size_definition(&self, configurations: &CompileConfigurations) -> Result<String, CompilerError> {
+        let c_standard: &CStandard = &configurations.c_standard;
+
         let size_string: String = match &self.data_type {
-            FieldType::Primitive(primitive) => format!("sizeof({0})", primitive.to_c_type(c_standard)?),
+            FieldType::Primitive(primitive) => format!("sizeof({0}{1})", primitive.to_c_type(c_standard)?, primitive.c_array_suffix(configurations)),
             FieldType::UserDefined(type_name) => format!("sizeof({0}_t)", pascal_to_snake_case(type_name)),
             FieldType::Array(array_type, array_size) => {
                 let type_string: String = match array_type {
-                    ArrayType::Primitive(primitive) => format!("sizeof({0})", primitive.to_c_type(c_standard)?),
+                    ArrayType::Primitive(primitive) => format!("sizeof({0}{1})", primitive.to_c_type(c_standard)?, primitive.c_array_suffix(configurations)),
                     ArrayType::UserDefined(name) => format!("sizeof({0}_t)", pascal_to_snake_case(name))
                 };
 
-                format!("({0} * {1})", type_string, array_size)
+                format!("({0} * {1})", type_string, array_size.to_c_literal(configurations))
             },
-            FieldType::Empty => String::from("0")
+            FieldType::Empty => format!("0{0}", configurations.unsigned_suffix())
         };
         Ok(size_string)
     }
@@ -419,22 +1257,7 @@ impl CStructMember for StructMember {
             // Calculate Array size based on (field type * field size)
             FieldType::Array(array_type, field_size) => {
                 // Get the array size first
-                let array_size: u64 = match field_size {
-                    ArraySize::Integer(value, _) => *value,
-                    ArraySize::UserDefinition(definition) => match &definition.value {
-                        DefineValue::NumericLiteral(value) => match value {
-                            NumericLiteral::PositiveInteger(value, _) => *value,
-                            _ => {
-                                error!("Got \"{0:?}\" array size definition of an invalid type!", self.identifier);
-                                return Err(CompilerError::MalformedSource);
-                            }
-                        },
-                        _ => {
-                            error!("Got \"{0}\" array size definition of an invalid type!", self.identifier);
-                            return Err(CompilerError::MalformedSource);
-                        }
-                    }
-                };
+                let array_size: u64 = self.array_length(field_size)?;
 
                 // Parse the byte size based on the array type
                 let total_size: u64 = match array_type {
@@ -487,166 +1310,624 @@ impl CStructMember for StructMember {
 // Struct definition methods
 // ——————————————————————————
 
+/// Estimate the alignment of a member from its size. Anything bigger than 4 bytes is assumed to align to 8 bytes, as a worst case scenario (64 bit targets)
+fn estimate_alignment(size: u64) -> u64 {
+    match size {
+        0..=1 => 1,
+        2 => 2,
+        3..=4 => 4,
+        5.. => 8
+    }
+}
+
+/// Placement of a member within a struct, as estimated by the layout model
+#[derive(Clone, Debug)]
+pub struct MemberLayout {
+    pub member: StructMember,
+    pub offset: u64,
+    pub size:   u64
+}
+
 pub trait CStructDefinition {
+    fn estimate_layout(&self, configurations: &CompileConfigurations) -> Result<Vec<MemberLayout>, CompilerError>;
     fn estimate_size(&self, configurations: &CompileConfigurations) -> Result<u64, CompilerError>;
+    fn estimate_alignment(&self, configurations: &CompileConfigurations) -> Result<u64, CompilerError>;
+    fn has_fixed_layout(&self, configurations: &CompileConfigurations) -> bool;
     fn sort_members(&self, configurations: &CompileConfigurations) -> Result<Vec<StructMember>, CompilerError>;
+    fn padded_members(&self, configurations: &CompileConfigurations) -> Result<Vec<StructMember>, CompilerError>;
 }
 
-#[derive(Clone, Debug)]
-struct SizedStructMember {
-    member: StructMember,
-    size:   u64
-}
+/// Get the alignment of a struct member, raised to the alignment forced onto it, if any
+fn member_alignment(struct_name: &str, member: &StructMember, configurations: &CompileConfigurations) -> Result<u64, CompilerError> {
+    let alignment: u64 = member.layout_alignment(configurations)?;
 
-impl SizedStructMember {
-    fn new(member: &StructMember, size: u64) -> SizedStructMember {
-        SizedStructMember { member: member.clone(), size }
-    }
+    Ok(match configurations.forced_alignment(struct_name, member) {
+        Some(forced_alignment) => forced_alignment.max(alignment),
+        None => alignment
+    })
 }
 
-/// Sort the non-aligned members based on the architecture
-fn sort_non_aligned(non_aligned: &mut Vec<SizedStructMember>, configurations: &CompileConfigurations) {
-    // Try to fit small non-aligned members in spaces between the bigger members
-    // ——————————————————————————————————————————————————————————————————————————
+impl CStructDefinition for StructDefinition {
+    /// Sort the members of a struct to minimize padding, first-fit-decreasing by alignment. Members are placed in order of decreasing
+    /// alignment, each into the first gap left by the previous members it fits in with its alignment, or else at the end of the struct.
+    /// Every member size is a multiple of its natural alignment, so gaps are only left by members aligned beyond their size with
+    /// --align-members, and are filled by the smaller members following them. Members of equal alignment keep their field index
+    /// order, as do members placed into the same gap
+    fn sort_members(&self, configurations: &CompileConfigurations) -> Result<Vec<StructMember>, CompilerError> {
+        let mut aligned_members: Vec<(u64, u64, StructMember)> = Vec::with_capacity(self.members.len());
+
+        for member in &self.members {
+            let member_size: u64 = member.layout_size(configurations)?;
+
+            // Zero-size members are discarded
+            if member_size == 0 {
+                warning!("Member {0} of struct {1} had size 0.", member.identifier, self.name);
+                continue;
+            }
+
+            aligned_members.push((member_alignment(&self.name, member, configurations)?, member_size, member.clone()));
+        }
+
+        aligned_members.sort_by_key(|(alignment, _, member)| (Reverse(*alignment), member.index.value()));
+
+        // Place the members, keeping the gaps before aligned members as ranges of free bytes
+        let mut placed_members: Vec<(u64, StructMember)> = Vec::with_capacity(aligned_members.len());
+        let mut gaps: Vec<(u64, u64)> = Vec::new();
+        let mut end: u64 = 0;
+
+        for (alignment, member_size, member) in aligned_members {
+            let gap_position: Option<usize> = gaps.iter().position(|(gap_start, gap_end)| gap_start.next_multiple_of(alignment) + member_size <= *gap_end);
+
+            let offset: u64 = match gap_position {
+                Some(position) => {
+                    let (gap_start, gap_end): (u64, u64) = gaps.remove(position);
+                    let offset: u64 = gap_start.next_multiple_of(alignment);
+
+                    // Keep what is left of the gap on either side of the member
+                    if offset + member_size < gap_end {
+                        gaps.insert(position, (offset + member_size, gap_end));
+                    }
+                    if gap_start < offset {
+                        gaps.insert(position, (gap_start, offset));
+                    }
+
+                    offset
+                },
+                None => {
+                    let offset: u64 = end.next_multiple_of(alignment);
 
-    let sorting_value: u64 = configurations.architecture.byte_size() as u64;
+                    if end < offset {
+                        gaps.push((end, offset));
+                    }
+                    end = offset + member_size;
 
-    let mut large_values: Vec<SizedStructMember> = Vec::with_capacity(0x20);
-    let mut small_values: Vec<SizedStructMember> = Vec::with_capacity(0x20);
+                    offset
+                }
+            };
 
-    // Sort all values into large and small items
-    for member in &*non_aligned {
-        if member.size > sorting_value {
-            large_values.push(member.clone());
-        } else {
-            small_values.push(member.clone());
+            placed_members.push((offset, member));
         }
+
+        placed_members.sort_by_key(|(offset, _)| *offset);
+
+        Ok(placed_members.into_iter().map(|(_, member)| member).collect())
     }
 
-    // Clear old list
-    non_aligned.clear();
+    /// Estimate the offset of each member, in the order they are placed in the struct
+    fn estimate_layout(&self, configurations: &CompileConfigurations) -> Result<Vec<MemberLayout>, CompilerError> {
+        let struct_list: Vec<StructMember> = match configurations.sort {
+            true => self.sort_members(configurations)?,
+            false => self.members.clone()
+        };
+
+        let mut layout: Vec<MemberLayout> = Vec::with_capacity(struct_list.len());
+
+        // Calculate padding
+        let mut total_size: u64 = 0;
+
+        for member in &struct_list {
+            // println!("   {0} - {1} bytes", member.identifier, member.c_size());
 
-    for large in large_values {
-        non_aligned.push(large.clone());
+            let member_size: u64 = member.layout_size(configurations)?;
 
-        let leftover_bytes: u64 = sorting_value - (large.size % sorting_value);
-        let mut best_found_index: isize = -1;
-        let mut best_found_size: u64 = sorting_value;
+            // Members with a size 0 can be skipped
+            if member_size == 0 {
+                continue;
+            }
 
-        debug!(
-            "    Handling large unaligned field {0} with index {1}, size {2}, and leftover {3}",
-            large.member.identifier,
-            large.member.index.value(),
-            large.size,
-            leftover_bytes
-        );
+            let member_alignment_size: u64 = member_alignment(&self.name, member, configurations)?;
 
-        // Try to find a value that fits perfectly. If none found, take the one that fits best
-        for (list_index, small) in small_values.iter().enumerate() {
-            if (small.size <= leftover_bytes) && (leftover_bytes - small.size < best_found_size) {
-                debug!("        Found new best in {0} with a size {1}", small.member.identifier, small.size);
-                best_found_size = leftover_bytes - small.size;
-                best_found_index = list_index as isize;
+            // Estimate padding if packing disabled, and member does not align to its alignment
+            if !configurations.pack_data && !total_size.is_multiple_of(member_alignment_size) {
+                // Add padding
+                let padding: u64 = member_alignment_size - (total_size % member_alignment_size);
+                total_size += padding;
             }
+
+            layout.push(MemberLayout {
+                member: member.clone(),
+                offset: total_size,
+                size:   member_size
+            });
+
+            total_size += member_size;
         }
 
-        // What to do if no values match ???
-        if best_found_index < 0 {
-            continue;
-        } else {
-            non_aligned.push(small_values[best_found_index as usize].clone());
-            small_values.remove(best_found_index as usize);
+        Ok(layout)
+    }
+
+    fn estimate_size(&self, configurations: &CompileConfigurations) -> Result<u64, CompilerError> {
+        if let Some(size) = configurations.struct_sizes.get(&self.name) {
+            return Ok(*size);
+        }
+
+        let layout: Vec<MemberLayout> = self.estimate_layout(configurations)?;
+
+        let end: u64 = match layout.last() {
+            Some(last) => last.offset + last.size,
+            None => 0
+        };
+
+        // Unpacked structs are padded at the end, up to the alignment of their largest aligned member
+        if configurations.pack_data {
+            return Ok(end);
         }
+
+        Ok(end.next_multiple_of(self.estimate_alignment(configurations)?))
     }
 
-    for remaining_small_value in small_values {
-        non_aligned.push(remaining_small_value);
+    /// Estimate the alignment of the struct, which is the alignment of its largest aligned member
+    fn estimate_alignment(&self, configurations: &CompileConfigurations) -> Result<u64, CompilerError> {
+        let mut alignment: u64 = 1;
+
+        for member in &self.members {
+            alignment = alignment.max(member_alignment(&self.name, member, configurations)?);
+        }
+
+        Ok(alignment)
     }
-}
 
-impl CStructDefinition for StructDefinition {
-    /// Sort the members of a struct based on their size alignment to reduce eventual padding
-    fn sort_members(&self, configurations: &CompileConfigurations) -> Result<Vec<StructMember>, CompilerError> {
-        let mut full_list: Vec<StructMember> = Vec::with_capacity(0x20);
+    /// Whether the size of every member is fixed by the generated code, making the layout estimate reliable
+    fn has_fixed_layout(&self, configurations: &CompileConfigurations) -> bool {
+        self.members.iter().all(|member| member.has_fixed_size(configurations))
+    }
 
-        let mut aligned_8: Vec<SizedStructMember> = Vec::with_capacity(0x20);
-        let mut aligned_4: Vec<SizedStructMember> = Vec::with_capacity(0x20);
-        let mut aligned_2: Vec<SizedStructMember> = Vec::with_capacity(0x20);
-        let mut aligned_1: Vec<SizedStructMember> = Vec::with_capacity(0x20);
+    /// Get the members in the order they are placed in the struct, with padding members wherever the layout estimate has a gap
+    fn padded_members(&self, configurations: &CompileConfigurations) -> Result<Vec<StructMember>, CompilerError> {
+        let layout: Vec<MemberLayout> = self.estimate_layout(configurations)?;
 
-        // Attempt to maintain index order wherever it makes sense
-        for member in &self.members {
-            let size: u64 = member.c_size()?;
+        let mut members: Vec<StructMember> = Vec::with_capacity(layout.len() * 2);
+        let mut padding_count: usize = 0;
+        let mut end: u64 = 0;
 
-            // Zero-size members are discarded
-            if size == 0 {
-                warning!("Member {0} of struct {1} had size 0.", member.identifier, self.name);
-                continue;
+        for member_layout in layout {
+            if member_layout.offset > end {
+                members.push(StructMember::padding(padding_count, member_layout.offset - end));
+                padding_count += 1;
             }
 
-            // Align by 8 only if platform is 64 bit. If building for a 32 bit platform sorting by 8 is pointless
-            if size % 8 == 0 && configurations.architecture == Architecture::_64Bit {
-                // First 8 aligned
-                aligned_8.push(SizedStructMember::new(member, size));
-            } else if member.c_size()? % 4 == 0 {
-                // First 4 aligned
-                aligned_4.push(SizedStructMember::new(member, size));
-            } else if member.c_size()? % 2 == 0 {
-                // First 2 aligned
-                aligned_2.push(SizedStructMember::new(member, size));
-            } else {
-                // Lastly non aligned
-                aligned_1.push(SizedStructMember::new(member, size));
-            }
+            end = member_layout.offset + member_layout.size;
+            members.push(member_layout.member);
         }
 
-        // Sort the non-aligned members to allow efficient packing
-        sort_non_aligned(&mut aligned_1, configurations);
+        let size: u64 = self.estimate_size(configurations)?;
 
-        // Append all member elements into the full sorted list
-        full_list.append(&mut aligned_8.into_iter().map(|sized_member| sized_member.member).collect());
-        full_list.append(&mut aligned_4.into_iter().map(|sized_member| sized_member.member).collect());
-        full_list.append(&mut aligned_2.into_iter().map(|sized_member| sized_member.member).collect());
-        full_list.append(&mut aligned_1.into_iter().map(|sized_member| sized_member.member).collect());
+        if size > end {
+            members.push(StructMember::padding(padding_count, size - end));
+        }
 
-        Ok(full_list)
+        Ok(members)
     }
+}
 
-    fn estimate_size(&self, configurations: &CompileConfigurations) -> Result<u64, CompilerError> {
-        // println!("Estimating size of {0}", struct_definition.name);
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::remove_dir_all,
+        path::{Path, PathBuf}
+    };
+
+    use rune_parser::{
+        RuneFileDescription, parser_rune_files,
+        types::{ArraySize, DefineValue, FieldType, Primitive, StructDefinition, StructMember}
+    };
+
+    use crate::{
+        c_utilities::{
+            CConfigurations, CPrimitive, CStructDefinition, CStructMember, CompileConfigurations, apply_path_namespaces, apply_prefix, banner_comment, c_float_literal, format_doc_comment,
+            link_user_definitions, pascal_to_snake_case, pascal_to_uppercase, path_namespace, sorted_struct_definitions
+        },
+        compile_error::CompilerError,
+        generate_to_memory,
+        test_utilities::{compile_configurations, parse_schema, parse_single_schema, write_schema}
+    };
+
+    const ARRAY_SCHEMA: &str = "enum Mode: u16 {\n    Idle = 0;\n    Running = 1\n}\n\nbitfield Flags: u8 {\n    Enabled: u1 = 0;\n    Level: u3 = 1\n}\n\nstruct Status {\n    Modes: [Mode; 3] = 1;\n    States: [Flags; 4] = 2\n}\n";
+
+    fn status_member(file_descriptions: &[RuneFileDescription], identifier: &str) -> StructMember {
+        let status: &StructDefinition = &file_descriptions[0].definitions.structs[0];
+
+        status.members.iter().find(|member| member.identifier == identifier).unwrap().clone()
+    }
 
-        let struct_list: Vec<StructMember> = match configurations.sort {
-            true => self.sort_members(configurations)?,
-            false => self.members.clone()
-        };
+    #[test]
+    fn array_of_enums_is_sized() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("status.rune", ARRAY_SCHEMA)]);
+        let member: StructMember = status_member(&file_descriptions, "Modes");
 
-        // Calculate padding
-        let mut total_size: u64 = 0;
+        assert_eq!(member.c_size().unwrap(), 6);
+        assert_eq!(member.c_size_definition(&compile_configurations(&[])).unwrap(), "(sizeof(mode_t) * 3)");
+    }
 
-        for member in &struct_list {
-            // println!("   {0} - {1} bytes", member.identifier, member.c_size());
+    #[test]
+    fn array_of_enums_is_sized_as_backing_type_with_strict_enum_width() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("status.rune", ARRAY_SCHEMA)]);
+        let configurations: CompileConfigurations = compile_configurations(&["--strict-enum-width"]);
+        let member: StructMember = status_member(&file_descriptions, "Modes").storage_member(&configurations);
 
-            // Assume 8 byte alignment target for items > 4 bytes for worst case scenario
-            let member_alignment_size: u64 = match member.c_size()? {
-                // Members with a size 0 can be skipped
-                0 => continue,
-                1 => 1,
-                2 => 2,
-                3..=4 => 4,
-                // Assume that anything bigger than 4 bytes needs to align to 8 bytes as a worst case scenario (64 bit targets)
-                5.. => 8
-            };
+        assert_eq!(member.c_size_definition(&configurations).unwrap(), "(sizeof(uint16_t) * 3)");
+    }
 
-            // Estimate padding if packing disabled, and member does not align to the worst case 8 bytes (64 bit targets)
-            if !configurations.pack_data && (total_size % member_alignment_size) != 0 {
-                // Add padding
-                let padding: u64 = member_alignment_size - (total_size % member_alignment_size);
-                total_size += padding;
+    #[test]
+    fn array_of_bitfields_is_sized() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("status.rune", ARRAY_SCHEMA)]);
+        let member: StructMember = status_member(&file_descriptions, "States");
+
+        assert_eq!(member.c_size().unwrap(), 4);
+        assert_eq!(member.c_size_definition(&compile_configurations(&[])).unwrap(), "(sizeof(flags_t) * 4)");
+    }
+
+    // A byte aligned to 8 bytes leaves a gap of 7 bytes, which sorting by alignment alone leaves as padding
+    const PATHOLOGICAL_SCHEMA: &str = "struct Frame {\n    Flag: u8 = 1;\n    Tag: u8 = 2;\n    Count: u32 = 3;\n    Value: u64 = 4;\n    Level: u16 = 5\n}\n";
+
+    fn sorted_identifiers(struct_definition: &StructDefinition, configurations: &CompileConfigurations) -> Vec<String> {
+        struct_definition.sort_members(configurations).unwrap().into_iter().map(|member| member.identifier).collect()
+    }
+
+    #[test]
+    fn sorting_fills_gaps_left_by_aligned_members() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("frame.rune", PATHOLOGICAL_SCHEMA)]);
+        let frame: &StructDefinition = &file_descriptions[0].definitions.structs[0];
+
+        let unsorted: CompileConfigurations = compile_configurations(&["--unsorted", "--align-members", "Frame.Tag=8"]);
+        let sorted: CompileConfigurations = compile_configurations(&["--align-members", "Frame.Tag=8"]);
+
+        assert_eq!(frame.estimate_size(&unsorted).unwrap(), 32);
+        assert_eq!(frame.estimate_size(&sorted).unwrap(), 16);
+        assert_eq!(sorted_identifiers(frame, &sorted), ["Tag", "Flag", "Level", "Count", "Value"]);
+    }
+
+    #[test]
+    fn sorting_keeps_index_order_among_equal_alignments() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("frame.rune", PATHOLOGICAL_SCHEMA)]);
+        let frame: &StructDefinition = &file_descriptions[0].definitions.structs[0];
+        let configurations: CompileConfigurations = compile_configurations(&[]);
+
+        assert_eq!(frame.estimate_size(&compile_configurations(&["--unsorted"])).unwrap(), 24);
+        assert_eq!(frame.estimate_size(&configurations).unwrap(), 16);
+        assert_eq!(sorted_identifiers(frame, &configurations), ["Value", "Count", "Level", "Flag", "Tag"]);
+    }
+
+    #[test]
+    fn floats_initialize_with_their_precision() {
+        let configurations: CompileConfigurations = compile_configurations(&[]);
+
+        assert_eq!(Primitive::F32.c_initializer(&configurations), "0.0f");
+        assert_eq!(Primitive::F64.c_initializer(&configurations), "0.0");
+    }
+
+    #[test]
+    fn float_literals_round_trip() {
+        assert_eq!(c_float_literal(0.1, &Primitive::F32), "0.1f");
+        assert_eq!(c_float_literal(0.1, &Primitive::F64), "0.1");
+        assert_eq!(c_float_literal(1e-7, &Primitive::F64), "1e-7");
+    }
+
+    #[test]
+    fn schemas_without_structs_are_output() {
+        let schema: &str = "define Max 4;\n\nenum Mode: u8 {\n    Idle = 0;\n    Running = 1\n}\n";
+        let (file_descriptions, configurations): (Vec<RuneFileDescription>, CConfigurations) = parse_single_schema(schema, &[]);
+
+        assert_eq!(configurations.message_count, 0);
+        assert_eq!(configurations.message_size_type_size, 1);
+
+        let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &compile_configurations(&[])).unwrap();
+        let (_, header): &(String, String) = output_files.iter().find(|(name, _)| name == "schema.rune.h").unwrap();
+
+        assert!(header.contains("} mode_t;"));
+        assert!(!output_files.iter().any(|(_, contents)| contents.contains("[0]")));
+    }
+
+    const DEFINE_SCHEMA: &str = "define Count 4;\n\nstruct Status {\n    Readings: [u16; Count] = 1\n}\n";
+
+    #[test]
+    fn array_size_define_is_resolved() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("status.rune", DEFINE_SCHEMA)]);
+
+        assert_eq!(status_member(&file_descriptions, "Readings").c_size().unwrap(), 8);
+    }
+
+    #[test]
+    fn array_size_define_without_value_is_rejected() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("status.rune", DEFINE_SCHEMA)]);
+        let mut member: StructMember = status_member(&file_descriptions, "Readings");
+
+        // The parser rejects value-less defines, so the value is removed after parsing
+        if let FieldType::Array(_, ArraySize::UserDefinition(definition)) = &mut member.data_type {
+            definition.value = DefineValue::NoValue;
+        }
+
+        assert!(matches!(member.c_size(), Err(CompilerError::MalformedSource)));
+    }
+
+    #[test]
+    fn path_namespace_is_pascal_case() {
+        assert_eq!(path_namespace("net/link_layer/"), "NetLinkLayer");
+        assert_eq!(path_namespace(""), "");
+    }
+
+    #[test]
+    fn types_are_prefixed_with_their_directory() {
+        let schema_folder: PathBuf = write_schema(&[
+            ("net/header.rune", "struct Header {\n    Id: u16 = 1\n}\n"),
+            ("disk/block.rune", "struct Block {\n    Id: u16 = 1\n}\n"),
+            (
+                "frame.rune",
+                "include \"net/header.rune\";\ninclude \"disk/block.rune\";\n\nstruct Frame {\n    Head: Header = 1;\n    Data: Block = 2\n}\n"
+            )
+        ]);
+
+        let mut file_descriptions: Vec<RuneFileDescription> = parser_rune_files(&[Path::new(&schema_folder)], true, true).unwrap();
+        remove_dir_all(&schema_folder).unwrap();
+
+        apply_path_namespaces(&mut file_descriptions);
+        link_user_definitions(&mut file_descriptions).unwrap();
+
+        let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &compile_configurations(&["--namespace-from-path"])).unwrap();
+        let contents = |name: &str| output_files.iter().find(|(file_name, _)| file_name == name).unwrap().1.clone();
+
+        assert!(contents("net/header.rune.h").contains("} net_header_t;"));
+        assert!(contents("net/header.rune.h").contains("NET_HEADER_RUNE_H"));
+        assert!(contents("disk/block.rune.h").contains("} disk_block_t;"));
+        assert!(contents("frame.rune.h").contains("    net_header_t head;\n    disk_block_t data;"));
+    }
+
+    #[test]
+    fn case_conversion_handles_long_identifiers() {
+        let pascal: String = "SensorValue".repeat(1000);
+
+        assert_eq!(pascal_to_snake_case(&pascal), vec!["sensor_value"; 1000].join("_"));
+        assert_eq!(pascal_to_uppercase(&pascal), vec!["SENSOR_VALUE"; 1000].join("_"));
+    }
+
+    #[test]
+    fn case_conversion_handles_multi_byte_characters() {
+        assert_eq!(pascal_to_snake_case("TempératureMax"), "température_max");
+        assert_eq!(pascal_to_uppercase("TempératureMax"), "TEMPéRATURE_MAX");
+    }
+
+    #[test]
+    fn acronym_runs_are_one_word() {
+        assert_eq!(pascal_to_snake_case("HTTPServer"), "http_server");
+        assert_eq!(pascal_to_snake_case("ParseXMLNode"), "parse_xml_node");
+        assert_eq!(pascal_to_snake_case("DeviceID"), "device_id");
+        assert_eq!(pascal_to_snake_case("IOBuffer"), "io_buffer");
+        assert_eq!(pascal_to_snake_case("Vec3Point"), "vec3_point");
+        assert_eq!(pascal_to_uppercase("HTTPServer"), "HTTP_SERVER");
+    }
+
+    #[test]
+    fn message_indexes_follow_the_alphabetical_order_ignoring_case() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[
+            ("zeta.rune", "struct Zeta {\n    X: u8 = 1\n}\n\nstruct alpha {\n    X: u8 = 1\n}\n"),
+            ("beta.rune", "struct Beta {\n    X: u8 = 1\n}\n\nstruct Gamma {\n    X: u8 = 1\n}\n")
+        ]);
+
+        let names: Vec<String> = sorted_struct_definitions(&file_descriptions).into_iter().map(|definition| definition.name).collect();
+
+        assert_eq!(names, ["alpha", "Beta", "Gamma", "Zeta"]);
+    }
+
+    /// Parse the given Rune files, and link them as the compiler does, returning the result of linking
+    fn link_schema(files: &[(&str, &str)]) -> Result<(), CompilerError> {
+        let schema_folder: PathBuf = write_schema(files);
+
+        let mut file_descriptions: Vec<RuneFileDescription> = parser_rune_files(&[Path::new(&schema_folder)], true, true).expect("Schema should parse");
+        remove_dir_all(&schema_folder).expect("Schema folder should be removed");
+
+        link_user_definitions(&mut file_descriptions)
+    }
+
+    #[test]
+    fn structs_containing_other_structs_are_linked() {
+        let schema: &str = "struct Point {\n    X: i16 = 1\n}\n\nstruct Path {\n    Start: Point = 1;\n    Steps: [Point; 4] = 2\n}\n";
+
+        assert!(link_schema(&[("path.rune", schema)]).is_ok());
+    }
+
+    #[test]
+    fn struct_containing_itself_is_rejected() {
+        let schema: &str = "struct Node {\n    Value: u8 = 1;\n    Children: [Node; 2] = 2\n}\n";
+
+        assert!(matches!(link_schema(&[("node.rune", schema)]), Err(CompilerError::MalformedSource)));
+    }
+
+    #[test]
+    fn structs_containing_each_other_are_rejected() {
+        let schema: &str = "struct Tree {\n    Branches: [Branch; 2] = 1\n}\n\nstruct Branch {\n    Owner: Tree = 1\n}\n";
+
+        assert!(matches!(link_schema(&[("tree.rune", schema)]), Err(CompilerError::MalformedSource)));
+    }
+
+    const NESTED_SCHEMA: &str = "struct Level1 {\n    Value: u32 = 1;\n    Flag: bool = 2\n}\n\nstruct Level2 {\n    Inner: Level1 = 1;\n    Flag: bool = 2\n}\n\nstruct Level3 {\n    Inner: Level2 = 1;\n    Flag: bool = 2\n}\n\nstruct Level4 {\n    Inner: Level3 = 1;\n    Flag: bool = 2\n}\n\nstruct Level5 {\n    Inner: Level4 = 1;\n    Flag: bool = 2\n}\n";
+
+    #[test]
+    fn struct_sizes_are_cached_while_parsing_configurations() {
+        let (file_descriptions, configurations) = parse_single_schema(NESTED_SCHEMA, &[]);
+        let uncached: CompileConfigurations = compile_configurations(&[]);
+
+        for definition in &file_descriptions[0].definitions.structs {
+            assert_eq!(
+                configurations.compiler_configurations.struct_sizes.get(&definition.name).copied(),
+                Some(definition.estimate_size(&uncached).unwrap())
+            );
+        }
+
+        assert_eq!(configurations.compiler_configurations.struct_sizes.get("Level5").copied(), Some(24));
+    }
+
+    #[test]
+    fn cached_struct_sizes_are_used_for_nested_structs() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("nested.rune", NESTED_SCHEMA)]);
+        let mut configurations: CompileConfigurations = compile_configurations(&[]);
+        let level5: &StructDefinition = &file_descriptions[0].definitions.structs[4];
+
+        assert_eq!(level5.estimate_size(&configurations).unwrap(), 24);
+
+        // A cached size is taken as is, without sizing the struct again
+        configurations.struct_sizes.insert(String::from("Level5"), 100);
+        assert_eq!(level5.estimate_size(&configurations).unwrap(), 100);
+    }
+
+    #[test]
+    fn doc_comment_terminators_are_escaped() {
+        assert_eq!(format_doc_comment(" Ends early */ otherwise", false), ["/** Ends early *\\/ otherwise */"]);
+    }
+
+    #[test]
+    fn multi_line_doc_comments_are_prefixed_and_trimmed() {
+        let comment: &str = "*\n     * A mode\n     *\n     * Second paragraph\n     ";
+
+        assert_eq!(format_doc_comment(comment, false), ["/**", " * A mode", " *", " * Second paragraph", " */"]);
+    }
+
+    #[test]
+    fn doxygen_doc_comments_mark_the_brief_description() {
+        assert_eq!(format_doc_comment(" Point in space", true), ["/** @brief Point in space */"]);
+        assert_eq!(format_doc_comment("*\n * A mode\n *\n * Details\n", true), ["/**", " * @brief A mode", " *", " * Details", " */"]);
+    }
+
+    #[test]
+    fn blank_doc_comments_are_left_out() {
+        assert!(format_doc_comment("*\n *\n ", false).is_empty());
+    }
+
+    #[test]
+    fn debug_checks_are_only_compiled_in_with_rune_debug() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("sample.rune", "struct Sample {\n    Value: u16 = 1\n}\n")]);
+        let checks: &str = "#ifdef RUNE_DEBUG\n    RUNE_ASSERT(message != NULL);\n";
+
+        let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &compile_configurations(&["--emit-codec", "--emit-getters", "--emit-debug-checks"])).unwrap();
+        let header: &str = &output_files.iter().find(|(name, _)| name == "sample.rune.h").unwrap().1;
+        let source: &str = &output_files.iter().find(|(name, _)| name == "sample.rune.c").unwrap().1;
+
+        assert!(header.contains(&format!("sample_get_value(const sample_t* message) {{\n{0}#endif\n    return message->value;", checks)));
+        assert_eq!(source.matches(&format!("{0}    RUNE_ASSERT(buffer != NULL);\n#endif\n", checks)).count(), 2);
+
+        let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &compile_configurations(&["--emit-codec", "--emit-getters"])).unwrap();
+        assert!(output_files.iter().all(|(_, contents)| !contents.contains("RUNE_ASSERT(")));
+    }
+
+    #[test]
+    fn prefixed_output_has_no_unprefixed_type_names() {
+        let common: &str = "enum Mode: u8 {\n    Idle = 0;\n    Running = 1\n}\n\nstruct Point {\n    X: i16 = 1\n}\n";
+        let device: &str = "include \"common.rune\";\n\nstruct Device {\n    Current: Mode = 1;\n    Origin: Point = 2;\n    Path: [Point; 2] = 3\n}\n";
+        let schema_folder: PathBuf = write_schema(&[("common.rune", common), ("device.rune", device)]);
+
+        // The prefix is applied before linking, as the compiler does
+        let mut file_descriptions: Vec<RuneFileDescription> = parser_rune_files(&[Path::new(&schema_folder)], true, true).expect("Schema should parse");
+        remove_dir_all(&schema_folder).expect("Schema folder should be removed");
+        apply_prefix(&mut file_descriptions, "acme");
+        link_user_definitions(&mut file_descriptions).expect("Schema should link");
+
+        let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &compile_configurations(&["--prefix", "acme"])).unwrap();
+        let output: String = output_files.iter().map(|(_, contents)| contents.as_str()).collect();
+
+        // File names are not prefixed, so includes are left out
+        let identifiers = output
+            .lines()
+            .filter(|line| !line.starts_with("#include"))
+            .flat_map(|line| line.split(|character: char| !character.is_ascii_alphanumeric() && character != '_'));
+
+        for identifier in identifiers {
+            let lowercase: String = identifier.to_ascii_lowercase();
+
+            if ["mode", "point", "device"].iter().any(|name| lowercase.contains(name)) {
+                assert!(lowercase.starts_with("acme_"), "{0} is not prefixed", identifier);
             }
+        }
 
-            total_size += member.c_size()?;
+        for name in [
+            "acme_device_t",
+            "acme_point_t origin;",
+            "acme_point_t path[2];",
+            "acme_mode_t current;",
+            "ACME_POINT_INIT",
+            "acme_point_descriptor",
+            "ACME_DEVICE_RUNE_H"
+        ] {
+            assert!(output.contains(name), "{0} is missing", name);
         }
+    }
+
+    #[test]
+    fn flattened_outputs_are_named_after_their_directories() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[
+            ("net/link/frame.rune", "struct Frame {\n    Value: u8 = 1\n}\n"),
+            ("app.rune", "include \"net/link/frame.rune\";\n\nstruct App {\n    Last: Frame = 1\n}\n")
+        ]);
+        let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &compile_configurations(&["--flatten"])).unwrap();
+
+        let mut names: Vec<&str> = output_files.iter().map(|(name, _)| name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, ["app.rune.c", "app.rune.h", "net_link_frame.rune.c", "net_link_frame.rune.h", "runic_definitions.h"]);
 
-        Ok(total_size)
+        let file = |name: &str| -> &str { &output_files.iter().find(|(output_name, _)| output_name == name).unwrap().1 };
+
+        assert!(file("app.rune.h").contains("#include \"net_link_frame.rune.h\""));
+        assert!(!file("app.rune.h").contains("net/link/"));
+        assert!(file("net_link_frame.rune.c").starts_with("#include \"net_link_frame.rune.h\""));
+        assert!(file("net_link_frame.rune.h").starts_with("#ifndef NET_LINK_FRAME_RUNE_H\n#define NET_LINK_FRAME_RUNE_H\n"));
+    }
+
+    #[test]
+    fn verifier_fields_require_verifier_support() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("frame.rune", "struct Frame {\n    Value: u8 = 1;\n    Crc: u16 = verifier\n}\n")]);
+
+        assert!(CConfigurations::parse(&file_descriptions, &compile_configurations(&[])).is_ok());
+        assert!(matches!(
+            CConfigurations::parse(&file_descriptions, &compile_configurations(&["--no-verifier-support"])),
+            Err(CompilerError::ConfigurationError)
+        ));
+    }
+
+    #[test]
+    fn banners_are_wrapped_in_a_comment_block() {
+        let banner: String = banner_comment("Copyright Acme\n\nAll rights reserved\n\n", false).unwrap();
+
+        assert_eq!(banner, "/*\n * Copyright Acme\n *\n * All rights reserved\n */");
+    }
+
+    #[test]
+    fn verbatim_banners_are_passed_through() {
+        let banner: String = banner_comment("// Copyright Acme\n/* All rights reserved */\n", true).unwrap();
+
+        assert_eq!(banner, "// Copyright Acme\n/* All rights reserved */");
+    }
+
+    #[test]
+    fn banners_ending_the_comment_block_are_rejected() {
+        assert!(matches!(banner_comment("Copyright Acme */ int x;", false), Err(CompilerError::InvalidArgument)));
+    }
+
+    #[test]
+    fn banners_open_every_generated_file() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("frame.rune", "struct Frame {\n    Value: u8 = 1\n}\n")]);
+        let mut configurations: CompileConfigurations = compile_configurations(&[]);
+        configurations.banner = Some(banner_comment("Copyright Acme", false).unwrap());
+
+        for (name, contents) in generate_to_memory(&file_descriptions, &configurations).unwrap() {
+            assert!(contents.starts_with("/*\n * Copyright Acme\n */\n\n"), "{0} does not start with the banner", name);
+        }
     }
 }
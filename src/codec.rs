@@ -0,0 +1,421 @@
+use rune_parser::types::{ArrayType, FieldType, Primitive, StructDefinition, StructMember, UserDefinitionLink};
+
+use crate::{
+    c_standard::CStandard,
+    c_utilities::{CArraySize, CConfigurations, CPrimitive, CStructMember, output_debug_checks, pascal_to_snake_case, pascal_to_uppercase},
+    compile_error::CompilerError,
+    endianness::Endianness,
+    output_file::OutputFile
+};
+
+// Codec wire format
+// ——————————————————
+//
+// Members are encoded back to back in field index order, so the verifier field comes first, without tags or padding. Nested
+// structs, also within arrays, are encoded recursively, while all other members are copied as they are stored in memory, in host
+// byte order. With a byte order fixed by --endianness, the bytes of every multi-byte element are instead reversed whenever the
+// host byte order differs from the chosen one. As every member has a fixed size, so has every encoded message, allowing buffers to
+// be sized at compile time.
+
+/// How a member is copied in and out of the buffer
+enum CodecMember {
+    /// Copied as it is stored in memory, with the given size
+    Plain(String, String),
+    /// Boolean, or array of booleans of the given length, copied as it is stored in memory with the given size. Bytes other than 0
+    /// and 1 are rejected when deserializing, as they are no valid boolean
+    Boolean(String, String, Option<String>),
+    /// Copied in the chosen byte order, with the given size, the size of its elements and their amount
+    Ordered(String, String, String, String),
+    /// Nested struct, encoded recursively
+    Struct(String, String),
+    /// Array of nested structs, each encoded recursively
+    StructArray(String, String, String)
+}
+
+/// Members of the struct in field index order, with how each is encoded
+fn codec_members(configurations: &CConfigurations, struct_definition: &StructDefinition) -> Result<Vec<CodecMember>, CompilerError> {
+    let mut members: Vec<StructMember> = struct_definition.members.iter().filter(|member| member.data_type != FieldType::Empty).cloned().collect();
+    members.sort_by_key(|member| member.index.value());
+
+    let mut codec_members: Vec<CodecMember> = Vec::with_capacity(members.len());
+
+    for member in &members {
+        let member_name: String = pascal_to_snake_case(&member.identifier);
+
+        let codec_member: CodecMember = match (&member.data_type, &member.user_definition_link) {
+            (FieldType::UserDefined(_), UserDefinitionLink::StructLink(definition)) => CodecMember::Struct(member_name, definition.name.clone()),
+            (FieldType::Primitive(Primitive::Bool), _) => CodecMember::Boolean(member_name, member.c_size_definition(&configurations.compiler_configurations)?, None),
+            (FieldType::Array(ArrayType::Primitive(Primitive::Bool), array_size), _) => CodecMember::Boolean(
+                member_name,
+                member.c_size_definition(&configurations.compiler_configurations)?,
+                Some(array_size.to_c_literal(&configurations.compiler_configurations))
+            ),
+            (FieldType::Array(ArrayType::UserDefined(_), array_size), UserDefinitionLink::StructLink(definition)) => {
+                CodecMember::StructArray(member_name, definition.name.clone(), array_size.to_c_literal(&configurations.compiler_configurations))
+            },
+            _ => {
+                let storage_member: StructMember = member.storage_member(&configurations.compiler_configurations);
+
+                let size: String = storage_member.c_size_definition(&configurations.compiler_configurations)?;
+
+                match ordered_elements(configurations, &storage_member)? {
+                    Some((element_size, element_count)) => CodecMember::Ordered(member_name, size, element_size, element_count),
+                    None => CodecMember::Plain(member_name, size)
+                }
+            }
+        };
+
+        codec_members.push(codec_member);
+    }
+
+    Ok(codec_members)
+}
+
+/// Size and amount of the elements of a member whose bytes follow the chosen byte order, or None if the member is copied as it is
+/// stored. Single byte elements have no byte order, nor do 128 bit integers, which are stored as byte arrays
+fn ordered_elements(configurations: &CConfigurations, member: &StructMember) -> Result<Option<(String, String)>, CompilerError> {
+    if configurations.compiler_configurations.endianness == Endianness::Native {
+        return Ok(None);
+    }
+
+    let c_standard: &CStandard = &configurations.compiler_configurations.c_standard;
+
+    let (element_type, element_count): (String, String) = match (&member.data_type, &member.user_definition_link) {
+        (FieldType::Primitive(primitive), _) if primitive.c_size() > 1 && primitive.c_size() < 16 => (primitive.to_c_type(c_standard)?, String::from("1")),
+        (FieldType::Array(ArrayType::Primitive(primitive), array_size), _) if primitive.c_size() > 1 && primitive.c_size() < 16 => {
+            (primitive.to_c_type(c_standard)?, array_size.to_c_literal(&configurations.compiler_configurations))
+        },
+        // Enums are as wide as the C compiler makes them, so they are reversed whatever their size
+        (FieldType::UserDefined(name), UserDefinitionLink::EnumLink(_)) => (format!("{0}_t", pascal_to_snake_case(name)), String::from("1")),
+        (FieldType::Array(ArrayType::UserDefined(name), array_size), UserDefinitionLink::EnumLink(_)) => {
+            (format!("{0}_t", pascal_to_snake_case(name)), array_size.to_c_literal(&configurations.compiler_configurations))
+        },
+        (FieldType::UserDefined(name), UserDefinitionLink::BitfieldLink(definition)) if definition.backing_type.c_size() > 1 => (format!("{0}_t", pascal_to_snake_case(name)), String::from("1")),
+        (FieldType::Array(ArrayType::UserDefined(name), array_size), UserDefinitionLink::BitfieldLink(definition)) if definition.backing_type.c_size() > 1 => {
+            (format!("{0}_t", pascal_to_snake_case(name)), array_size.to_c_literal(&configurations.compiler_configurations))
+        },
+        _ => return Ok(None)
+    };
+
+    Ok(Some((format!("sizeof({0})", element_type), element_count)))
+}
+
+/// Whether any codec function of the structs copies members through the byte order function
+pub fn uses_byte_order_function(configurations: &CConfigurations, struct_definitions: &[StructDefinition]) -> Result<bool, CompilerError> {
+    for struct_definition in struct_definitions {
+        if codec_members(configurations, struct_definition)?.iter().any(|member| matches!(member, CodecMember::Ordered(..))) {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Outputs the function copying members between memory and the chosen byte order. Reversing the bytes of an element both converts
+/// it to and from the other byte order, so it serves both the serialize and deserialize functions
+pub fn output_byte_order_function(source_file: &mut OutputFile, configurations: &CConfigurations) {
+    let suffix: &'static str = configurations.compiler_configurations.unsigned_suffix();
+
+    // The first byte of the probe is 1 on little endian hosts
+    let (endianness, host_matches): (&'static str, String) = match configurations.compiler_configurations.endianness {
+        Endianness::Big => ("big", format!("*(const unsigned char*)&probe == 0{0}", suffix)),
+        _ => ("little", format!("*(const unsigned char*)&probe == 1{0}", suffix))
+    };
+
+    source_file.add_line(format!(
+        "/* Copy elements of the given size between memory and the {0} endian wire order, reversing their bytes on other hosts */",
+        endianness
+    ));
+    source_file.add_line(String::from(
+        "static void rune_codec_copy(unsigned char* destination, const unsigned char* source, size_t size, size_t count) {"
+    ));
+    source_file.add_indented_line(1, format!("const unsigned short probe = 1{0};", suffix));
+    source_file.add_indented_line(1, String::from("size_t i;"));
+    source_file.add_indented_line(1, String::from("size_t j;"));
+    source_file.add_newline();
+    source_file.add_indented_line(1, format!("if ({0}) {{", host_matches));
+    source_file.add_indented_line(2, String::from("memcpy(destination, source, size * count);"));
+    source_file.add_indented_line(2, String::from("return;"));
+    source_file.add_indented_line(1, String::from("}"));
+    source_file.add_newline();
+    source_file.add_indented_line(1, String::from("for (i = 0; i < count; i++) {"));
+    source_file.add_indented_line(2, String::from("for (j = 0; j < size; j++) {"));
+    source_file.add_indented_line(3, format!("destination[i * size + j] = source[i * size + size - 1{0} - j];", suffix));
+    source_file.add_indented_line(2, String::from("}"));
+    source_file.add_indented_line(1, String::from("}"));
+    source_file.add_line(String::from("}"));
+    source_file.add_newline();
+}
+
+/// Outputs the encoded size and the declarations of the serialize and deserialize functions of a struct into the header file
+pub fn output_codec_declarations(header_file: &mut OutputFile, configurations: &CConfigurations, struct_definition: &StructDefinition) -> Result<(), CompilerError> {
+    let struct_name: String = pascal_to_snake_case(&struct_definition.name);
+
+    let sizes: Vec<String> = codec_members(configurations, struct_definition)?
+        .iter()
+        .map(|member| match member {
+            CodecMember::Plain(_, size) | CodecMember::Boolean(_, size, _) | CodecMember::Ordered(_, size, _, _) => size.clone(),
+            CodecMember::Struct(_, name) => format!("{0}_ENCODED_SIZE", pascal_to_uppercase(name)),
+            CodecMember::StructArray(_, name, length) => format!("({0}_ENCODED_SIZE * {1})", pascal_to_uppercase(name), length)
+        })
+        .collect();
+
+    let encoded_size: String = match sizes.is_empty() {
+        true => format!("0{0}", configurations.compiler_configurations.unsigned_suffix()),
+        false => format!("({0})", sizes.join(" + "))
+    };
+
+    header_file.add_line(format!("#define {0}_ENCODED_SIZE {1}", pascal_to_uppercase(&struct_definition.name), encoded_size));
+    header_file.add_line(format!(
+        "RUNE_NODISCARD size_t {0}_serialize(const {0}_t* message, unsigned char* buffer, size_t capacity);",
+        struct_name
+    ));
+    header_file.add_line(format!(
+        "RUNE_NODISCARD {0} {1}_deserialize({1}_t* message, const unsigned char* buffer, size_t length);",
+        Primitive::Bool.to_c_type(&configurations.compiler_configurations.c_standard)?,
+        struct_name
+    ));
+
+    Ok(())
+}
+
+/// Outputs the serialize and deserialize functions of a struct into the source file
+pub fn output_codec(source_file: &mut OutputFile, configurations: &CConfigurations, struct_definition: &StructDefinition) -> Result<(), CompilerError> {
+    let struct_name: String = pascal_to_snake_case(&struct_definition.name);
+    let encoded_size: String = format!("{0}_ENCODED_SIZE", pascal_to_uppercase(&struct_definition.name));
+
+    let (true_value, false_value): (&'static str, &'static str) = match configurations.compiler_configurations.c_standard.allows_boolean() {
+        true => ("true", "false"),
+        false => ("RUNE_TRUE", "RUNE_FALSE")
+    };
+
+    let members: Vec<CodecMember> = codec_members(configurations, struct_definition)?;
+    let has_arrays: bool = members.iter().any(|member| matches!(member, CodecMember::StructArray(..)));
+    let has_boolean_arrays: bool = members.iter().any(|member| matches!(member, CodecMember::Boolean(_, _, Some(_))));
+
+    // Serialize
+    // ——————————
+
+    source_file.add_line(format!("size_t {0}_serialize(const {0}_t* message, unsigned char* buffer, size_t capacity) {{", struct_name));
+
+    // Empty structs encode to nothing, and comparing the capacity against 0 would always be false
+    if members.is_empty() {
+        source_file.add_indented_line(1, String::from("(void)message;"));
+        source_file.add_indented_line(1, String::from("(void)buffer;"));
+        source_file.add_indented_line(1, String::from("(void)capacity;"));
+        source_file.add_indented_line(1, String::from("return 0;"));
+        source_file.add_line(String::from("}"));
+        source_file.add_newline();
+
+        source_file.add_line(format!(
+            "{0} {1}_deserialize({1}_t* message, const unsigned char* buffer, size_t length) {{",
+            Primitive::Bool.to_c_type(&configurations.compiler_configurations.c_standard)?,
+            struct_name
+        ));
+        source_file.add_indented_line(1, String::from("(void)message;"));
+        source_file.add_indented_line(1, String::from("(void)buffer;"));
+        source_file.add_indented_line(1, String::from("(void)length;"));
+        source_file.add_indented_line(1, format!("return {0};", true_value));
+        source_file.add_line(String::from("}"));
+        source_file.add_newline();
+
+        return Ok(());
+    }
+
+    source_file.add_indented_line(1, String::from("size_t offset = 0;"));
+    if has_arrays {
+        source_file.add_indented_line(1, String::from("size_t i;"));
+    }
+    source_file.add_newline();
+
+    if configurations.compiler_configurations.debug_checks {
+        output_debug_checks(source_file, &["message != NULL", "buffer != NULL"]);
+        source_file.add_newline();
+    }
+
+    source_file.add_indented_line(1, format!("if (capacity < {0}) {{", encoded_size));
+    source_file.add_indented_line(2, String::from("return 0;"));
+    source_file.add_indented_line(1, String::from("}"));
+    source_file.add_newline();
+
+    // Nested structs always fit, as the capacity covers the encoded size of the whole message
+    for member in &members {
+        match member {
+            CodecMember::Plain(member_name, size) | CodecMember::Boolean(member_name, size, _) => {
+                source_file.add_indented_line(1, format!("memcpy(&buffer[offset], &message->{0}, {1});", member_name, size));
+                source_file.add_indented_line(1, format!("offset += {0};", size));
+            },
+            CodecMember::Ordered(member_name, size, element_size, element_count) => {
+                source_file.add_indented_line(
+                    1,
+                    format!(
+                        "rune_codec_copy(&buffer[offset], (const unsigned char*)&message->{0}, {1}, {2});",
+                        member_name, element_size, element_count
+                    )
+                );
+                source_file.add_indented_line(1, format!("offset += {0};", size));
+            },
+            CodecMember::Struct(member_name, name) => {
+                source_file.add_indented_line(
+                    1,
+                    format!("offset += {0}_serialize(&message->{1}, &buffer[offset], capacity - offset);", pascal_to_snake_case(name), member_name)
+                );
+            },
+            CodecMember::StructArray(member_name, name, length) => {
+                source_file.add_indented_line(1, format!("for (i = 0; i < {0}; i++) {{", length));
+                source_file.add_indented_line(
+                    2,
+                    format!(
+                        "offset += {0}_serialize(&message->{1}[i], &buffer[offset], capacity - offset);",
+                        pascal_to_snake_case(name),
+                        member_name
+                    )
+                );
+                source_file.add_indented_line(1, String::from("}"));
+            }
+        }
+    }
+
+    source_file.add_newline();
+    source_file.add_indented_line(1, String::from("return offset;"));
+    source_file.add_line(String::from("}"));
+    source_file.add_newline();
+
+    // Deserialize
+    // ————————————
+
+    source_file.add_line(format!(
+        "{0} {1}_deserialize({1}_t* message, const unsigned char* buffer, size_t length) {{",
+        Primitive::Bool.to_c_type(&configurations.compiler_configurations.c_standard)?,
+        struct_name
+    ));
+    source_file.add_indented_line(1, String::from("size_t offset = 0;"));
+    if has_arrays || has_boolean_arrays {
+        source_file.add_indented_line(1, String::from("size_t i;"));
+    }
+    source_file.add_newline();
+
+    if configurations.compiler_configurations.debug_checks {
+        output_debug_checks(source_file, &["message != NULL", "buffer != NULL"]);
+        source_file.add_newline();
+    }
+
+    // Bytes past the encoded message are left for the caller
+    source_file.add_indented_line(1, format!("if (length < {0}) {{", encoded_size));
+    source_file.add_indented_line(2, format!("return {0};", false_value));
+    source_file.add_indented_line(1, String::from("}"));
+    source_file.add_newline();
+
+    for member in &members {
+        match member {
+            CodecMember::Plain(member_name, size) => {
+                source_file.add_indented_line(1, format!("memcpy(&message->{0}, &buffer[offset], {1});", member_name, size));
+                source_file.add_indented_line(1, format!("offset += {0};", size));
+            },
+            // Storing any other value than 0 or 1 in a boolean is undefined behavior, so the buffer is checked before the copy
+            CodecMember::Boolean(member_name, size, None) => {
+                source_file.add_indented_line(1, String::from("if (buffer[offset] > 1) {"));
+                source_file.add_indented_line(2, format!("return {0};", false_value));
+                source_file.add_indented_line(1, String::from("}"));
+                source_file.add_indented_line(1, format!("memcpy(&message->{0}, &buffer[offset], {1});", member_name, size));
+                source_file.add_indented_line(1, format!("offset += {0};", size));
+            },
+            CodecMember::Boolean(member_name, size, Some(length)) => {
+                source_file.add_indented_line(1, format!("for (i = 0; i < {0}; i++) {{", length));
+                source_file.add_indented_line(2, String::from("if (buffer[offset + i] > 1) {"));
+                source_file.add_indented_line(3, format!("return {0};", false_value));
+                source_file.add_indented_line(2, String::from("}"));
+                source_file.add_indented_line(1, String::from("}"));
+                source_file.add_indented_line(1, format!("memcpy(&message->{0}, &buffer[offset], {1});", member_name, size));
+                source_file.add_indented_line(1, format!("offset += {0};", size));
+            },
+            CodecMember::Ordered(member_name, size, element_size, element_count) => {
+                source_file.add_indented_line(
+                    1,
+                    format!("rune_codec_copy((unsigned char*)&message->{0}, &buffer[offset], {1}, {2});", member_name, element_size, element_count)
+                );
+                source_file.add_indented_line(1, format!("offset += {0};", size));
+            },
+            CodecMember::Struct(member_name, name) => {
+                source_file.add_indented_line(
+                    1,
+                    format!("if (!{0}_deserialize(&message->{1}, &buffer[offset], length - offset)) {{", pascal_to_snake_case(name), member_name)
+                );
+                source_file.add_indented_line(2, format!("return {0};", false_value));
+                source_file.add_indented_line(1, String::from("}"));
+                source_file.add_indented_line(1, format!("offset += {0}_ENCODED_SIZE;", pascal_to_uppercase(name)));
+            },
+            CodecMember::StructArray(member_name, name, length) => {
+                source_file.add_indented_line(1, format!("for (i = 0; i < {0}; i++) {{", length));
+                source_file.add_indented_line(
+                    2,
+                    format!("if (!{0}_deserialize(&message->{1}[i], &buffer[offset], length - offset)) {{", pascal_to_snake_case(name), member_name)
+                );
+                source_file.add_indented_line(3, format!("return {0};", false_value));
+                source_file.add_indented_line(2, String::from("}"));
+                source_file.add_indented_line(2, format!("offset += {0}_ENCODED_SIZE;", pascal_to_uppercase(name)));
+                source_file.add_indented_line(1, String::from("}"));
+            }
+        }
+    }
+
+    source_file.add_newline();
+    source_file.add_indented_line(1, format!("return {0};", true_value));
+    source_file.add_line(String::from("}"));
+    source_file.add_newline();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rune_parser::RuneFileDescription;
+
+    use crate::{c_utilities::CConfigurations, codec::output_codec, output_file::OutputFile, test_utilities::parse_single_schema};
+
+    const SCHEMA: &str = "struct Sample {\n    Channel: u8 = 1;\n    Value: u32 = 2;\n    History: [i16; 4] = 3\n}\n";
+
+    fn codec_source(arguments: &[&str]) -> String {
+        schema_codec_source(SCHEMA, arguments)
+    }
+
+    /// Output the codec functions of the first struct of a Rune file, compiled with the given command line arguments
+    fn schema_codec_source(contents: &str, arguments: &[&str]) -> String {
+        let (file_descriptions, configurations): (Vec<RuneFileDescription>, CConfigurations) = parse_single_schema(contents, arguments);
+
+        let mut source_file: OutputFile = OutputFile::new(String::from("schema.rune.c"), &configurations.compiler_configurations);
+        output_codec(&mut source_file, &configurations, &file_descriptions[0].definitions.structs[0]).unwrap();
+
+        source_file.into_contents()
+    }
+
+    #[test]
+    fn native_members_are_copied_in_host_byte_order() {
+        let source: String = codec_source(&["--emit-codec"]);
+
+        assert!(source.contains("memcpy(&buffer[offset], &message->value, sizeof(uint32_t));"));
+        assert!(!source.contains("rune_codec_copy"));
+    }
+
+    #[test]
+    fn fixed_byte_order_members_are_copied_per_element() {
+        let source: String = codec_source(&["--emit-codec", "--endianness", "big"]);
+
+        assert!(source.contains("memcpy(&buffer[offset], &message->channel, sizeof(uint8_t));"));
+        assert!(source.contains("rune_codec_copy(&buffer[offset], (const unsigned char*)&message->value, sizeof(uint32_t), 1);"));
+        assert!(source.contains("rune_codec_copy((unsigned char*)&message->history, &buffer[offset], sizeof(int16_t), 4);"));
+    }
+
+    #[test]
+    fn deserialize_rejects_invalid_booleans_before_copying_them() {
+        let source: String = schema_codec_source("struct Sample {\n    Valid: bool = 1;\n    Flags: [bool; 3] = 2\n}\n", &["--emit-codec"]);
+
+        assert!(source.contains("    if (buffer[offset] > 1) {\n        return false;\n    }\n    memcpy(&message->valid, &buffer[offset], sizeof(bool));"));
+        assert!(source.contains(
+            "    for (i = 0; i < 3; i++) {\n        if (buffer[offset + i] > 1) {\n            return false;\n        }\n    }\n    memcpy(&message->flags, &buffer[offset], (sizeof(bool) * 3));"
+        ));
+
+        // Serializing copies booleans as they are
+        assert!(source.contains("memcpy(&buffer[offset], &message->valid, sizeof(bool));"));
+    }
+}
@@ -12,5 +12,62 @@ pub enum CompilerError {
     LogicError,
     MalformedSource,
     UnsupportedFeature,
+    IncompatibleSchema,
+    ImplementationDefinedBehavior,
     FileSystemError(Error)
 }
+
+impl CompilerError {
+    /// Process exit code reported for the error, so scripts can tell failure kinds apart. Command line parsing errors reported by
+    /// clap also exit with 2
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            // Internal compiler error
+            CompilerError::LogicError => 1,
+
+            // Bad arguments or configurations
+            CompilerError::InvalidArgument | CompilerError::InvalidInputPath | CompilerError::ConfigurationError => 2,
+
+            // Rune files could not be parsed
+            CompilerError::ParsingError(_) => 3,
+
+            // Rune files could be parsed, but cannot be output as C
+            CompilerError::MalformedSource | CompilerError::SourceAndCStandardMismatch | CompilerError::UnsupportedFeature | CompilerError::ImplementationDefinedBehavior => 4,
+
+            // Reading or writing files failed
+            CompilerError::FileSystemError(_) => 5,
+
+            // Schema diff found changes breaking wire compatibility
+            CompilerError::IncompatibleSchema => 6
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Error, ErrorKind};
+
+    use rune_parser::RuneParserError;
+
+    use super::CompilerError;
+
+    #[test]
+    fn exit_codes_match_the_documented_table() {
+        assert_eq!(CompilerError::LogicError.exit_code(), 1);
+
+        assert_eq!(CompilerError::InvalidArgument.exit_code(), 2);
+        assert_eq!(CompilerError::InvalidInputPath.exit_code(), 2);
+        assert_eq!(CompilerError::ConfigurationError.exit_code(), 2);
+
+        assert_eq!(CompilerError::ParsingError(RuneParserError::InvalidInputPath).exit_code(), 3);
+
+        assert_eq!(CompilerError::MalformedSource.exit_code(), 4);
+        assert_eq!(CompilerError::SourceAndCStandardMismatch.exit_code(), 4);
+        assert_eq!(CompilerError::UnsupportedFeature.exit_code(), 4);
+        assert_eq!(CompilerError::ImplementationDefinedBehavior.exit_code(), 4);
+
+        assert_eq!(CompilerError::FileSystemError(Error::from(ErrorKind::NotFound)).exit_code(), 5);
+
+        assert_eq!(CompilerError::IncompatibleSchema.exit_code(), 6);
+    }
+}
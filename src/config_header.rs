@@ -0,0 +1,165 @@
+use crate::{
+    c_utilities::{CConfigurations, CompileConfigurations, output_disclaimer},
+    endianness::Endianness,
+    output_file::OutputFile
+};
+
+/// Output a flag as a define only when it is set, so it can be checked with #if defined
+fn output_flag(config_file: &mut OutputFile, name: &str, enabled: bool) {
+    if enabled {
+        config_file.add_line(format!("#define RUNE_CONFIG_{0}", name));
+    }
+}
+
+/// Outputs a rune_config.h header recording the options the code was generated with, letting consumers assert them at compile time
+pub fn output_config_header(configurations: &CConfigurations) -> OutputFile {
+    let compiler_configurations: &CompileConfigurations = &configurations.compiler_configurations;
+
+    let mut config_file: OutputFile = OutputFile::new(String::from("rune_config.h"), compiler_configurations);
+
+    // Disclaimers
+    // ————————————
+
+    output_disclaimer(&mut config_file, compiler_configurations);
+
+    // Description
+    // ————————————
+
+    let enabled = |flag: bool| match flag {
+        true => "yes",
+        false => "no"
+    };
+
+    config_file.add_line(String::from("/* Options used to generate this code:"));
+    config_file.add_line(format!(
+        " *     C standard:         {0} ({1})",
+        compiler_configurations.c_standard,
+        compiler_configurations.c_standard.gcc_std_flag()
+    ));
+    config_file.add_line(format!(" *     GNU extensions:     {0}", enabled(compiler_configurations.c_standard.allows_gnu_extensions())));
+    config_file.add_line(format!(" *     Architecture:       {0} bit", compiler_configurations.architecture.byte_size() * 8));
+    config_file.add_line(format!(
+        " *     Endianness:         {0}",
+        match compiler_configurations.endianness {
+            Endianness::Native => "native",
+            Endianness::Little => "little",
+            Endianness::Big => "big"
+        }
+    ));
+    config_file.add_line(format!(" *     Pack data:          {0}", enabled(compiler_configurations.pack_data)));
+    config_file.add_line(format!(" *     Pack metadata:      {0}", enabled(compiler_configurations.pack_metadata)));
+    config_file.add_line(format!(" *     Data section:       {0}", compiler_configurations.section.as_deref().unwrap_or("none")));
+    config_file.add_line(format!(" *     Sorted:             {0}", enabled(compiler_configurations.sort)));
+    config_file.add_line(format!(" *     Verifier support:   {0}", enabled(compiler_configurations.verifier_support)));
+    config_file.add_line(format!(" *     Strict enum width:  {0}", enabled(compiler_configurations.strict_enum_width)));
+    config_file.add_line(format!(" *     Safe packed access: {0}", enabled(compiler_configurations.safe_packed_access())));
+    config_file.add_line(format!(" *     Explicit padding:   {0}", enabled(compiler_configurations.explicit_padding)));
+    config_file.add_line(format!(" *     MISRA:              {0}", enabled(compiler_configurations.misra)));
+    config_file.add_line(format!(" *     TLV mode:           {0}", enabled(compiler_configurations.tlv)));
+    config_file.add_line(format!(" *     Named fields:       {0}", enabled(compiler_configurations.named_fields)));
+    config_file.add_line(format!(" *     Codec:              {0}", enabled(compiler_configurations.codec)));
+    config_file.add_line(format!(" *     Verifiers:          {0}", enabled(compiler_configurations.verifiers)));
+    config_file.add_line(format!(" *     Enum names:         {0}", enabled(compiler_configurations.enum_names)));
+    config_file.add_line(format!(" *     ABI probe:          {0}", enabled(compiler_configurations.abi_probe)));
+    config_file.add_line(format!(" *     Fuzz target:        {0}", enabled(compiler_configurations.fuzz)));
+    config_file.add_line(format!(" *     TypeScript:         {0}", compiler_configurations.typescript.as_deref().unwrap_or("none")));
+    config_file.add_line(String::from(" */"));
+    config_file.add_newline();
+
+    config_file.add_line(String::from("#ifndef RUNE_CONFIG_H"));
+    config_file.add_line(String::from("#define RUNE_CONFIG_H"));
+    config_file.add_newline();
+
+    // Values
+    // ———————
+
+    config_file.add_line(String::from("/** Value of __STDC_VERSION__ of the C standard the code complies with */"));
+    config_file.add_line(format!("#define RUNE_CONFIG_C_STANDARD {0}", compiler_configurations.c_standard.version_value()));
+    config_file.add_line(format!(
+        "#define RUNE_CONFIG_ARCHITECTURE_BITS {0}{1}",
+        compiler_configurations.architecture.byte_size() * 8,
+        compiler_configurations.unsigned_suffix()
+    ));
+
+    if let Some(section) = &compiler_configurations.section {
+        config_file.add_line(format!("#define RUNE_CONFIG_DATA_SECTION \"{0}\"", section));
+    }
+    config_file.add_newline();
+
+    // Flags
+    // ——————
+
+    config_file.add_line(String::from("/* Flags are only defined when enabled */"));
+    output_flag(&mut config_file, "GNU_EXTENSIONS", compiler_configurations.c_standard.allows_gnu_extensions());
+    output_flag(&mut config_file, "PACK_DATA", compiler_configurations.pack_data);
+    output_flag(&mut config_file, "PACK_METADATA", compiler_configurations.pack_metadata);
+    output_flag(&mut config_file, "SORTED", compiler_configurations.sort);
+    output_flag(&mut config_file, "VERIFIER_SUPPORT", compiler_configurations.verifier_support);
+    output_flag(&mut config_file, "STRICT_ENUM_WIDTH", compiler_configurations.strict_enum_width);
+    output_flag(&mut config_file, "SAFE_PACKED_ACCESS", compiler_configurations.safe_packed_access());
+    output_flag(&mut config_file, "EXPLICIT_PADDING", compiler_configurations.explicit_padding);
+    output_flag(&mut config_file, "MISRA", compiler_configurations.misra);
+    output_flag(&mut config_file, "TLV", compiler_configurations.tlv);
+    output_flag(&mut config_file, "NAMED_FIELDS", compiler_configurations.named_fields);
+    output_flag(&mut config_file, "CODEC", compiler_configurations.codec);
+    output_flag(&mut config_file, "VERIFIERS", compiler_configurations.verifiers);
+    output_flag(&mut config_file, "ENUM_NAMES", compiler_configurations.enum_names);
+    output_flag(&mut config_file, "ABI_PROBE", compiler_configurations.abi_probe);
+    output_flag(&mut config_file, "FUZZ", compiler_configurations.fuzz);
+    output_flag(&mut config_file, "TYPESCRIPT", compiler_configurations.typescript.is_some());
+    config_file.add_newline();
+
+    config_file.add_line(String::from("#endif /* RUNE_CONFIG_H */"));
+
+    config_file
+}
+
+#[cfg(test)]
+mod tests {
+    use rune_parser::RuneFileDescription;
+
+    use crate::{
+        generate_to_memory,
+        test_utilities::{compile_configurations, parse_schema}
+    };
+
+    /// Output the configuration header generated with the given command line arguments
+    fn config_header(arguments: &[&str]) -> String {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("frame.rune", "struct Frame {\n    Value: u8 = 1\n}\n")]);
+        let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &compile_configurations(&[&["--emit-config-header"], arguments].concat())).unwrap();
+
+        output_files.into_iter().find(|(name, _)| name == "rune_config.h").expect("Configuration header should be generated").1
+    }
+
+    #[test]
+    fn options_are_captured_as_defines() {
+        let header: String = config_header(&["--pack-data", "--data-section", ".rune_data", "--emit-codec", "--c-standard", "C99"]);
+
+        assert!(header.contains(" *     C standard:         C99 (-std=c99)\n"));
+        assert!(header.contains(" *     Pack data:          yes\n *     Pack metadata:      no\n *     Data section:       .rune_data\n"));
+        assert!(header.contains(" *     Codec:              yes\n"));
+        assert!(header.contains("#define RUNE_CONFIG_C_STANDARD 199901L\n#define RUNE_CONFIG_ARCHITECTURE_BITS 32\n#define RUNE_CONFIG_DATA_SECTION \".rune_data\"\n"));
+        assert!(header.contains("#define RUNE_CONFIG_PACK_DATA\n"));
+        assert!(header.contains("#define RUNE_CONFIG_CODEC\n"));
+        assert!(header.ends_with("#endif /* RUNE_CONFIG_H */\n"));
+    }
+
+    #[test]
+    fn disabled_flags_are_not_defined() {
+        let header: String = config_header(&["--unsorted", "--no-verifier-support"]);
+
+        assert!(header.contains(" *     Sorted:             no\n *     Verifier support:   no\n"));
+        assert!(header.contains(" *     Data section:       none\n"));
+        for flag in ["SORTED", "VERIFIER_SUPPORT", "PACK_DATA", "CODEC", "DATA_SECTION", "TYPESCRIPT"] {
+            assert!(!header.contains(&format!("RUNE_CONFIG_{0}", flag)), "RUNE_CONFIG_{0} should not be defined", flag);
+        }
+    }
+
+    #[test]
+    fn no_configuration_header_is_generated_by_default() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("frame.rune", "struct Frame {\n    Value: u8 = 1\n}\n")]);
+        let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &compile_configurations(&[])).unwrap();
+
+        assert!(output_files.iter().all(|(name, _)| name != "rune_config.h"));
+    }
+}
@@ -0,0 +1,229 @@
+use std::fs::read_to_string;
+
+use clap::{ArgMatches, parser::ValueSource};
+use serde::Deserialize;
+
+use crate::{Args, compile_error::CompilerError, output::*};
+
+/// Project configurations read from a TOML file. Keys match the long command line argument names, using underscores
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigurationFile {
+    input_folder:               Option<Vec<String>>,
+    output_folder:              Option<String>,
+    output_archive:             Option<String>,
+    architecture:               Option<usize>,
+    endianness:                 Option<String>,
+    pack_data:                  Option<bool>,
+    pack_metadata:              Option<bool>,
+    data_section:               Option<String>,
+    unsorted:                   Option<bool>,
+    silent:                     Option<bool>,
+    c_standard:                 Option<String>,
+    gnu_extensions:             Option<bool>,
+    compiler:                   Option<String>,
+    include_guard_style:        Option<String>,
+    indent:                     Option<String>,
+    line_endings:               Option<String>,
+    strict_enum_width:          Option<bool>,
+    strict_size_checks:         Option<bool>,
+    safe_packed_access:         Option<bool>,
+    emit_getters:               Option<bool>,
+    emit_debug_checks:          Option<bool>,
+    emit_named_fields:          Option<bool>,
+    emit_codec:                 Option<bool>,
+    emit_verifiers:             Option<bool>,
+    emit_enum_names:            Option<bool>,
+    emit_cpp:                   Option<bool>,
+    explicit_padding:           Option<bool>,
+    anonymous_bitfield_padding: Option<bool>,
+    emit_abi_probe:             Option<bool>,
+    emit_fuzz:                  Option<bool>,
+    emit_config_header:         Option<bool>,
+    emit_runtime_header:        Option<bool>,
+    emit_ts:                    Option<String>,
+    emit_proto:                 Option<String>,
+    emit_constants_only:        Option<String>,
+    definitions_output:         Option<String>,
+    only:                       Option<Vec<String>>,
+    exclude:                    Option<Vec<String>>,
+    exclude_files:              Option<Vec<String>>,
+    doxygen_comments:           Option<bool>,
+    flatten:                    Option<bool>,
+    inline_members:             Option<Vec<String>>,
+    align_members:              Option<Vec<String>>,
+    fixed_point_members:        Option<Vec<String>>,
+    namespace_from_path:        Option<bool>,
+    prefix:                     Option<String>,
+    amalgamate:                 Option<bool>,
+    ignore_extensions:          Option<bool>,
+    strict:                     Option<bool>,
+    no_descriptors:             Option<bool>,
+    no_verifier_support:        Option<bool>,
+    log_level:                  Option<String>,
+    debug:                      Option<bool>,
+    diff_schema:                Option<String>,
+    banner_file:                Option<String>,
+    banner_verbatim:            Option<bool>,
+    misra:                      Option<bool>,
+    tlv_mode:                   Option<bool>,
+    varint:                     Option<bool>,
+    depfile:                    Option<String>,
+    emit_layout_snapshot:       Option<String>,
+    manifest:                   Option<String>,
+    clean:                      Option<bool>,
+    stdout:                     Option<bool>
+}
+
+/// Whether an argument was explicitly passed on the command line, in which case it takes precedence over the configuration file
+fn is_from_command_line(matches: &ArgMatches, id: &str) -> bool {
+    matches.value_source(id) == Some(ValueSource::CommandLine)
+}
+
+impl ConfigurationFile {
+    pub fn read(path: &str) -> Result<ConfigurationFile, CompilerError> {
+        let string: String = match read_to_string(path) {
+            Ok(string) => string,
+            Err(error) => {
+                error!("Could not read configuration file \"{0}\". Got error {1}", path, error);
+                return Err(CompilerError::FileSystemError(error));
+            }
+        };
+
+        match toml::from_str(&string) {
+            Ok(configuration_file) => Ok(configuration_file),
+            Err(error) => {
+                error!("Could not parse configuration file \"{0}\". Got error {1}", path, error);
+                Err(CompilerError::InvalidArgument)
+            }
+        }
+    }
+
+    /// Apply the file values to the arguments. Values passed on the command line take precedence over file values, which take precedence over defaults
+    pub(crate) fn apply(self, args: &mut Args, matches: &ArgMatches) {
+        macro_rules! apply {
+            // Arguments with a value
+            ($field: ident) => {
+                if let Some(value) = self.$field
+                    && !is_from_command_line(matches, stringify!($field))
+                {
+                    args.$field = value;
+                }
+            };
+            // Optional arguments
+            (optional $field: ident) => {
+                if let Some(value) = self.$field
+                    && !is_from_command_line(matches, stringify!($field))
+                {
+                    args.$field = Some(value);
+                }
+            };
+        }
+
+        apply!(input_folder);
+        apply!(optional output_folder);
+        apply!(optional output_archive);
+        apply!(architecture);
+        apply!(endianness);
+        apply!(pack_data);
+        apply!(pack_metadata);
+        apply!(optional data_section);
+        apply!(unsorted);
+        apply!(silent);
+        apply!(c_standard);
+        apply!(gnu_extensions);
+        apply!(compiler);
+        apply!(include_guard_style);
+        apply!(indent);
+        apply!(line_endings);
+        apply!(strict_enum_width);
+        apply!(strict_size_checks);
+        apply!(safe_packed_access);
+        apply!(emit_getters);
+        apply!(emit_debug_checks);
+        apply!(emit_named_fields);
+        apply!(emit_codec);
+        apply!(emit_verifiers);
+        apply!(emit_enum_names);
+        apply!(emit_cpp);
+        apply!(explicit_padding);
+        apply!(anonymous_bitfield_padding);
+        apply!(emit_abi_probe);
+        apply!(emit_fuzz);
+        apply!(emit_config_header);
+        apply!(emit_runtime_header);
+        apply!(optional emit_ts);
+        apply!(optional emit_proto);
+        apply!(optional emit_constants_only);
+        apply!(definitions_output);
+        apply!(only);
+        apply!(exclude);
+        apply!(exclude_files);
+        apply!(doxygen_comments);
+        apply!(flatten);
+        apply!(inline_members);
+        apply!(align_members);
+        apply!(fixed_point_members);
+        apply!(namespace_from_path);
+        apply!(optional prefix);
+        apply!(amalgamate);
+        apply!(ignore_extensions);
+        apply!(strict);
+        apply!(no_descriptors);
+        apply!(no_verifier_support);
+        apply!(log_level);
+        apply!(debug);
+        apply!(optional diff_schema);
+        apply!(optional banner_file);
+        apply!(banner_verbatim);
+        apply!(misra);
+        apply!(tlv_mode);
+        apply!(varint);
+        apply!(optional depfile);
+        apply!(optional emit_layout_snapshot);
+        apply!(optional manifest);
+        apply!(clean);
+        apply!(stdout);
+
+        // An output target passed on the command line replaces the one from the file
+        if is_from_command_line(matches, "output_archive") && !is_from_command_line(matches, "output_folder") {
+            args.output_folder = None;
+        } else if is_from_command_line(matches, "output_folder") && !is_from_command_line(matches, "output_archive") {
+            args.output_archive = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::{ArgMatches, CommandFactory, FromArgMatches};
+
+    use super::ConfigurationFile;
+    use crate::Args;
+
+    fn applied_args(configuration: &str, arguments: &[&str]) -> Args {
+        let matches: ArgMatches = Args::command().get_matches_from(["rune_c_compiler"].iter().chain(arguments));
+        let mut args: Args = Args::from_arg_matches(&matches).unwrap();
+
+        toml::from_str::<ConfigurationFile>(configuration).unwrap().apply(&mut args, &matches);
+
+        args
+    }
+
+    #[test]
+    fn file_values_apply_to_unset_arguments() {
+        let args: Args = applied_args("stdout = true\nc_standard = \"C11\"\ngnu_extensions = false\n", &[]);
+
+        assert!(args.stdout);
+        assert_eq!(args.c_standard, "C11");
+        assert!(!args.gnu_extensions);
+    }
+
+    #[test]
+    fn command_line_arguments_take_precedence() {
+        let args: Args = applied_args("c_standard = \"C11\"\nunsorted = true\n", &["--c-standard", "C99"]);
+
+        assert_eq!(args.c_standard, "C99");
+        assert!(args.unsorted);
+    }
+}
@@ -0,0 +1,183 @@
+use rune_parser::{
+    RuneFileDescription,
+    types::{DefineValue, StructDefinition}
+};
+
+use crate::{
+    c_utilities::{CConfigurations, CNumericValue, CStructDefinition, output_disclaimer, pascal_to_uppercase, sorted_struct_definitions},
+    compile_error::CompilerError,
+    output_file::OutputFile
+};
+
+/// Convert a file name such as rune_constants.h to a guard such as RUNE_CONSTANTS_H
+fn guard_from_file_name(file_name: &str) -> String {
+    file_name
+        .chars()
+        .map(|letter| match letter.is_ascii_alphanumeric() {
+            true => letter.to_ascii_uppercase(),
+            false => '_'
+        })
+        .collect()
+}
+
+/// Outputs a single header containing only the protocol constants as macros, without any typedefs or descriptors
+pub fn output_constants(file_descriptions: &Vec<RuneFileDescription>, configurations: &CConfigurations, file_name: &str, selection: &[String]) -> Result<OutputFile, CompilerError> {
+    let struct_definitions: Vec<StructDefinition> = sorted_struct_definitions(file_descriptions);
+
+    let mut constants_file: OutputFile = OutputFile::new(String::from(file_name), &configurations.compiler_configurations);
+
+    let guard: String = guard_from_file_name(file_name);
+    let unsigned_suffix: &'static str = configurations.compiler_configurations.unsigned_suffix();
+
+    // Disclaimers
+    // ————————————
+
+    output_disclaimer(&mut constants_file, &configurations.compiler_configurations);
+
+    // Start guard
+    // ————————————
+
+    constants_file.add_line(format!("#ifndef {0}", guard));
+    constants_file.add_line(format!("#define {0}", guard));
+    constants_file.add_newline();
+
+    // Stamps
+    // ———————
+
+    constants_file.add_line("/** Version of the Rune C compiler that generated these constants */".to_string());
+    constants_file.add_line(format!("#define RUNE_COMPILER_VERSION \"{0}\"", env!("CARGO_PKG_VERSION")));
+    constants_file.add_newline();
+
+    constants_file.add_line("/** Amount of declared messages, and the largest declared field index */".to_string());
+    constants_file.add_line(format!("#define RUNE_MESSAGE_COUNT       {0}{1}", struct_definitions.len(), unsigned_suffix));
+    constants_file.add_line(format!("#define RUNE_LARGEST_FIELD_INDEX {0}{1}", configurations.largest_message_index, unsigned_suffix));
+    constants_file.add_newline();
+
+    // Messages
+    // —————————
+
+    if !struct_definitions.is_empty() {
+        constants_file.add_line("// Messages".to_string());
+        constants_file.add_line("// —————————".to_string());
+        constants_file.add_newline();
+
+        // Message indexes follow the alphabetical ordering of all declared structs
+        for (index, struct_definition) in struct_definitions.iter().enumerate() {
+            if !selection.contains(&struct_definition.name) {
+                continue;
+            }
+
+            let struct_name: String = pascal_to_uppercase(&struct_definition.name);
+
+            constants_file.add_line(format!("#define {0}_INDEX {1}{2}", struct_name, index, unsigned_suffix));
+            constants_file.add_line(format!(
+                "#define {0}_SIZE  {1}{2}",
+                struct_name,
+                struct_definition.estimate_size(&configurations.compiler_configurations)?,
+                unsigned_suffix
+            ));
+            constants_file.add_newline();
+        }
+    }
+
+    // Enums
+    // ——————
+
+    for file in file_descriptions {
+        for enum_definition in &file.definitions.enums {
+            let enum_name: String = pascal_to_uppercase(&enum_definition.name);
+
+            // Print comment if present
+            if let Some(comment) = &enum_definition.comment {
+                constants_file.add_doc_comment(0, comment);
+            }
+
+            for member in &enum_definition.members {
+                constants_file.add_line(format!(
+                    "#define {0}_{1} {2}",
+                    enum_name,
+                    pascal_to_uppercase(&member.identifier),
+                    member.value.to_c_literal(!enum_definition.backing_type.is_signed(), &configurations.compiler_configurations)
+                ));
+            }
+
+            constants_file.add_newline();
+        }
+    }
+
+    // User defines
+    // —————————————
+
+    for file in file_descriptions {
+        if file.definitions.defines.is_empty() {
+            continue;
+        }
+
+        for define in &file.definitions.defines {
+            // Check if the value has been redefined. If so, use the redefined value
+            let value: &DefineValue = match &define.redefinition {
+                Some(redefine) => &redefine.value,
+                None => &define.value
+            };
+
+            let define_value: String = match value {
+                DefineValue::NoValue => String::from(""),
+                DefineValue::NumericLiteral(value) => value.to_c_literal(true, &configurations.compiler_configurations)
+            };
+
+            constants_file.add_line(format!("#define {0} {1}", define.name, define_value));
+        }
+
+        constants_file.add_newline();
+    }
+
+    // End guard
+    // ——————————
+
+    constants_file.add_line(format!("#endif /* {0} */", guard));
+
+    Ok(constants_file)
+}
+
+#[cfg(test)]
+mod tests {
+    use rune_parser::RuneFileDescription;
+
+    use crate::{
+        generate_to_memory,
+        test_utilities::{compile_configurations, parse_schema}
+    };
+
+    const SCHEMA: &str =
+        "define MaxLength 16;\n\nenum Mode: u8 {\n    Idle = 0;\n    Running = 2\n}\n\nstruct Status {\n    Code: u8 = 1;\n    Current: Mode = 2\n}\n\nstruct Frame {\n    Value: u32 = 1\n}\n";
+
+    /// Output the constants header generated from the schema with the given command line arguments
+    fn constants_header(arguments: &[&str]) -> String {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("schema.rune", SCHEMA)]);
+        let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &compile_configurations(&[&["--emit-constants-only", "protocol.h"], arguments].concat())).unwrap();
+
+        assert_eq!(output_files.len(), 1);
+        assert_eq!(output_files[0].0, "protocol.h");
+
+        output_files.into_iter().next().unwrap().1
+    }
+
+    #[test]
+    fn only_the_protocol_constants_are_output() {
+        let expected: String = format!(
+            "#ifndef PROTOCOL_H\n#define PROTOCOL_H\n\n/** Version of the Rune C compiler that generated these constants */\n#define RUNE_COMPILER_VERSION \"{0}\"\n\n/** Amount of declared messages, and the largest declared field index */\n#define RUNE_MESSAGE_COUNT       2\n#define RUNE_LARGEST_FIELD_INDEX 2\n\n// Messages\n// —————————\n\n#define FRAME_INDEX 0\n#define FRAME_SIZE  4\n\n#define STATUS_INDEX 1\n#define STATUS_SIZE  2\n\n#define MODE_IDLE 0\n#define MODE_RUNNING 2\n\n#define MaxLength 16\n\n#endif /* PROTOCOL_H */\n",
+            env!("CARGO_PKG_VERSION")
+        );
+
+        assert_eq!(constants_header(&[]), expected);
+    }
+
+    #[test]
+    fn unselected_messages_keep_the_indexes_of_the_others() {
+        let header: String = constants_header(&["--only", "Status"]);
+
+        assert!(header.contains("#define STATUS_INDEX 1"));
+        assert!(!header.contains("FRAME_INDEX"));
+        assert!(!header.contains("typedef"));
+    }
+}
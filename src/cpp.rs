@@ -0,0 +1,439 @@
+use rune_parser::{
+    RuneFileDescription,
+    types::{ArrayType, BitfieldDefinition, FieldType, IncludeDefinition, Primitive, StructDefinition, StructMember, UserDefinitionLink}
+};
+
+use crate::{
+    c_standard::CStandard,
+    c_utilities::{CArraySize, CConfigurations, CPrimitive, CStructMember, include_base_name, output_base_name, output_disclaimer, pascal_to_snake_case, pascal_to_uppercase, sibling_base_name},
+    compile_error::CompilerError,
+    header::{header_guard, is_deferred_include},
+    include_guard_style::IncludeGuardStyle,
+    output_file::OutputFile
+};
+
+// C++ wrappers
+// —————————————
+//
+// Every struct is wrapped by a class of its Rune name, holding the C struct and accessing its members through methods of their Rune
+// names. Nested structs are returned and taken as their wrappers, fully qualified as members are often named after their type,
+// while all other members are returned and taken as their C type, with arrays accessed one element at a time. Values are copied
+// in and out, as packed members cannot be bound to references.
+// Comparison operators are defined on the C types themselves, comparing members one by one so padding never affects the result.
+
+/// A member as accessed through the wrapper
+struct CppMember {
+    /// Rune name of the member, used for its accessors
+    name:       String,
+    /// C name of the member
+    c_name:     String,
+    /// Type the member is returned and taken as
+    cpp_type:   String,
+    /// Whether the member is a nested struct, returned and taken as its wrapper
+    is_struct:  bool,
+    /// Index parameters of the member and their lengths, one per array dimension
+    dimensions: Vec<(&'static str, String)>
+}
+
+/// Members of the struct in field index order, as they are accessed through the wrapper
+fn cpp_members(configurations: &CConfigurations, struct_definition: &StructDefinition) -> Result<Vec<CppMember>, CompilerError> {
+    let mut members: Vec<StructMember> = struct_definition
+        .members
+        .iter()
+        .filter(|member| member.data_type != FieldType::Empty)
+        .map(|member| member.storage_member(&configurations.compiler_configurations))
+        .collect();
+    members.sort_by_key(|member| member.index.value());
+
+    let c_standard: &CStandard = &configurations.compiler_configurations.c_standard;
+
+    // 128 bit integers are stored as byte arrays, and thus accessed one byte at a time
+    let byte_dimension = |primitive: &Primitive| -> Option<(&'static str, String)> {
+        match primitive {
+            Primitive::I128 | Primitive::U128 => Some(("byte", format!("{0}{1}", primitive.c_size(), configurations.compiler_configurations.unsigned_suffix()))),
+            _ => None
+        }
+    };
+
+    let mut cpp_members: Vec<CppMember> = Vec::with_capacity(members.len());
+
+    for member in &members {
+        let is_struct: bool = matches!(member.user_definition_link, UserDefinitionLink::StructLink(_));
+
+        let (cpp_type, dimensions): (String, Vec<(&'static str, String)>) = match &member.data_type {
+            FieldType::Primitive(primitive) => (primitive.to_c_type(c_standard)?, byte_dimension(primitive).into_iter().collect()),
+            FieldType::UserDefined(name) => (
+                match is_struct {
+                    true => format!("::rune::{0}", name),
+                    false => format!("{0}_t", pascal_to_snake_case(name))
+                },
+                Vec::new()
+            ),
+            FieldType::Array(array_type, array_size) => {
+                let length: (&'static str, String) = ("index", array_size.to_c_literal(&configurations.compiler_configurations));
+
+                match array_type {
+                    ArrayType::Primitive(primitive) => (primitive.to_c_type(c_standard)?, std::iter::once(length).chain(byte_dimension(primitive)).collect()),
+                    ArrayType::UserDefined(name) => (
+                        match is_struct {
+                            true => format!("::rune::{0}", name),
+                            false => format!("{0}_t", pascal_to_snake_case(name))
+                        },
+                        vec![length]
+                    )
+                }
+            },
+            FieldType::Empty => continue
+        };
+
+        cpp_members.push(CppMember {
+            name: member.identifier.clone(),
+            c_name: pascal_to_snake_case(&member.identifier),
+            cpp_type,
+            is_struct,
+            dimensions
+        });
+    }
+
+    Ok(cpp_members)
+}
+
+/// Outputs the comparison operators of a C type, given as the C names of the members to compare and their array lengths
+fn output_comparison_operators(hpp_file: &mut OutputFile, type_name: &str, members: &[(String, Vec<String>)]) {
+    hpp_file.add_line(format!("inline bool operator==(const {0}& left, const {0}& right) {{", type_name));
+
+    if members.is_empty() {
+        hpp_file.add_indented_line(1, String::from("(void)left;"));
+        hpp_file.add_indented_line(1, String::from("(void)right;"));
+    }
+
+    for (member_name, lengths) in members {
+        let indexes: Vec<String> = (0..lengths.len()).map(|dimension| format!("i{0}", dimension)).collect();
+
+        for (dimension, length) in lengths.iter().enumerate() {
+            hpp_file.add_indented_line(1 + dimension, format!("for (std::size_t {0} = 0; {0} < {1}; {0}++) {{", indexes[dimension], length));
+        }
+
+        let access: String = indexes.iter().map(|index| format!("[{0}]", index)).collect();
+
+        hpp_file.add_indented_line(1 + lengths.len(), format!("if (!(left.{0}{1} == right.{0}{1})) {{", member_name, access));
+        hpp_file.add_indented_line(2 + lengths.len(), String::from("return false;"));
+        hpp_file.add_indented_line(1 + lengths.len(), String::from("}"));
+
+        for dimension in (0..lengths.len()).rev() {
+            hpp_file.add_indented_line(1 + dimension, String::from("}"));
+        }
+    }
+
+    hpp_file.add_indented_line(1, String::from("return true;"));
+    hpp_file.add_line(String::from("}"));
+    hpp_file.add_newline();
+
+    hpp_file.add_line(format!("inline bool operator!=(const {0}& left, const {0}& right) {{", type_name));
+    hpp_file.add_indented_line(1, String::from("return !(left == right);"));
+    hpp_file.add_line(String::from("}"));
+    hpp_file.add_newline();
+}
+
+/// Outputs the comparison operators of a bitfield, comparing its fields but not its padding
+fn output_bitfield_operators(hpp_file: &mut OutputFile, bitfield_definition: &BitfieldDefinition) {
+    let members: Vec<(String, Vec<String>)> = bitfield_definition.members.iter().map(|member| (pascal_to_snake_case(&member.identifier), Vec::new())).collect();
+
+    output_comparison_operators(hpp_file, &format!("{0}_t", pascal_to_snake_case(&bitfield_definition.name)), &members);
+}
+
+/// Outputs the comparison operators of a struct, comparing its members but not its padding
+fn output_struct_operators(hpp_file: &mut OutputFile, configurations: &CConfigurations, struct_definition: &StructDefinition) -> Result<(), CompilerError> {
+    let members: Vec<(String, Vec<String>)> = cpp_members(configurations, struct_definition)?
+        .into_iter()
+        .map(|member| (member.c_name, member.dimensions.into_iter().map(|(_, length)| length).collect()))
+        .collect();
+
+    output_comparison_operators(hpp_file, &format!("{0}_t", pascal_to_snake_case(&struct_definition.name)), &members);
+
+    Ok(())
+}
+
+/// Outputs the wrapper class of a struct
+fn output_wrapper(hpp_file: &mut OutputFile, configurations: &CConfigurations, struct_definition: &StructDefinition) -> Result<(), CompilerError> {
+    let struct_name: String = pascal_to_snake_case(&struct_definition.name);
+    let class_name: &String = &struct_definition.name;
+
+    if let Some(comment) = &struct_definition.comment {
+        hpp_file.add_doc_comment(0, comment);
+    }
+
+    hpp_file.add_line(format!("class {0} {{", class_name));
+    hpp_file.add_line(String::from("public:"));
+
+    // Construction
+    // —————————————
+
+    hpp_file.add_indented_line(1, String::from("/** Zero initialized message */"));
+    hpp_file.add_indented_line(1, format!("{0}() : message() {{}}", class_name));
+    hpp_file.add_indented_line(1, format!("explicit {0}(const {1}_t& value) : message(value) {{}}", class_name, struct_name));
+    hpp_file.add_newline();
+
+    hpp_file.add_indented_line(1, String::from("/** The wrapped C struct */"));
+    hpp_file.add_indented_line(1, format!("const {0}_t& c_struct() const {{ return message; }}", struct_name));
+    hpp_file.add_indented_line(1, format!("{0}_t& c_struct() {{ return message; }}", struct_name));
+    hpp_file.add_newline();
+
+    if configurations.compiler_configurations.descriptors {
+        hpp_file.add_indented_line(1, format!("static const rune_descriptor_t& descriptor() {{ return {0}_descriptor; }}", struct_name));
+        hpp_file.add_newline();
+    }
+
+    // Accessors
+    // ——————————
+
+    let members: Vec<CppMember> = cpp_members(configurations, struct_definition)?;
+
+    for member in &members {
+        let parameters: Vec<String> = member.dimensions.iter().map(|(index, _)| format!("std::size_t {0}", index)).collect();
+        let access: String = format!("message.{0}{1}", member.c_name, member.dimensions.iter().map(|(index, _)| format!("[{0}]", index)).collect::<String>());
+
+        let (getter_value, setter_type, setter_value): (String, String, &'static str) = match member.is_struct {
+            true => (format!("{0}({1})", member.cpp_type, access), format!("const {0}&", member.cpp_type), "value.c_struct()"),
+            false => (access.clone(), member.cpp_type.clone(), "value")
+        };
+
+        hpp_file.add_indented_line(1, format!("{0} {1}({2}) const {{ return {3}; }}", member.cpp_type, member.name, parameters.join(", "), getter_value));
+        hpp_file.add_indented_line(
+            1,
+            format!(
+                "void Set{0}({1}) {{ {2} = {3}; }}",
+                member.name,
+                parameters.iter().cloned().chain(std::iter::once(format!("{0} value", setter_type))).collect::<Vec<String>>().join(", "),
+                access,
+                setter_value
+            )
+        );
+    }
+
+    if !members.is_empty() {
+        hpp_file.add_newline();
+    }
+
+    // Codec
+    // ——————
+
+    if configurations.compiler_configurations.codec {
+        hpp_file.add_indented_line(1, format!("static constexpr std::size_t EncodedSize = {0}_ENCODED_SIZE;", pascal_to_uppercase(&struct_definition.name)));
+        hpp_file.add_indented_line(
+            1,
+            format!(
+                "[[nodiscard]] std::size_t serialize(unsigned char* buffer, std::size_t capacity) const {{ return {0}_serialize(&message, buffer, capacity); }}",
+                struct_name
+            )
+        );
+        hpp_file.add_indented_line(
+            1,
+            format!(
+                "[[nodiscard]] bool deserialize(const unsigned char* buffer, std::size_t length) {{ return {0}_deserialize(&message, buffer, length); }}",
+                struct_name
+            )
+        );
+        hpp_file.add_newline();
+    }
+
+    // Comparison
+    // ———————————
+
+    hpp_file.add_indented_line(
+        1,
+        format!("friend bool operator==(const {0}& left, const {0}& right) {{ return left.message == right.message; }}", class_name)
+    );
+    hpp_file.add_indented_line(
+        1,
+        format!("friend bool operator!=(const {0}& left, const {0}& right) {{ return left.message != right.message; }}", class_name)
+    );
+    hpp_file.add_newline();
+
+    hpp_file.add_line(String::from("private:"));
+    hpp_file.add_indented_line(1, format!("{0}_t message;", struct_name));
+    hpp_file.add_line(String::from("};"));
+    hpp_file.add_newline();
+
+    Ok(())
+}
+
+/// Outputs a C++ header wrapping the structs of the file in classes, next to the C header it includes
+pub fn output_cpp_header(file: &RuneFileDescription, file_descriptions: &Vec<RuneFileDescription>, configurations: &CConfigurations) -> Result<OutputFile, CompilerError> {
+    let extension: String = configurations.compiler_configurations.output_extension("hpp");
+
+    let hpp_file_string: String = format!("{0}.{1}", output_base_name(file, &configurations.compiler_configurations), extension);
+
+    let mut hpp_file: OutputFile = OutputFile::new(hpp_file_string, &configurations.compiler_configurations);
+
+    output_disclaimer(&mut hpp_file, &configurations.compiler_configurations);
+
+    // Start & C++ guards
+    // ———————————————————
+
+    let guard: String = header_guard(file, configurations, &extension);
+
+    match configurations.compiler_configurations.include_guard_style {
+        IncludeGuardStyle::Ifndef => {
+            hpp_file.add_line(format!("#ifndef {0}", guard));
+            hpp_file.add_line(format!("#define {0}", guard));
+        },
+        IncludeGuardStyle::PragmaOnce => hpp_file.add_line(String::from("#pragma once"))
+    }
+    hpp_file.add_newline();
+
+    hpp_file.add_line(String::from("#ifdef __cplusplus"));
+    hpp_file.add_newline();
+
+    // C header
+    // —————————
+
+    hpp_file.add_line(format!(
+        "#include \"{0}.{1}\"",
+        sibling_base_name(file, &configurations.compiler_configurations),
+        configurations.compiler_configurations.output_extension("h")
+    ));
+
+    hpp_file.add_newline();
+
+    // File inclusions
+    // ————————————————
+
+    hpp_file.add_line(String::from("#include <cstddef>"));
+    hpp_file.add_newline();
+
+    let (deferred_includes, includes): (Vec<&IncludeDefinition>, Vec<&IncludeDefinition>) = file
+        .definitions
+        .includes
+        .iter()
+        .partition(|include_definition| is_deferred_include(file, include_definition, file_descriptions));
+
+    if !includes.is_empty() {
+        for include_definition in &includes {
+            hpp_file.add_line(format!(
+                "#include \"{0}.rune.hpp\"",
+                include_base_name(include_definition, file_descriptions, &configurations.compiler_configurations)
+            ));
+        }
+        hpp_file.add_newline();
+    }
+
+    // Comparison operators
+    // —————————————————————
+
+    for bitfield_definition in &file.definitions.bitfields {
+        output_bitfield_operators(&mut hpp_file, bitfield_definition);
+    }
+
+    for struct_definition in &file.definitions.structs {
+        output_struct_operators(&mut hpp_file, configurations, struct_definition)?;
+    }
+
+    // Wrappers
+    // —————————
+
+    if !file.definitions.structs.is_empty() {
+        hpp_file.add_line(String::from("namespace rune {"));
+        hpp_file.add_newline();
+
+        for struct_definition in &file.definitions.structs {
+            output_wrapper(&mut hpp_file, configurations, struct_definition)?;
+        }
+
+        hpp_file.add_line(String::from("} /* namespace rune */"));
+        hpp_file.add_newline();
+    }
+
+    // Deferred includes
+    // ——————————————————
+
+    if !deferred_includes.is_empty() {
+        hpp_file.add_line(String::from("/* Included last, as these include this file back */"));
+
+        for include_definition in &deferred_includes {
+            hpp_file.add_line(format!(
+                "#include \"{0}.rune.hpp\"",
+                include_base_name(include_definition, file_descriptions, &configurations.compiler_configurations)
+            ));
+        }
+
+        hpp_file.add_newline();
+    }
+
+    // End & C++ guards
+    // —————————————————
+
+    hpp_file.add_line(String::from("#endif /* __cplusplus */"));
+
+    if configurations.compiler_configurations.include_guard_style == IncludeGuardStyle::Ifndef {
+        hpp_file.add_newline();
+        hpp_file.add_line(format!("#endif /* {0} */", guard));
+    }
+
+    Ok(hpp_file)
+}
+
+#[cfg(test)]
+mod tests {
+    use rune_parser::RuneFileDescription;
+
+    use crate::{
+        generate_to_memory,
+        test_utilities::{compile_configurations, parse_schema}
+    };
+
+    const SCHEMA: &str =
+        "bitfield Flags: u8 {\n    Enabled: u1 = 0;\n    Level: i3 = 1\n}\n\nstruct Probe {\n    Flag: u8 = 1;\n    Count: u32 = 2;\n    Options: Flags = 3;\n    Samples: [u16; 2] = 4\n}\n";
+
+    /// Output the C++ header generated from the schema with the given command line arguments
+    fn cpp_header(arguments: &[&str]) -> String {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("probe.rune", SCHEMA)]);
+        let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &compile_configurations(&[&["--emit-cpp"], arguments].concat())).unwrap();
+
+        output_files.into_iter().find(|(name, _)| name == "probe.rune.hpp").expect("C++ header should be generated").1
+    }
+
+    #[test]
+    fn structs_are_compared_member_by_member() {
+        // Explicit padding members are named struct members, yet their contents are not part of the message
+        let header: String = cpp_header(&["--explicit-padding"]);
+
+        assert!(header.contains(
+            "inline bool operator==(const probe_t& left, const probe_t& right) {\n    if (!(left.flag == right.flag)) {\n        return false;\n    }\n    if (!(left.count == right.count)) {\n        return false;\n    }\n    if (!(left.options == right.options)) {\n        return false;\n    }\n    for (std::size_t i0 = 0; i0 < 2; i0++) {\n        if (!(left.samples[i0] == right.samples[i0])) {\n            return false;\n        }\n    }\n    return true;\n}"
+        ));
+        assert!(header.contains("inline bool operator!=(const probe_t& left, const probe_t& right) {\n    return !(left == right);\n}"));
+        assert!(!header.contains("_pad"));
+        assert!(!header.contains("memcmp"));
+    }
+
+    #[test]
+    fn bitfields_are_compared_without_their_padding() {
+        let header: String = cpp_header(&[]);
+
+        assert!(header.contains(
+            "inline bool operator==(const flags_t& left, const flags_t& right) {\n    if (!(left.enabled == right.enabled)) {\n        return false;\n    }\n    if (!(left.level == right.level)) {\n        return false;\n    }\n    return true;\n}"
+        ));
+        assert!(!header.contains("left.padding"));
+    }
+
+    #[test]
+    fn wrappers_compare_the_wrapped_structs() {
+        let header: String = cpp_header(&[]);
+
+        assert!(header.contains("    friend bool operator==(const Probe& left, const Probe& right) { return left.message == right.message; }"));
+        assert!(header.contains("    friend bool operator!=(const Probe& left, const Probe& right) { return left.message != right.message; }"));
+    }
+
+    #[test]
+    fn wrappers_serialize_through_the_codec_functions() {
+        let header: String = cpp_header(&["--emit-codec"]);
+
+        assert!(header.contains("    static constexpr std::size_t EncodedSize = PROBE_ENCODED_SIZE;"));
+        assert!(header.contains("    [[nodiscard]] std::size_t serialize(unsigned char* buffer, std::size_t capacity) const { return probe_serialize(&message, buffer, capacity); }"));
+        assert!(header.contains("    [[nodiscard]] bool deserialize(const unsigned char* buffer, std::size_t length) { return probe_deserialize(&message, buffer, length); }"));
+
+        let header: String = cpp_header(&[]);
+        assert!(!header.contains("serialize("));
+        assert!(!header.contains("EncodedSize"));
+    }
+}
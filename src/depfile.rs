@@ -0,0 +1,188 @@
+use std::path::{Path, PathBuf};
+
+use rune_parser::RuneFileDescription;
+
+use crate::{
+    c_utilities::{CompileConfigurations, collect_dependencies, output_base_name},
+    compile_error::CompilerError,
+    output::*,
+    output_file::{OutputFile, join_relative_path, split_file_path},
+    selection::is_excluded_file
+};
+
+/// Escape a path for use in a Makefile rule
+fn escape_path(path: &str) -> String {
+    path.replace(' ', "\\ ").replace('#', "\\#")
+}
+
+/// Names of the header, source and C++ wrapper generated from a Rune file, relative to the output folder
+fn rune_file_outputs(file: &RuneFileDescription, configurations: &CompileConfigurations) -> Vec<String> {
+    let output_base: String = output_base_name(file, configurations);
+
+    ["h", "c", "hpp"]
+        .iter()
+        .map(|extension| format!("{0}.{1}", output_base, configurations.output_extension(extension)))
+        .collect()
+}
+
+/// Find the path of the Rune file a description was parsed from, by searching the input folders for it
+fn source_path(file: &RuneFileDescription, input_paths: &[&Path]) -> Result<String, CompilerError> {
+    for input_path in input_paths {
+        let path: PathBuf = join_relative_path(input_path, &format!("{0}{1}.rune", file.relative_path, file.name));
+
+        if path.is_file() {
+            return Ok(path.display().to_string());
+        }
+    }
+
+    error!("Could not find the source of {0}{1}.rune in any input folder!", file.relative_path, file.name);
+    Err(CompilerError::InvalidInputPath)
+}
+
+/// Outputs a Makefile syntax dependency file, listing the generated files and the Rune files they were generated from. The targets are
+/// the files written to the output folder, so every generated file is listed, and the extra files written outside of it, such as the
+/// output archive, manifest and layout snapshot, which depend on every Rune file
+pub fn output_depfile(
+    file_descriptions: &Vec<RuneFileDescription>,
+    input_paths: &[&Path],
+    output_path: &Path,
+    output_files: &[(String, String)],
+    extra_targets: &[&str],
+    configurations: &CompileConfigurations,
+    depfile_path: &str
+) -> Result<(), CompilerError> {
+    let (depfile_folder, depfile_name): (&Path, String) = split_file_path("--depfile", depfile_path)?;
+
+    let mut dependency_file: OutputFile = OutputFile::new(depfile_name, configurations);
+
+    // Aggregated outputs depend on every Rune file
+    let mut all_sources: Vec<String> = Vec::with_capacity(file_descriptions.len());
+
+    for file in file_descriptions {
+        all_sources.push(escape_path(&source_path(file, input_paths)?));
+    }
+
+    let output_target = |name: &str| escape_path(&join_relative_path(output_path, name).display().to_string());
+
+    // Per file outputs depend on their own Rune file, as well as every file they transitively include. The amalgamated header and
+    // source mirror no single Rune file, so they depend on every Rune file like all other outputs
+    let mut per_file_rules: Vec<(Vec<String>, Vec<String>)> = Vec::with_capacity(file_descriptions.len());
+    let mut per_file_names: Vec<String> = Vec::with_capacity(file_descriptions.len() * 3);
+
+    if !configurations.amalgamate && configurations.constants_only.is_none() {
+        for file in file_descriptions.iter().filter(|file| !is_excluded_file(file, configurations)) {
+            let file_outputs: Vec<String> = rune_file_outputs(file, configurations);
+            let targets: Vec<String> = output_files.iter().filter(|(name, _)| file_outputs.contains(name)).map(|(name, _)| output_target(name)).collect();
+
+            if targets.is_empty() {
+                continue;
+            }
+
+            let mut dependencies: Vec<&RuneFileDescription> = Vec::with_capacity(0x10);
+            collect_dependencies(file, file_descriptions, &mut dependencies);
+
+            let mut sources: Vec<String> = Vec::with_capacity(dependencies.len());
+
+            for dependency in dependencies {
+                sources.push(escape_path(&source_path(dependency, input_paths)?));
+            }
+
+            per_file_names.extend(file_outputs);
+            per_file_rules.push((targets, sources));
+        }
+    }
+
+    let mut generated_targets: Vec<String> = output_files.iter().filter(|(name, _)| !per_file_names.contains(name)).map(|(name, _)| output_target(name)).collect();
+    generated_targets.extend(extra_targets.iter().map(|target| escape_path(target)));
+
+    if !generated_targets.is_empty() {
+        dependency_file.add_line(format!("{0}: {1}", generated_targets.join(" "), all_sources.join(" ")));
+    }
+
+    for (targets, sources) in per_file_rules {
+        dependency_file.add_line(format!("{0}: {1}", targets.join(" "), sources.join(" ")));
+    }
+
+    dependency_file.output_file(depfile_folder)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::{read_to_string, remove_dir_all},
+        path::{Path, PathBuf}
+    };
+
+    use rune_parser::{RuneFileDescription, parser_rune_files};
+
+    use super::output_depfile;
+    use crate::{
+        c_utilities::{CompileConfigurations, link_user_definitions},
+        generate_to_memory,
+        test_utilities::{compile_configurations, write_schema}
+    };
+
+    /// Generate the files for a schema with the given arguments, and output the depfile of the given extra targets to the schema folder
+    fn depfile_lines(arguments: &[&str], extra_targets: &[&str]) -> Vec<String> {
+        let schema_folder: PathBuf = write_schema(&[
+            ("common.rune", "struct Header {\n    Id: u16 = 1\n}\n"),
+            ("device.rune", "include \"common.rune\";\n\nstruct Device {\n    Head: Header = 1\n}\n")
+        ]);
+        let input_paths: [&Path; 1] = [Path::new(&schema_folder)];
+
+        let mut file_descriptions: Vec<RuneFileDescription> = parser_rune_files(&input_paths, true, true).unwrap();
+        link_user_definitions(&mut file_descriptions).unwrap();
+
+        let configurations: CompileConfigurations = compile_configurations(arguments);
+        let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &configurations).unwrap();
+        let depfile_path: PathBuf = schema_folder.join("rune.d");
+
+        output_depfile(
+            &file_descriptions,
+            &input_paths,
+            Path::new("out"),
+            &output_files,
+            extra_targets,
+            &configurations,
+            depfile_path.to_str().unwrap()
+        )
+        .unwrap();
+
+        let depfile: String = read_to_string(&depfile_path).unwrap().replace(&format!("{0}/", schema_folder.display()), "");
+        remove_dir_all(&schema_folder).unwrap();
+
+        depfile.lines().map(String::from).collect()
+    }
+
+    #[test]
+    fn rune_file_outputs_depend_on_their_includes() {
+        let lines: Vec<String> = depfile_lines(&[], &[]);
+
+        // The order of the Rune files follows the order they are found in the input folder
+        assert!(lines[0] == "out/runic_definitions.h: common.rune device.rune" || lines[0] == "out/runic_definitions.h: device.rune common.rune");
+        assert!(lines.contains(&String::from("out/common.rune.h out/common.rune.c: common.rune")));
+        assert!(lines.contains(&String::from("out/device.rune.h out/device.rune.c: device.rune common.rune")));
+    }
+
+    #[test]
+    fn every_generated_file_is_a_target() {
+        let lines: Vec<String> = depfile_lines(
+            &[
+                "--tlv-mode",
+                "--emit-named-fields",
+                "--emit-ts",
+                "types.ts",
+                "--emit-proto",
+                "types.proto",
+                "--emit-abi-probe",
+                "--emit-config-header"
+            ],
+            &["layout.txt"]
+        );
+
+        for target in ["rune_tlv.h", "rune_tlv.c", "rune_named_fields.h", "rune_named_fields.c", "types.ts", "types.proto", "rune_config.h"] {
+            assert!(lines[0].contains(&format!("out/{0}", target)), "{0} is missing from {1}", target, lines[0]);
+        }
+        assert!(lines[0].contains("layout.txt:"));
+    }
+}
@@ -0,0 +1,27 @@
+use crate::{compile_error::CompilerError, output::*};
+
+/// Byte order of the target. Native leaves it to be detected by the C compiler
+#[derive(Debug, Clone, PartialEq)]
+pub enum Endianness {
+    Native,
+    Little,
+    Big
+}
+
+impl Endianness {
+    pub fn from_string(string: &str) -> Result<Endianness, CompilerError> {
+        match string {
+            "native" => Ok(Endianness::Native),
+            "little" => Ok(Endianness::Little),
+            "big" => Ok(Endianness::Big),
+            _ => {
+                error!("Invalid endianness passed. Got {0}, and valid values are: {1}", string, Endianness::valid_values());
+                Err(CompilerError::InvalidArgument)
+            }
+        }
+    }
+
+    fn valid_values() -> String {
+        String::from("native, little, big")
+    }
+}
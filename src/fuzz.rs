@@ -0,0 +1,146 @@
+use rune_parser::{RuneFileDescription, types::StructDefinition};
+
+use crate::{
+    c_utilities::{CConfigurations, output_base_name, output_disclaimer, pascal_to_snake_case, sorted_struct_definitions},
+    compile_error::CompilerError,
+    output_file::OutputFile
+};
+
+/// Outputs a libFuzzer target feeding arbitrary bytes to the deserialize function of a struct, selected by its message index
+pub fn output_fuzz_target(
+    file_descriptions: &Vec<RuneFileDescription>,
+    configurations: &CConfigurations,
+    all_file_descriptions: &Vec<RuneFileDescription>,
+    selection: &[String]
+) -> Result<OutputFile, CompilerError> {
+    let mut fuzz_file: OutputFile = OutputFile::new(String::from("rune_fuzz.c"), &configurations.compiler_configurations);
+
+    // Disclaimers
+    // ————————————
+
+    output_disclaimer(&mut fuzz_file, &configurations.compiler_configurations);
+
+    // Description & includes
+    // ———————————————————————
+
+    // Comments are kept C89 compliant, as the target is compiled with the same standard as the generated sources
+    fuzz_file.add_line(String::from(
+        "/* Compile together with the generated sources and a fuzzing engine, e.g. clang -fsanitize=fuzzer,address, to feed arbitrary"
+    ));
+    fuzz_file.add_line(String::from(
+        " * bytes to the generated deserialize functions. The first byte selects the message by its index, and the remaining bytes"
+    ));
+    fuzz_file.add_line(String::from(" * are deserialized as that message */"));
+    fuzz_file.add_newline();
+
+    fuzz_file.add_line(String::from("#include <stddef.h>"));
+    fuzz_file.add_line(String::from("#include <stdint.h>"));
+    fuzz_file.add_newline();
+
+    for file in file_descriptions {
+        if !file.definitions.structs.is_empty() {
+            fuzz_file.add_line(format!(
+                "#include \"{0}.{1}\"",
+                output_base_name(file, &configurations.compiler_configurations),
+                configurations.compiler_configurations.output_extension("h")
+            ));
+        }
+    }
+    fuzz_file.add_newline();
+
+    // Fuzz target
+    // ————————————
+
+    fuzz_file.add_line(String::from("int LLVMFuzzerTestOneInput(const uint8_t* data, size_t size) {"));
+    fuzz_file.add_indented_line(1, String::from("if (size == 0) {"));
+    fuzz_file.add_indented_line(2, String::from("return 0;"));
+    fuzz_file.add_indented_line(1, String::from("}"));
+    fuzz_file.add_newline();
+
+    // Message indexes are taken from all structs, to match the ones of the protocol constants
+    fuzz_file.add_indented_line(1, String::from("switch (data[0]) {"));
+
+    for (message_index, struct_definition) in sorted_struct_definitions(all_file_descriptions).iter().enumerate() {
+        // Indexes beyond the range of the selecting byte cannot be reached
+        if message_index > u8::MAX as usize || !selection.contains(&struct_definition.name) {
+            continue;
+        }
+
+        output_fuzz_case(&mut fuzz_file, message_index, struct_definition);
+    }
+
+    fuzz_file.add_indented_line(2, String::from("default:"));
+    fuzz_file.add_indented_line(3, String::from("break;"));
+    fuzz_file.add_indented_line(1, String::from("}"));
+    fuzz_file.add_newline();
+    fuzz_file.add_indented_line(1, String::from("return 0;"));
+    fuzz_file.add_line(String::from("}"));
+
+    Ok(fuzz_file)
+}
+
+fn output_fuzz_case(fuzz_file: &mut OutputFile, message_index: usize, struct_definition: &StructDefinition) {
+    let struct_name: String = pascal_to_snake_case(&struct_definition.name);
+
+    fuzz_file.add_indented_line(2, format!("case {0}: {{", message_index));
+    fuzz_file.add_indented_line(3, format!("{0}_t message;", struct_name));
+    fuzz_file.add_newline();
+
+    // The result is checked, as the deserialize functions must not be ignored
+    fuzz_file.add_indented_line(3, format!("if (!{0}_deserialize(&message, &data[1], size - 1)) {{", struct_name));
+    fuzz_file.add_indented_line(4, String::from("return 0;"));
+    fuzz_file.add_indented_line(3, String::from("}"));
+    fuzz_file.add_indented_line(3, String::from("break;"));
+    fuzz_file.add_indented_line(2, String::from("}"));
+}
+
+#[cfg(test)]
+mod tests {
+    use rune_parser::RuneFileDescription;
+
+    use crate::{
+        generate_to_memory,
+        test_utilities::{compile_configurations, parse_schema}
+    };
+
+    const SCHEMA: &str = "struct Reading {\n    Value: u16 = 1\n}\n\nstruct Frame {\n    Valid: bool = 1;\n    Last: Reading = 2\n}\n\nstruct Status {\n    Code: u8 = 1\n}\n";
+
+    /// Output the fuzz target generated from the schema with the given command line arguments
+    fn fuzz_target(arguments: &[&str]) -> String {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("frame.rune", SCHEMA)]);
+        let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &compile_configurations(&[&["--emit-codec", "--emit-fuzz"], arguments].concat())).unwrap();
+
+        output_files.into_iter().find(|(name, _)| name == "rune_fuzz.c").expect("Fuzz target should be generated").1
+    }
+
+    #[test]
+    fn messages_are_dispatched_to_their_deserializer_by_message_index() {
+        let fuzz_target: String = fuzz_target(&[]);
+
+        assert!(fuzz_target.contains("#include \"frame.rune.h\""));
+        assert!(fuzz_target.contains("int LLVMFuzzerTestOneInput(const uint8_t* data, size_t size) {\n    if (size == 0) {\n        return 0;\n    }\n\n    switch (data[0]) {"));
+        assert!(fuzz_target.contains(
+            "        case 0: {\n            frame_t message;\n\n            if (!frame_deserialize(&message, &data[1], size - 1)) {\n                return 0;\n            }\n            break;\n        }"
+        ));
+        assert!(fuzz_target.contains("        case 1: {\n            reading_t message;"));
+        assert!(fuzz_target.contains("        case 2: {\n            status_t message;"));
+        assert!(fuzz_target.contains("        default:\n            break;\n    }\n\n    return 0;\n}"));
+    }
+
+    #[test]
+    fn unselected_messages_keep_the_indexes_of_the_others() {
+        let fuzz_target: String = fuzz_target(&["--only", "Status"]);
+
+        assert!(fuzz_target.contains("        case 2: {\n            status_t message;"));
+        assert!(!fuzz_target.contains("case 0:"));
+        assert!(!fuzz_target.contains("case 1:"));
+    }
+
+    #[test]
+    fn no_fuzz_target_is_generated_by_default() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("frame.rune", SCHEMA)]);
+        let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &compile_configurations(&["--emit-codec"])).unwrap();
+
+        assert!(output_files.iter().all(|(name, _)| name != "rune_fuzz.c"));
+    }
+}
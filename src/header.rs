@@ -1,26 +1,88 @@
-use std::path::Path;
-
 use rune_parser::{
     scanner::NumericLiteral,
-    types::{BitSize, BitfieldDefinition, BitfieldMember, DefineDefinition, DefineValue, EnumDefinition, Primitive, StructDefinition, StructMember}
+    types::{
+        ArraySize, ArrayType, BitSize, BitfieldDefinition, BitfieldMember, DefineDefinition, DefineValue, EnumDefinition, FieldType, IncludeDefinition, Primitive, StructDefinition, StructMember,
+        UserDefinitionLink
+    }
 };
 
 use crate::{
     RuneFileDescription,
     c_standard::CStandard,
-    c_utilities::{CConfigurations, CFieldType, CNumericValue, CPrimitive, CStructDefinition, pascal_to_snake_case, pascal_to_uppercase, spaces},
+    c_utilities::{
+        CConfigurations, CFieldType, CNumericValue, CPrimitive, CStructDefinition, CStructMember, collect_dependencies, declares_type, include_base_name, inlined_definition, inlined_members,
+        is_included_file, output_base_name, output_debug_checks, output_disclaimer, output_pack_pop, output_pack_push, pascal_to_snake_case, pascal_to_uppercase, path_namespace,
+        prefix_inlined_padding, spaces, struct_members
+    },
+    codec::output_codec_declarations,
     compile_error::CompilerError,
+    endianness::Endianness,
+    include_guard_style::IncludeGuardStyle,
     output::*,
-    output_file::OutputFile
+    output_file::OutputFile,
+    verifier::output_verifier_declarations
 };
 
+/// Output the struct of a bitfield, with its members in the given order
+fn output_bitfield_struct(
+    header_file: &mut OutputFile,
+    c_standard: &CStandard,
+    bitfield_definition: &BitfieldDefinition,
+    backing_type: &(Primitive, Primitive),
+    order: &[BitfieldMember],
+    longest_name: usize
+) -> Result<(), CompilerError> {
+    let bitfield_name: String = pascal_to_snake_case(&bitfield_definition.name);
+
+    header_file.add_line(format!("typedef struct RUNIC_BITFIELD {0} {{", bitfield_name));
+
+    // Comment
+    if let Some(comment) = &bitfield_definition.comment {
+        header_file.add_doc_comment(0, comment);
+    }
+
+    // Print bits
+    for member in order.iter().enumerate() {
+        // Member comment
+        if let Some(comment) = &member.1.comment {
+            if member.0 != 0 {
+                header_file.add_newline();
+            }
+            header_file.add_doc_comment(1, comment);
+        }
+
+        let member_name: String = pascal_to_snake_case(&member.1.identifier);
+
+        // Get bit size
+        let bit_size: u64;
+        let backing_string: String;
+
+        match member.1.size {
+            BitSize::Signed(size) => {
+                backing_string = format!("{0} ", backing_type.1.to_c_type(c_standard)?);
+                bit_size = size;
+            },
+            BitSize::Unsigned(size) => {
+                backing_string = backing_type.0.to_c_type(c_standard)?;
+                bit_size = size;
+            }
+        };
+
+        header_file.add_indented_line(1, format!("{0} {1}{2} : {3};", backing_string, member_name, spaces(longest_name - member_name.len()), bit_size));
+    }
+
+    header_file.add_line(format!("}} {0}_t;", bitfield_name));
+
+    Ok(())
+}
+
 /// Outputs a bitfield definition into the header file
 fn output_bitfield(header_file: &mut OutputFile, configurations: &CConfigurations, bitfield_definition: &BitfieldDefinition) -> Result<(), CompilerError> {
     let c_standard = &configurations.compiler_configurations.c_standard;
 
     // Print comment if present
     if let Some(comment) = &bitfield_definition.comment {
-        header_file.add_line(format!("/**{0}*/", comment))
+        header_file.add_doc_comment(0, comment);
     }
 
     let bitfield_name: String = pascal_to_snake_case(&bitfield_definition.name);
@@ -50,22 +112,36 @@ fn output_bitfield(header_file: &mut OutputFile, configurations: &CConfiguration
         };
     }
 
+    let backing_size: u64 = bitfield_definition.backing_type.c_size() * 8;
+
+    // The parser rejects these, but check anyway, as the padding calculation would otherwise underflow
+    if total_size > backing_size {
+        error!(
+            "Members of bitfield {0} take up {1} bits, which does not fit within its {2} bit backing type",
+            bitfield_definition.name, total_size, backing_size
+        );
+        return Err(CompilerError::MalformedSource);
+    }
+
+    let padding_size: u64 = backing_size - total_size;
+
+    // Anonymous padding has no name, so it cannot be accessed by accident
+    let padding_name: String = match configurations.compiler_configurations.anonymous_bitfield_padding {
+        true => String::new(),
+        false => String::from("padding")
+    };
+
     let padding: BitfieldMember = BitfieldMember {
-        identifier: String::from("padding"),
-        size:       BitSize::Unsigned((bitfield_definition.backing_type.c_size() * 8) - total_size),
+        identifier: padding_name.clone(),
+        size:       BitSize::Unsigned(padding_size),
         index:      0, // Does not matter
         comment:    Some(String::from(" Padding to ensure proper alignment "))
     };
 
-    let padding_name_size: u64 = match padding.size {
-        BitSize::Signed(size) => size,
-        BitSize::Unsigned(size) => size
-    };
-
     // Calculate longest member name for spacing
-    let mut longest_name: usize = match padding_name_size {
+    let mut longest_name: usize = match padding_size {
         0 => 0,
-        _ => String::from("padding").len()
+        _ => padding_name.len()
     };
 
     for member in &bitfield_definition.members {
@@ -80,18 +156,10 @@ fn output_bitfield(header_file: &mut OutputFile, configurations: &CConfiguration
 
     header_file.add_line(String::from("// Disclaimer ! Run rune_bitfield_tester() function to check whether bitfields are behaving as intended"));
 
-    // Little endian order
-    // ————————————————————
-
-    header_file.add_line(String::from("#if defined __LITTLE_ENDIAN__"));
-    header_file.add_line(format!("typedef struct RUNIC_BITFIELD {0} {{", bitfield_name));
-
-    // Comment
-    if bitfield_definition.comment.is_some() {
-        header_file.add_line(format!("/**{0}*/", bitfield_definition.comment.as_ref().unwrap()));
-    }
+    // Member orders
+    // ——————————————
 
-    // Get little endian order
+    // Little endian compilers allocate bits from the least significant end, so members are listed in index order, with padding last
     for i in 0..bitfield_definition.members.len() as u64 {
         for member in &bitfield_definition.members {
             if member.index == i {
@@ -100,56 +168,15 @@ fn output_bitfield(header_file: &mut OutputFile, configurations: &CConfiguration
         }
     }
 
-    // Add padding - In the end for little endian
-    little_endian_order.push(padding.clone());
-
-    // Print bits
-    for member in little_endian_order.iter().enumerate() {
-        // Member comment
-        if member.1.comment.is_some() {
-            if member.0 != 0 {
-                header_file.add_newline();
-            }
-            header_file.add_line(format!("    /**{0}*/", member.1.comment.as_ref().unwrap()));
-        }
-
-        let member_name = pascal_to_snake_case(&member.1.identifier);
-
-        // Get bit size
-        let bit_size: u64;
-        let backing_string: String;
-
-        match member.1.size {
-            BitSize::Signed(size) => {
-                backing_string = format!("{0} ", backing_type.1.to_c_type(c_standard)?);
-                bit_size = size;
-            },
-            BitSize::Unsigned(size) => {
-                backing_string = backing_type.0.to_c_type(c_standard)?;
-                bit_size = size;
-            }
-        };
-
-        header_file.add_line(format!("    {0} {1}{2} : {3};", backing_string, member_name, spaces(longest_name - member_name.len()), bit_size));
+    if padding_size != 0 {
+        little_endian_order.push(padding.clone());
     }
 
-    header_file.add_line(format!("}} {0}_t;", bitfield_name));
-
-    // Big endian order
-    // —————————————————
-
-    header_file.add_line(String::from("#elif defined __BIG_ENDIAN__"));
-    header_file.add_line(format!("typedef struct RUNIC_BITFIELD {0} {{", bitfield_name));
-
-    // Comment
-    if bitfield_definition.comment.is_some() {
-        header_file.add_line(format!("/**{0}*/", bitfield_definition.comment.as_ref().unwrap()));
+    // Big endian compilers allocate bits from the most significant end, so the order is reversed, with padding first
+    if padding_size != 0 {
+        big_endian_order.push(padding.clone());
     }
 
-    // Add padding - In the beginning for little endian
-    big_endian_order.push(padding.clone());
-
-    // Get big endian order
     for z in 0..bitfield_definition.members.len() as u64 {
         let i = bitfield_definition.members.len() as u64 - 1 - z;
         for member in &bitfield_definition.members {
@@ -159,46 +186,52 @@ fn output_bitfield(header_file: &mut OutputFile, configurations: &CConfiguration
         }
     }
 
-    // Print bits
-    for member in big_endian_order.iter().enumerate() {
-        // Member comment
-        if member.1.comment.is_some() {
-            if member.0 != 0 {
-                header_file.add_newline();
-            }
-            header_file.add_line(format!("    /**{0}*/", member.1.comment.as_ref().unwrap()));
-        }
+    // Bitfields are always packed
+    output_pack_push(header_file, &configurations.compiler_configurations, true);
+
+    // Only the order of a byte order fixed at generation time is output, otherwise the C compiler picks the matching one
+    match configurations.compiler_configurations.endianness {
+        Endianness::Native => {
+            header_file.add_line(String::from("#if defined __LITTLE_ENDIAN__"));
+            output_bitfield_struct(header_file, c_standard, bitfield_definition, &backing_type, &little_endian_order, longest_name)?;
+            header_file.add_line(String::from("#elif defined __BIG_ENDIAN__"));
+            output_bitfield_struct(header_file, c_standard, bitfield_definition, &backing_type, &big_endian_order, longest_name)?;
+            header_file.add_line(String::from("#else"));
+            header_file.add_line(String::from("#error \"Only little and big endianness is supported by this Rune C implementation\""));
+            header_file.add_line(String::from("#endif // __BYTE_ORDER__"));
+        },
+        Endianness::Little => output_bitfield_struct(header_file, c_standard, bitfield_definition, &backing_type, &little_endian_order, longest_name)?,
+        Endianness::Big => output_bitfield_struct(header_file, c_standard, bitfield_definition, &backing_type, &big_endian_order, longest_name)?
+    }
 
-        let member_name: String = pascal_to_snake_case(&member.1.identifier);
+    output_pack_pop(header_file, &configurations.compiler_configurations, true);
+    header_file.add_newline();
 
-        // Get bit size
-        let bit_size: u64;
-        let backing_string: String;
+    // Size check
+    // ———————————
 
-        match member.1.size {
-            BitSize::Signed(size) => {
-                backing_string = format!("{0} ", backing_type.1.to_c_type(c_standard)?);
-                bit_size = size;
-            },
-            BitSize::Unsigned(size) => {
-                backing_string = backing_type.0.to_c_type(c_standard)?;
-                bit_size = size;
+    // Catch compilers laying out the bitfield larger than its backing type, as that breaks wire assumptions
+    if c_standard.allows_static_assert() {
+        let assertion: String = format!(
+            "(sizeof({0}_t) == sizeof({1}), \"{0}_t must have the same size as its backing type {1}\");",
+            bitfield_name,
+            bitfield_definition.backing_type.to_c_type(c_standard)?
+        );
+
+        // Before C23 the keyword is underscored, which C++ does not have, so headers included from C++ use static_assert instead
+        match c_standard.allows_static_assert_keyword() {
+            true => header_file.add_line(format!("static_assert{0}", assertion)),
+            false => {
+                header_file.add_line(String::from("#ifdef __cplusplus"));
+                header_file.add_line(format!("static_assert{0}", assertion));
+                header_file.add_line(String::from("#else"));
+                header_file.add_line(format!("_Static_assert{0}", assertion));
+                header_file.add_line(String::from("#endif"));
             }
-        };
-
-        header_file.add_line(format!("    {0} {1}{2} : {3};", backing_string, member_name, spaces(longest_name - member_name.len()), bit_size));
+        }
+        header_file.add_newline();
     }
 
-    header_file.add_line(format!("}} {0}_t;", bitfield_name));
-
-    // Error
-    // ——————
-
-    header_file.add_line(String::from("#else"));
-    header_file.add_line(String::from("#error \"Only little and big endianness is supported by this Rune C implementation\""));
-    header_file.add_line(String::from("#endif // __BYTE_ORDER__"));
-    header_file.add_newline();
-
     // Initializer
     // ————————————
 
@@ -209,10 +242,10 @@ fn output_bitfield(header_file: &mut OutputFile, configurations: &CConfiguration
 }
 
 /// Outputs a define statement into the header file
-fn output_define(header_file: &mut OutputFile, define: &DefineDefinition) {
+fn output_define(header_file: &mut OutputFile, configurations: &CConfigurations, define: &DefineDefinition) {
     // Print comment if present
     if let Some(comment) = &define.comment {
-        header_file.add_line(format!("/**{0}*/", comment))
+        header_file.add_doc_comment(0, comment);
     }
 
     let define_name: String = define.name.clone();
@@ -226,7 +259,7 @@ fn output_define(header_file: &mut OutputFile, define: &DefineDefinition) {
 
         match value {
             DefineValue::NoValue => String::from(""),
-            DefineValue::NumericLiteral(value) => value.to_string()
+            DefineValue::NumericLiteral(value) => value.to_c_literal(true, &configurations.compiler_configurations)
         }
     };
 
@@ -239,7 +272,7 @@ fn output_enum(header_file: &mut OutputFile, configurations: &CConfigurations, e
 
     // Print comment if present
     if let Some(comment) = &enum_definition.comment {
-        header_file.add_line(format!("/**{0}*/", comment))
+        header_file.add_doc_comment(0, comment);
     }
 
     let enum_name: String = pascal_to_snake_case(&enum_definition.name);
@@ -267,16 +300,18 @@ fn output_enum(header_file: &mut OutputFile, configurations: &CConfigurations, e
 
     let mut initializer_value: String = String::from("0");
 
+    let is_unsigned: bool = !enum_definition.backing_type.is_signed();
+
     // Print all enum members
     for i in 0..enum_definition.members.len() {
         let enum_member = &enum_definition.members[i];
 
         // Member comment
-        if enum_member.comment.is_some() {
+        if let Some(comment) = &enum_member.comment {
             if i != 0 {
                 header_file.add_newline();
             }
-            header_file.add_line(format!("    /**{0}*/", enum_member.comment.as_ref().unwrap()));
+            header_file.add_doc_comment(1, comment);
         }
 
         let member_name: String = pascal_to_uppercase(&enum_member.identifier);
@@ -302,27 +337,43 @@ fn output_enum(header_file: &mut OutputFile, configurations: &CConfigurations, e
             true => String::from("")
         };
 
-        header_file.add_line(format!("    {0}{1} = {2}{3}", member_name, spaces(longest_member_name - member_name.len()), enum_member.value, ending));
+        header_file.add_indented_line(
+            1,
+            format!(
+                "{0}{1} = {2}{3}",
+                member_name,
+                spaces(longest_member_name - member_name.len()),
+                enum_member.value.to_c_literal(is_unsigned, &configurations.compiler_configurations),
+                ending
+            )
+        );
     }
 
     if needs_backing_value {
         header_file.add_newline();
-        header_file.add_line(format!(
-            "    /** Value to coerce enum to minimum size of declared backing type {0} */",
-            enum_definition.backing_type.to_c_type(c_standard)?
-        ));
-        header_file.add_line(format!(
-            "    {0}_SIZE_RESERVE_VALUE = {1}",
-            pascal_to_uppercase(&enum_definition.name),
-            match enum_definition.backing_type.c_size() {
-                0 => "0",
-                1 => "0xFF",
-                2 => "0xFFFF",
-                4 => "0xFFFFFFFF",
-                8 => "0xFFFFFFFFFFFFFFFF",
-                _ => unreachable!("Invalid value returned from primitive_c_size()!")
-            }
-        ));
+        header_file.add_indented_line(
+            1,
+            format!(
+                "/** Value to coerce enum to minimum size of declared backing type {0} */",
+                enum_definition.backing_type.to_c_type(c_standard)?
+            )
+        );
+        header_file.add_indented_line(
+            1,
+            format!(
+                "{0}_SIZE_RESERVE_VALUE = {1}{2}",
+                pascal_to_uppercase(&enum_definition.name),
+                match enum_definition.backing_type.c_size() {
+                    0 => "0",
+                    1 => "0xFF",
+                    2 => "0xFFFF",
+                    4 => "0xFFFFFFFF",
+                    8 => "0xFFFFFFFFFFFFFFFF",
+                    _ => unreachable!("Invalid value returned from primitive_c_size()!")
+                },
+                configurations.compiler_configurations.unsigned_suffix()
+            )
+        );
     }
 
     // Output enum definitions
@@ -333,24 +384,164 @@ fn output_enum(header_file: &mut OutputFile, configurations: &CConfigurations, e
     header_file.add_line(format!("#define {0}_INIT {1}", pascal_to_uppercase(&enum_name), initializer_value));
     header_file.add_newline();
 
+    if configurations.compiler_configurations.enum_names {
+        header_file.add_line(format!("const char* {0}_to_string({0}_t value);", enum_name));
+        header_file.add_newline();
+    }
+
+    Ok(())
+}
+
+/// Check whether any struct member of the file, as stored in memory, is of a primitive type matching the predicate
+fn uses_primitive(file: &RuneFileDescription, configurations: &CConfigurations, predicate: fn(&Primitive) -> bool) -> Result<bool, CompilerError> {
+    for struct_definition in &file.definitions.structs {
+        for member in struct_members(configurations, struct_definition)? {
+            match &member.data_type {
+                FieldType::Primitive(primitive) | FieldType::Array(ArrayType::Primitive(primitive), _) if predicate(primitive) => return Ok(true),
+                _ => ()
+            }
+        }
+    }
+
+    Ok(false)
+}
+
+/// Whether the file uses bool, and thus needs <stdbool.h>. Before C99 booleans are a rune_bool_t instead
+fn needs_stdbool(file: &RuneFileDescription, configurations: &CConfigurations) -> Result<bool, CompilerError> {
+    match configurations.compiler_configurations.c_standard.allows_boolean() {
+        // Deserialize functions return a bool
+        true if configurations.compiler_configurations.codec && !file.definitions.structs.is_empty() => Ok(true),
+        // Verify functions return a bool
+        true if configurations.compiler_configurations.verifiers && file.definitions.structs.iter().any(|definition| definition.members.iter().any(|member| member.index.is_verifier())) => Ok(true),
+        true => uses_primitive(file, configurations, |primitive| *primitive == Primitive::Bool),
+        false => Ok(false)
+    }
+}
+
+/// Whether the file uses exact-width integer types, and thus needs <stdint.h>. Before C99 native integer types are used instead
+fn needs_stdint(file: &RuneFileDescription, configurations: &CConfigurations) -> Result<bool, CompilerError> {
+    let c_standard: &CStandard = &configurations.compiler_configurations.c_standard;
+
+    if !c_standard.allows_integer_types() {
+        return Ok(false);
+    }
+
+    // Bitfields are made of their backing type, as are enums declaring it from C23
+    if !file.definitions.bitfields.is_empty() || (c_standard.allows_enum_backing_type() && !file.definitions.enums.is_empty()) {
+        return Ok(true);
+    }
+
+    uses_primitive(file, configurations, |primitive| {
+        !matches!(primitive, Primitive::Bool | Primitive::Char | Primitive::F32 | Primitive::F64)
+    })
+}
+
+/// Whether any struct member of the file uses a type or define declared by the other file
+fn uses_declarations_of(file: &RuneFileDescription, other_file: &RuneFileDescription) -> bool {
+    file.definitions.structs.iter().flat_map(|definition| definition.members.iter()).any(|member| match &member.data_type {
+        FieldType::UserDefined(name) => declares_type(other_file, name),
+        FieldType::Array(array_type, array_size) => {
+            let uses_type: bool = match array_type {
+                ArrayType::UserDefined(name) => declares_type(other_file, name),
+                _ => false
+            };
+            let uses_define: bool = match array_size {
+                ArraySize::UserDefinition(define) => other_file.definitions.defines.iter().any(|definition| definition.name == define.name),
+                _ => false
+            };
+
+            uses_type || uses_define
+        },
+        _ => false
+    })
+}
+
+/// Whether the include is output after the definitions of the file. Files including each other, directly or through other files,
+/// are only kept from being included twice by their guards, so the header included last is output in the middle of the first one,
+/// before its definitions. When the first file needs nothing from the other files of the cycle, including them after its own
+/// definitions lets the other headers find everything they need
+pub fn is_deferred_include(file: &RuneFileDescription, include_definition: &IncludeDefinition, file_descriptions: &Vec<RuneFileDescription>) -> bool {
+    let mut dependencies: Vec<&RuneFileDescription> = Vec::with_capacity(0x10);
+
+    for included_file in file_descriptions.iter().filter(|included_file| is_included_file(included_file, include_definition)) {
+        collect_dependencies(included_file, file_descriptions, &mut dependencies);
+    }
+
+    let is_cyclic: bool = dependencies.iter().any(|dependency| std::ptr::eq(*dependency, file));
+
+    is_cyclic && !dependencies.iter().any(|dependency| !std::ptr::eq(*dependency, file) && uses_declarations_of(file, dependency))
+}
+
+/// Output the members of a struct at the given depth. Inlined members are output as an anonymous struct holding the members of
+/// their struct, which keeps its layout, so the descriptor of the struct still describes the inlined member
+fn output_struct_members(header_file: &mut OutputFile, configurations: &CConfigurations, struct_definition: &StructDefinition, members: &[StructMember], depth: usize) -> Result<(), CompilerError> {
+    let mut is_first: bool = true;
+
+    // Print all struct members
+    for member in members {
+        // Member comment
+        if let Some(comment) = &member.comment {
+            if !is_first {
+                header_file.add_newline();
+            }
+            header_file.add_doc_comment(depth, comment);
+        }
+
+        let member_name: String = pascal_to_snake_case(&member.identifier);
+        let spacing: usize = 0; // longest_type - sorted_member_list[i].field_type.to_c_type().len();
+
+        match configurations.compiler_configurations.is_inlined(&struct_definition.name, member) {
+            true => {
+                let inlined_definition: &StructDefinition = inlined_definition(member)?;
+
+                let mut inlined_members: Vec<StructMember> = struct_members(configurations, inlined_definition)?;
+                for inlined_member in &mut inlined_members {
+                    prefix_inlined_padding(member, inlined_member);
+                }
+
+                header_file.add_indented_line(depth, format!("struct RUNIC_STRUCT {{ /* {0} */", member_name));
+                output_struct_members(header_file, configurations, inlined_definition, &inlined_members, depth + 1)?;
+                header_file.add_indented_line(depth, String::from("};"));
+            },
+            false => {
+                // Alignment specifiers are keywords from C23, and underscored keywords before, which runic definitions spell for C and C++
+                let alignment_specifier: String = match configurations.compiler_configurations.forced_alignment(&struct_definition.name, member) {
+                    Some(alignment) if configurations.compiler_configurations.c_standard.allows_alignas_keyword() => format!("alignas({0}) ", alignment),
+                    Some(alignment) => format!("RUNE_ALIGNAS({0}) ", alignment),
+                    None => String::new()
+                };
+
+                header_file.add_indented_line(
+                    depth,
+                    format!(
+                        "{0}{1};",
+                        alignment_specifier,
+                        member.data_type.create_c_variable(&member_name, spacing, &configurations.compiler_configurations)?
+                    )
+                )
+            }
+        }
+
+        is_first = false;
+    }
+
     Ok(())
 }
 
 /// Output a struct into the header file
 fn output_struct(header_file: &mut OutputFile, configurations: &CConfigurations, struct_definition: &StructDefinition) -> Result<Vec<StructMember>, CompilerError> {
-    let c_standard = &configurations.compiler_configurations.c_standard;
-
     // Print comment if present
     if let Some(comment) = &struct_definition.comment {
-        header_file.add_line(format!("/**{0}*/", comment))
+        header_file.add_doc_comment(0, comment);
     }
 
     let struct_name: String = pascal_to_snake_case(&struct_definition.name);
 
+    output_pack_push(header_file, &configurations.compiler_configurations, configurations.compiler_configurations.pack_data);
     header_file.add_line(format!("typedef struct RUNIC_STRUCT {0} {{", struct_name));
 
     // Sorted list --> Then use sorted list instead of other one
-    let sorted_member_list: Vec<StructMember> = struct_definition.sort_members(&configurations.compiler_configurations)?;
+    let sorted_member_list: Vec<StructMember> = struct_members(configurations, struct_definition)?;
 
     // >>> Spacing of struct members does not look good, and will thus be dropped <<<
 
@@ -365,157 +556,346 @@ fn output_struct(header_file: &mut OutputFile, configurations: &CConfigurations,
 
     // >>> end <<<
 
-    let mut is_first: bool = true;
+    output_struct_members(header_file, configurations, struct_definition, &sorted_member_list, 1)?;
 
-    // Print all struct members
-    for member in &sorted_member_list {
-        // Member comment
-        if member.comment.is_some() {
-            if !is_first {
-                header_file.add_newline();
-            }
-            header_file.add_line(format!("    /**{0}*/", member.comment.as_ref().unwrap()));
-        }
+    header_file.add_line(format!("}} {0}_t;", struct_name));
+    output_pack_pop(header_file, &configurations.compiler_configurations, configurations.compiler_configurations.pack_data);
+    header_file.add_newline();
 
-        let member_name: String = pascal_to_snake_case(&member.identifier);
-        let spacing: usize = 0; // longest_type - sorted_member_list[i].field_type.to_c_type().len();
+    if configurations.compiler_configurations.descriptors {
+        header_file.add_line(format!("extern const rune_descriptor_t {0}_descriptor;", struct_name));
+    }
 
-        header_file.add_line(format!("    {0};", member.data_type.create_c_variable(&member_name, spacing, c_standard)?));
+    if configurations.compiler_configurations.tlv {
+        header_file.add_line(format!("extern const rune_tlv_descriptor_t {0}_tlv_descriptor;", struct_name));
+    }
 
-        is_first = false;
+    if configurations.compiler_configurations.named_fields {
+        header_file.add_line(format!("extern const rune_named_fields_t {0}_named_fields;", struct_name));
     }
 
-    header_file.add_line(format!("}} {0}_t;", struct_name));
-    header_file.add_newline();
+    if configurations.compiler_configurations.codec {
+        output_codec_declarations(header_file, configurations, struct_definition)?;
+    }
+
+    if configurations.compiler_configurations.verifiers {
+        output_verifier_declarations(header_file, configurations, struct_definition)?;
+    }
 
-    header_file.add_line(format!("extern const rune_descriptor_t {0}_descriptor;", struct_name));
     header_file.add_newline();
 
     Ok(sorted_member_list)
 }
 
+/// Get the struct members to initialize, in the order they are stored. The members of anonymous structs are initialized as members
+/// of the parent
+fn initializer_members(configurations: &CConfigurations, struct_definition: &StructDefinition) -> Result<Vec<StructMember>, CompilerError> {
+    let mut members: Vec<StructMember> = Vec::with_capacity(struct_definition.members.len());
+
+    for member in struct_members(configurations, struct_definition)? {
+        match configurations.compiler_configurations.is_inlined(&struct_definition.name, &member) {
+            true => members.extend(inlined_members(configurations, &member)?),
+            false => members.push(member)
+        }
+    }
+
+    Ok(members)
+}
+
+/// Get the initializer of a struct member. Nested structs are initialized with their own members in place, rather than with their
+/// _INIT macro, which is a compound literal from C99, and thus neither a constant expression nor addressable by nested designators
+fn member_initializer(configurations: &CConfigurations, member: &StructMember) -> Result<String, CompilerError> {
+    match (&member.data_type, &member.user_definition_link) {
+        (FieldType::UserDefined(_), UserDefinitionLink::StructLink(struct_definition)) => nested_struct_initializer(configurations, struct_definition),
+        (FieldType::Array(ArrayType::UserDefined(_), _), UserDefinitionLink::StructLink(struct_definition)) => Ok(format!("{{ {0} }}", nested_struct_initializer(configurations, struct_definition)?)),
+        _ => member.data_type.c_initializer(&configurations.compiler_configurations)
+    }
+}
+
+/// Get the brace enclosed initializer of a nested struct, on a single line
+fn nested_struct_initializer(configurations: &CConfigurations, struct_definition: &StructDefinition) -> Result<String, CompilerError> {
+    let mut initializers: Vec<String> = Vec::with_capacity(struct_definition.members.len());
+
+    for member in initializer_members(configurations, struct_definition)? {
+        let initializer: String = member_initializer(configurations, &member)?;
+
+        initializers.push(match configurations.compiler_configurations.c_standard.allows_designated_initializers() {
+            true => format!(".{0} = {1}", pascal_to_snake_case(&member.identifier), initializer),
+            false => initializer
+        });
+    }
+
+    match initializers.is_empty() {
+        true => Ok(String::from("{ 0 }")),
+        false => Ok(format!("{{ {0} }}", initializers.join(", ")))
+    }
+}
+
 fn output_struct_initializer(output_file: &mut OutputFile, configurations: &CConfigurations, struct_definition: &StructDefinition) -> Result<(), CompilerError> {
     let c_standard: &CStandard = &configurations.compiler_configurations.c_standard;
 
-    let mut pre_equal_length: usize = 0;
+    let sorted_member_list: Vec<StructMember> = initializer_members(configurations, struct_definition)?;
 
-    let sorted_member_list: Vec<StructMember> = struct_definition.sort_members(&configurations.compiler_configurations)?;
+    // Designators name the members as declared in C
+    let member_names: Vec<String> = sorted_member_list.iter().map(|member| pascal_to_snake_case(&member.identifier)).collect();
 
-    // Calculate spacing for aligning the '=' sign
-    // ————————————————————————————————————————————
+    // Align the '=' signs after the longest member name
+    let name_width: usize = member_names.iter().map(|member_name| member_name.len()).max().unwrap_or(0);
 
-    for member in &sorted_member_list {
-        if member.identifier.len() > pre_equal_length {
-            pre_equal_length = member.identifier.len();
-        }
+    // Build lines
+    // ————————————
+
+    // Compound literals are only available from C99, before which the initializer can only be used in declarations
+    let mut lines: Vec<String> = vec![match c_standard.allows_designated_initializers() {
+        true => format!(
+            "#define {0}_INIT ({1}_t) {{",
+            pascal_to_uppercase(&struct_definition.name),
+            pascal_to_snake_case(&struct_definition.name)
+        ),
+        false => format!("#define {0}_INIT {{", pascal_to_uppercase(&struct_definition.name))
+    }];
+
+    for (i, member) in sorted_member_list.iter().enumerate() {
+        let comma: &'static str = match i == sorted_member_list.len() - 1 {
+            true => "",
+            false => ","
+        };
+
+        let initializer: String = member_initializer(configurations, member)?;
+
+        lines.push(match c_standard.allows_designated_initializers() {
+            true => format!("{0}.{1:2$} = {3}{4}", output_file.indent(1), member_names[i], name_width, initializer, comma),
+            false => format!("{0}{1}{2}", output_file.indent(1), initializer, comma)
+        });
     }
 
-    // Calculate the space for aligning the '\' at the end
-    // ————————————————————————————————————————————————————
+    // Align the '\' at the end after the longest line
+    // ———————————————————————————————————————————————
+
+    let line_width: usize = lines.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+
+    for line in lines {
+        let padding: usize = line_width - line.chars().count();
+        output_file.add_line(format!("{0}{1} \\", line, spaces(padding)));
+    }
+    output_file.add_line("}".to_string());
+    output_file.add_newline();
 
-    let initializer_string: String = format!(
-        "#define {0}_INIT ({1}_t) {{{2}",
-        pascal_to_uppercase(&struct_definition.name),
-        pascal_to_snake_case(&struct_definition.name),
-        spaces(0)
-    );
-    let mut pre_newline_length: usize = initializer_string.len();
+    if configurations.compiler_configurations.descriptors {
+        output_file.add_line(format!(
+            "#define {0}_DESCRIPTOR &{1}_descriptor",
+            pascal_to_uppercase(&struct_definition.name),
+            pascal_to_snake_case(&struct_definition.name)
+        ));
+        output_file.add_newline();
+    }
 
-    // Calculate spacing for after the newline
-    for i in 0..sorted_member_list.len() {
-        let member: &StructMember = &sorted_member_list[i];
+    Ok(())
+}
 
-        let is_last: bool = i != sorted_member_list.len() - 1;
+/// Outputs the macros converting the fixed-point members of a struct between their stored integers and floating point values
+fn output_fixed_point_macros(output_file: &mut OutputFile, configurations: &CConfigurations, struct_definition: &StructDefinition) -> Result<(), CompilerError> {
+    let c_standard: &CStandard = &configurations.compiler_configurations.c_standard;
 
-        let pre_equal: usize = pre_equal_length - member.identifier.len();
+    let mut has_fixed_point_members: bool = false;
 
-        let comma = match is_last {
-            true => ",",
-            false => ""
+    for member in &struct_definition.members {
+        let Some(format) = configurations.compiler_configurations.fixed_point_format(&struct_definition.name, member) else {
+            continue;
         };
 
-        let string: String = match c_standard.allows_designated_initializers() {
-            true => format!(
-                "    .{0}{1} = {2}{3} {4}\\",
-                member.identifier,
-                spaces(pre_equal),
-                member.data_type.c_initializer(c_standard)?,
-                comma,
-                ""
-            ),
-            false => format!("    {0}{1} {2}\\", member.data_type.c_initializer(c_standard)?, comma, "")
+        // Checked by validate_fixed_point_members
+        let storage_type: String = match &member.data_type {
+            FieldType::Primitive(primitive) | FieldType::Array(ArrayType::Primitive(primitive), _) => primitive.to_c_type(c_standard)?,
+            _ => {
+                error!("Fixed-point member {0} of struct {1} must be an integer!", member.identifier, struct_definition.name);
+                return Err(CompilerError::MalformedSource);
+            }
         };
 
-        // I don't know why the -2 is needed, but it does not work without it
-        if string.len() - 2 > pre_newline_length {
-            pre_newline_length = string.len() - 2;
-        }
+        let macro_name: String = format!("{0}_{1}", pascal_to_uppercase(&struct_definition.name), pascal_to_uppercase(&member.identifier));
+        let scale: String = format!("{0}.0", 1u128 << format.fractional_bits);
+
+        output_file.add_line(format!(
+            "/** Member {0} holds {1} fixed-point values, scaled by {2} */",
+            pascal_to_snake_case(&member.identifier),
+            format,
+            scale
+        ));
+        output_file.add_line(format!(
+            "#define {0}_FRACTIONAL_BITS {1}{2}",
+            macro_name,
+            format.fractional_bits,
+            configurations.compiler_configurations.unsigned_suffix()
+        ));
+        output_file.add_line(format!("#define {0}_TO_FLOAT(value) ((double)(value) / {1})", macro_name, scale));
+        output_file.add_line(format!("#define FLOAT_TO_{0}(value) (({1})((value) * {2}))", macro_name, storage_type, scale));
+
+        has_fixed_point_members = true;
     }
 
-    // 20 seems to be the number of fixed characters on the define string
-    let define_size: usize = 20 + pascal_to_uppercase(&struct_definition.name).len() + pascal_to_snake_case(&struct_definition.name).len();
+    if has_fixed_point_members {
+        output_file.add_newline();
+    }
 
-    output_file.add_line(format!(
-        "#define {0}_INIT ({1}_t) {{ {2}\\",
-        pascal_to_uppercase(&struct_definition.name),
-        pascal_to_snake_case(&struct_definition.name),
-        spaces(pre_newline_length - define_size)
-    ));
+    Ok(())
+}
 
-    for i in 0..sorted_member_list.len() {
-        let member: &StructMember = &sorted_member_list[i];
+/// Outputs a helper returning the size of the struct. Inline functions are type safe and debuggable, but only available from C99,
+/// before which a function-like macro with the same name is output instead
+fn output_struct_size_helper(output_file: &mut OutputFile, configurations: &CConfigurations, struct_definition: &StructDefinition) {
+    let struct_name: String = pascal_to_snake_case(&struct_definition.name);
 
-        let is_last: bool = i != sorted_member_list.len() - 1;
-        let static_length: usize;
-        let pre_equal: usize;
-        let pre_newline;
+    match configurations.compiler_configurations.c_standard.allows_inline() {
+        true => {
+            output_file.add_line(format!("static inline size_t {0}_size(void) {{", struct_name));
+            output_file.add_indented_line(1, format!("return sizeof({0}_t);", struct_name));
+            output_file.add_line(String::from("}"));
+        },
+        false => output_file.add_line(format!("#define {0}_size() (sizeof({0}_t))", struct_name))
+    }
 
-        match c_standard.allows_designated_initializers() {
-            true => {
-                pre_equal = pre_equal_length - member.identifier.len();
-                static_length = 9;
-                pre_newline = pre_newline_length - pre_equal_length - member.data_type.c_initializer(c_standard)?.len() - static_length + (!is_last as usize);
-            },
-            false => {
-                pre_equal = 0;
-                static_length = 5;
-                pre_newline = pre_newline_length - member.data_type.c_initializer(c_standard)?.len() - static_length + (!is_last as usize)
-            }
+    output_file.add_newline();
+}
+
+/// Outputs memcpy based accessors for the struct members, avoiding unaligned access faults on packed structs
+fn output_struct_accessors(output_file: &mut OutputFile, configurations: &CConfigurations, struct_definition: &StructDefinition) -> Result<(), CompilerError> {
+    let c_standard: &CStandard = &configurations.compiler_configurations.c_standard;
+
+    let struct_name: String = pascal_to_snake_case(&struct_definition.name);
+
+    // Functions defined in headers should be inlined, which is only available from C99
+    let function_prefix: &'static str = match c_standard.allows_inline() {
+        true => "static inline",
+        false => "static"
+    };
+
+    let sorted_member_list: Vec<StructMember> = struct_definition
+        .sort_members(&configurations.compiler_configurations)?
+        .iter()
+        .map(|member| member.storage_member(&configurations.compiler_configurations))
+        .collect();
+
+    for member in &sorted_member_list {
+        // Arrays and 128 bit integers are byte accessed, or should be copied with memcpy directly
+        let member_type: String = match &member.data_type {
+            FieldType::Primitive(Primitive::I128) | FieldType::Primitive(Primitive::U128) => continue,
+            FieldType::Primitive(primitive) => primitive.to_c_type(c_standard)?,
+            FieldType::UserDefined(name) => format!("{0}_t", pascal_to_snake_case(name)),
+            FieldType::Array(_, _) | FieldType::Empty => continue
         };
 
-        let comma = match is_last {
-            true => ",",
-            false => ""
+        let member_name: String = pascal_to_snake_case(&member.identifier);
+
+        output_file.add_line(format!(
+            "RUNE_NODISCARD {0} {1} {2}_get_{3}(const {2}_t* message) {{",
+            function_prefix, member_type, struct_name, member_name
+        ));
+        output_file.add_indented_line(1, format!("{0} value;", member_type));
+
+        if configurations.compiler_configurations.debug_checks {
+            output_debug_checks(output_file, &["message != NULL"]);
+        }
+
+        output_file.add_indented_line(1, format!("memcpy(&value, &message->{0}, sizeof(value));", member_name));
+        output_file.add_indented_line(1, String::from("return value;"));
+        output_file.add_line(String::from("}"));
+        output_file.add_newline();
+
+        output_file.add_line(format!("{0} void {1}_set_{2}({1}_t* message, {3} value) {{", function_prefix, struct_name, member_name, member_type));
+
+        if configurations.compiler_configurations.debug_checks {
+            output_debug_checks(output_file, &["message != NULL"]);
+        }
+
+        output_file.add_indented_line(1, format!("memcpy(&message->{0}, &value, sizeof(value));", member_name));
+        output_file.add_line(String::from("}"));
+        output_file.add_newline();
+    }
+
+    Ok(())
+}
+
+/// Outputs getters for the struct members, returning single values by value, and nested structs and arrays by const pointer
+fn output_struct_getters(output_file: &mut OutputFile, configurations: &CConfigurations, struct_definition: &StructDefinition) -> Result<(), CompilerError> {
+    let c_standard: &CStandard = &configurations.compiler_configurations.c_standard;
+
+    let struct_name: String = pascal_to_snake_case(&struct_definition.name);
+
+    // Functions defined in headers should be inlined, which is only available from C99
+    let function_prefix: &'static str = match c_standard.allows_inline() {
+        true => "static inline",
+        false => "static"
+    };
+
+    // Getters are listed in index order
+    let mut members: Vec<StructMember> = struct_definition.members.iter().map(|member| member.storage_member(&configurations.compiler_configurations)).collect();
+    members.sort_by_key(|member| member.index.value());
+
+    for member in &members {
+        // Single values of packed structs already have memcpy based getters
+        let by_value: bool = !configurations.compiler_configurations.safe_packed_access();
+
+        let byte_pointer: String = format!("const {0}*", Primitive::U8.to_c_type(c_standard)?);
+
+        let (return_type, return_value): (String, String) = match (&member.data_type, &member.user_definition_link) {
+            // 128 bit integers are stored as byte arrays, so arrays of them are returned as a pointer to their first byte
+            (FieldType::Primitive(Primitive::I128 | Primitive::U128), _) => (byte_pointer, String::new()),
+            (FieldType::Array(ArrayType::Primitive(Primitive::I128 | Primitive::U128), _), _) => (byte_pointer.clone(), format!("({0})", byte_pointer)),
+            (FieldType::Primitive(primitive), _) if by_value => (primitive.to_c_type(c_standard)?, String::new()),
+            (FieldType::UserDefined(name), UserDefinitionLink::StructLink(_)) if by_value => (format!("const {0}_t*", pascal_to_snake_case(name)), String::from("&")),
+            (FieldType::UserDefined(name), _) if by_value => (format!("{0}_t", pascal_to_snake_case(name)), String::new()),
+            (FieldType::Array(ArrayType::Primitive(primitive), _), _) => (format!("const {0}*", primitive.to_c_type(c_standard)?), String::new()),
+            (FieldType::Array(ArrayType::UserDefined(name), _), _) => (format!("const {0}_t*", pascal_to_snake_case(name)), String::new()),
+            _ => continue
         };
 
-        let initializer_string = match c_standard.allows_designated_initializers() {
-            true => format!(
-                "    .{0}{1} = {2}{3} {4}\\",
-                member.identifier,
-                spaces(pre_equal),
-                member.data_type.c_initializer(c_standard)?,
-                comma,
-                spaces(pre_newline)
-            ),
-            false => format!("    {0}{1} {2}\\", member.data_type.c_initializer(c_standard)?, comma, spaces(pre_newline))
+        // Elements of packed arrays may be unaligned, so they must be copied out through an untyped pointer
+        let return_type: String = match &member.data_type {
+            FieldType::Array(_, array_size) if configurations.compiler_configurations.pack_data && !has_single_byte_elements(configurations, member, array_size)? => String::from("const void*"),
+            _ => return_type
         };
 
-        output_file.add_line(initializer_string);
-    }
-    output_file.add_line("}".to_string());
-    output_file.add_newline();
+        let member_name: String = pascal_to_snake_case(&member.identifier);
 
-    output_file.add_line(format!(
-        "#define {0}_DESCRIPTOR &{1}_descriptor",
-        pascal_to_uppercase(&struct_definition.name),
-        pascal_to_snake_case(&struct_definition.name)
-    ));
-    output_file.add_newline();
+        output_file.add_line(format!(
+            "RUNE_NODISCARD {0} {1} {2}_get_{3}(const {2}_t* message) {{",
+            function_prefix, return_type, struct_name, member_name
+        ));
+        if configurations.compiler_configurations.debug_checks {
+            output_debug_checks(output_file, &["message != NULL"]);
+        }
+
+        output_file.add_indented_line(1, format!("return {0}message->{1};", return_value, member_name));
+        output_file.add_line(String::from("}"));
+        output_file.add_newline();
+    }
 
     Ok(())
 }
 
-pub fn output_header(file: &RuneFileDescription, configurations: &CConfigurations, output_path: &Path) -> Result<(), CompilerError> {
+/// Whether the elements of an array member are single bytes, and thus never unaligned. Before C23 and without strict enum width, enums
+/// are int sized whatever their backing type, so their elements, and those of structs containing them, are multi-byte
+fn has_single_byte_elements(configurations: &CConfigurations, member: &StructMember, array_size: &ArraySize) -> Result<bool, CompilerError> {
+    Ok(member.has_fixed_size(&configurations.compiler_configurations) && member.c_size()? / member.array_length(array_size)?.max(1) <= 1)
+}
+
+/// Include guard of a header generated from the file, with the given extension
+pub fn header_guard(file: &RuneFileDescription, configurations: &CConfigurations, extension: &str) -> String {
+    // Files of the same name in different directories would otherwise share a guard
+    let namespace: String = path_namespace(&file.relative_path);
+    let guard: String = match (configurations.compiler_configurations.namespace_from_path || configurations.compiler_configurations.flatten) && !namespace.is_empty() {
+        true => format!("{0}_{1}_{2}", pascal_to_uppercase(&namespace), file.name.to_uppercase(), extension.replace('.', "_").to_uppercase()),
+        false => format!("{0}_{1}", file.name.to_uppercase(), extension.replace('.', "_").to_uppercase())
+    };
+
+    match &configurations.compiler_configurations.prefix {
+        Some(prefix) => format!("{0}_{1}", pascal_to_uppercase(&path_namespace(prefix)), guard),
+        None => guard
+    }
+}
+
+pub fn output_header(file: &RuneFileDescription, file_descriptions: &Vec<RuneFileDescription>, configurations: &CConfigurations) -> Result<OutputFile, CompilerError> {
     // Print disclaimers. Requires C23 compliant compiler
     //
     // · Autogenerated code info
@@ -529,32 +909,34 @@ pub fn output_header(file: &RuneFileDescription, configurations: &CConfiguration
     //
     // · standard includes
     //
-    // <stdbool.h>
-    // <stdint.h>
+    // <stdbool.h> (C99 or newer, when bool is used)
+    // <stdint.h> (C99 or newer, when exact-width integers are used)
     //
     // —————————————————————————————————————————————————
 
-    let h_file_string: String = format!(
-        "{0}{1}.rune.h",
-        match file.relative_path.is_empty() {
-            true => String::new(),
-            false => format!("/{0}", file.relative_path)
-        },
-        file.name
-    );
+    let extension: String = configurations.compiler_configurations.output_extension("h");
 
-    let mut header_file: OutputFile = OutputFile::new(String::from(output_path.to_str().unwrap()), h_file_string);
+    let h_file_string: String = format!("{0}.{1}", output_base_name(file, &configurations.compiler_configurations), extension);
+
+    let mut header_file: OutputFile = OutputFile::new(h_file_string, &configurations.compiler_configurations);
 
     // Disclaimers
     // ————————————
 
-    // ...
+    output_disclaimer(&mut header_file, &configurations.compiler_configurations);
 
     // Start & C++ guards
     // ———————————————————
 
-    header_file.add_line(format!("#ifndef {0}_RUNE_H", file.name.to_uppercase()));
-    header_file.add_line(format!("#define {0}_RUNE_H", file.name.to_uppercase()));
+    let guard: String = header_guard(file, configurations, &extension);
+
+    match configurations.compiler_configurations.include_guard_style {
+        IncludeGuardStyle::Ifndef => {
+            header_file.add_line(format!("#ifndef {0}", guard));
+            header_file.add_line(format!("#define {0}", guard));
+        },
+        IncludeGuardStyle::PragmaOnce => header_file.add_line(String::from("#pragma once"))
+    }
     header_file.add_newline();
 
     header_file.add_line("#ifdef __cplusplus".to_string());
@@ -565,19 +947,59 @@ pub fn output_header(file: &RuneFileDescription, configurations: &CConfiguration
     // File inclusions
     // ————————————————
 
-    // Standard library
-    header_file.add_line("#include <stdbool.h>".to_string());
-    header_file.add_line("#include <stdint.h>".to_string());
+    // Standard library. Only included when used
+    if needs_stdbool(file, configurations)? {
+        header_file.add_line("#include <stdbool.h>".to_string());
+    }
+
+    // Size helpers, codec and checksum functions use size_t
+    if (configurations.compiler_configurations.c_standard.allows_inline() || configurations.compiler_configurations.codec || configurations.compiler_configurations.verifiers)
+        && !file.definitions.structs.is_empty()
+    {
+        header_file.add_line("#include <stddef.h>".to_string());
+    }
+
+    if needs_stdint(file, configurations)? {
+        header_file.add_line("#include <stdint.h>".to_string());
+    }
+
+    // Accessors copy members with memcpy
+    if configurations.compiler_configurations.safe_packed_access() && !file.definitions.structs.is_empty() {
+        header_file.add_line("#include <string.h>".to_string());
+    }
+
     header_file.add_newline();
 
-    // Include Runic Definitions
-    header_file.add_line("#include \"rune.h\"".to_string());
+    // Include Runic Definitions. Without descriptors the Rune library is not needed, only the definitions it includes
+    match configurations.compiler_configurations.descriptors {
+        true => header_file.add_line("#include \"rune.h\"".to_string()),
+        false => header_file.add_line(format!("#include \"{0}\"", configurations.compiler_configurations.definitions_output))
+    }
+
+    // TLV descriptors are declared next to the regular ones
+    if configurations.compiler_configurations.tlv && !file.definitions.structs.is_empty() {
+        header_file.add_line("#include \"rune_tlv.h\"".to_string());
+    }
+
+    if configurations.compiler_configurations.named_fields && !file.definitions.structs.is_empty() {
+        header_file.add_line("#include \"rune_named_fields.h\"".to_string());
+    }
+
     header_file.add_newline();
 
-    if !file.definitions.includes.is_empty() {
+    let (deferred_includes, includes): (Vec<&IncludeDefinition>, Vec<&IncludeDefinition>) = file
+        .definitions
+        .includes
+        .iter()
+        .partition(|include_definition| is_deferred_include(file, include_definition, file_descriptions));
+
+    if !includes.is_empty() {
         // Print out includes
-        for include_definition in &file.definitions.includes {
-            header_file.add_line(format!("#include \"{0}.rune.h\"", include_definition.file));
+        for include_definition in &includes {
+            header_file.add_line(format!(
+                "#include \"{0}.rune.h\"",
+                include_base_name(include_definition, file_descriptions, &configurations.compiler_configurations)
+            ));
         }
 
         // Separation line
@@ -589,7 +1011,7 @@ pub fn output_header(file: &RuneFileDescription, configurations: &CConfiguration
 
     if !file.definitions.defines.is_empty() {
         for define in &file.definitions.defines {
-            output_define(&mut header_file, define);
+            output_define(&mut header_file, configurations, define);
         }
         header_file.add_newline();
     }
@@ -617,7 +1039,39 @@ pub fn output_header(file: &RuneFileDescription, configurations: &CConfiguration
         output_struct(&mut header_file, configurations, struct_definition)?;
 
         // Add struct initializer
-        output_struct_initializer(&mut header_file, configurations, struct_definition)?
+        output_struct_initializer(&mut header_file, configurations, struct_definition)?;
+
+        // Add fixed-point conversions
+        output_fixed_point_macros(&mut header_file, configurations, struct_definition)?;
+
+        // Add size helper
+        output_struct_size_helper(&mut header_file, configurations, struct_definition);
+
+        // Add unaligned access safe accessors
+        if configurations.compiler_configurations.safe_packed_access() {
+            output_struct_accessors(&mut header_file, configurations, struct_definition)?;
+        }
+
+        // Add getters
+        if configurations.compiler_configurations.getters {
+            output_struct_getters(&mut header_file, configurations, struct_definition)?;
+        }
+    }
+
+    // Deferred includes
+    // ——————————————————
+
+    if !deferred_includes.is_empty() {
+        header_file.add_line(String::from("/* Included last, as these include this file back */"));
+
+        for include_definition in &deferred_includes {
+            header_file.add_line(format!(
+                "#include \"{0}.rune.h\"",
+                include_base_name(include_definition, file_descriptions, &configurations.compiler_configurations)
+            ));
+        }
+
+        header_file.add_newline();
     }
 
     // End & C++ guards
@@ -626,12 +1080,428 @@ pub fn output_header(file: &RuneFileDescription, configurations: &CConfiguration
     header_file.add_line("#ifdef __cplusplus".to_string());
     header_file.add_line("}".to_string());
     header_file.add_line("#endif /* __cplusplus */".to_string());
-    header_file.add_newline();
 
-    header_file.add_line(format!("#endif /* {0}_RUNE_H */", file.name.to_uppercase()));
+    if configurations.compiler_configurations.include_guard_style == IncludeGuardStyle::Ifndef {
+        header_file.add_newline();
+        header_file.add_line(format!("#endif /* {0} */", guard));
+    }
 
-    // Output file
-    // ————————————
+    Ok(header_file)
+}
+
+#[cfg(test)]
+mod tests {
+    use rune_parser::{RuneFileDescription, types::BitSize};
+
+    use crate::{
+        c_utilities::CConfigurations,
+        compile_error::CompilerError,
+        generate_to_memory,
+        header::output_header,
+        test_utilities::{compile_configurations, parse_schema, parse_single_schema}
+    };
+
+    const BITFIELD_SCHEMA: &str = "bitfield Flags: u8 {\n    Enabled: u1 = 0;\n    Level: i3 = 1\n}\n";
+
+    const ENUM_SCHEMA: &str = "enum Mode: u8 {\n    Idle = 0;\n    Running = 1\n}\n\nstruct Status {\n    Current: Mode = 1;\n    History: [Mode; 2] = 2\n}\n";
+
+    /// Output the header of a single Rune file, compiled with the given command line arguments
+    fn schema_header(contents: &str, arguments: &[&str]) -> String {
+        let (file_descriptions, configurations): (Vec<RuneFileDescription>, CConfigurations) = parse_single_schema(contents, arguments);
+
+        output_header(&file_descriptions[0], &file_descriptions, &configurations).unwrap().into_contents()
+    }
+
+    fn bitfield_header(c_standard: &str) -> String {
+        schema_header(BITFIELD_SCHEMA, &["--c-standard", c_standard])
+    }
+
+    #[test]
+    fn bitfield_size_is_asserted_from_c11() {
+        let header: String = bitfield_header("C11");
+
+        assert!(header.contains("_Static_assert(sizeof(flags_t) == sizeof(uint8_t)"));
+        assert!(header.contains("#ifdef __cplusplus\nstatic_assert(sizeof(flags_t) == sizeof(uint8_t)"));
+    }
+
+    #[test]
+    fn bitfield_size_is_asserted_with_keyword_from_c23() {
+        let header: String = bitfield_header("C23");
+
+        assert!(header.contains("\nstatic_assert(sizeof(flags_t) == sizeof(uint8_t)"));
+        assert!(!header.contains("_Static_assert"));
+    }
+
+    #[test]
+    fn bitfield_size_is_not_asserted_before_c11() {
+        let header: String = bitfield_header("C99");
+
+        assert!(!header.contains("static_assert"));
+        assert!(!header.contains("_Static_assert"));
+    }
+
+    #[test]
+    fn enum_members_are_declared_as_enums() {
+        let header: String = schema_header(ENUM_SCHEMA, &["--c-standard", "C11"]);
 
-    header_file.output_file()
+        assert!(header.contains("    mode_t current;"));
+        assert!(header.contains("    mode_t history[2];"));
+    }
+
+    #[test]
+    fn enum_members_are_declared_as_backing_type_with_strict_enum_width() {
+        let header: String = schema_header(ENUM_SCHEMA, &["--c-standard", "C11", "--strict-enum-width"]);
+
+        assert!(header.contains("    uint8_t current;"));
+        assert!(header.contains("    uint8_t history[2];"));
+        assert!(header.contains("} mode_t;"));
+    }
+
+    #[test]
+    fn booleans_are_declared_as_rune_bool_before_c99() {
+        let schema: &str = "struct Switch {\n    Active: bool = 1\n}\n";
+
+        assert!(schema_header(schema, &["--c-standard", "C89"]).contains("    rune_bool_t active;"));
+        assert!(schema_header(schema, &["--c-standard", "C99"]).contains("    bool active;"));
+    }
+
+    #[test]
+    fn padding_members_are_output_where_the_compiler_would_pad() {
+        let schema: &str = "struct Frame {\n    Flag: u8 = 1;\n    Count: u32 = 2;\n    Level: u16 = 3\n}\n";
+        let header: String = schema_header(schema, &["--explicit-padding", "--unsorted"]);
+
+        assert!(header.contains("    uint8_t flag;\n    uint8_t _pad0[3];\n    uint32_t count;\n    uint16_t level;\n    uint8_t _pad1[2];\n} frame_t;"));
+    }
+
+    #[test]
+    fn padding_members_are_not_output_without_padding() {
+        let schema: &str = "struct Frame {\n    Flag: u8 = 1;\n    Count: u32 = 2;\n    Level: u16 = 3\n}\n";
+
+        assert!(!schema_header(schema, &["--unsorted"]).contains("_pad"));
+        assert!(!schema_header(schema, &["--explicit-padding", "--pack-data"]).contains("_pad"));
+    }
+
+    #[test]
+    fn size_helper_is_an_inline_function_from_c99() {
+        let schema: &str = "struct Frame {\n    Flag: u8 = 1\n}\n";
+
+        assert!(schema_header(schema, &["--c-standard", "C99"]).contains("static inline size_t frame_size(void) {\n    return sizeof(frame_t);\n}"));
+        assert!(schema_header(schema, &["--c-standard", "C89"]).contains("#define frame_size() (sizeof(frame_t))"));
+    }
+
+    const PADDED_BITFIELD_SCHEMA: &str = "bitfield Flags: u8 {\n    Enabled: u1 = 0;\n    Level: u3 = 1\n}\n\nbitfield Full: u8 {\n    Low: u4 = 0;\n    High: u4 = 1\n}\n";
+
+    #[test]
+    fn bitfield_padding_is_named() {
+        let header: String = schema_header(PADDED_BITFIELD_SCHEMA, &["--endianness", "little"]);
+
+        assert!(header.contains("    uint8_t padding : 4;"));
+        assert!(header.contains("    uint8_t low  : 4;\n    uint8_t high : 4;\n} full_t;"));
+    }
+
+    #[test]
+    fn bitfield_padding_is_anonymous_with_anonymous_bitfield_padding() {
+        let header: String = schema_header(PADDED_BITFIELD_SCHEMA, &["--endianness", "little", "--anonymous-bitfield-padding"]);
+
+        assert!(header.contains("    uint8_t         : 4;"));
+        assert!(!header.contains("padding :"));
+        assert!(header.contains("    uint8_t low  : 4;\n    uint8_t high : 4;\n} full_t;"));
+    }
+
+    #[test]
+    fn float_only_header_includes_no_standard_header_in_c89() {
+        let header: String = schema_header("struct Reading {\n    Value: f32 = 1;\n    Label: char = 2\n}\n", &["--c-standard", "C89"]);
+
+        assert!(!header.contains("#include <"));
+    }
+
+    #[test]
+    fn standard_headers_are_included_when_used() {
+        let header: String = schema_header("struct Reading {\n    Value: u16 = 1;\n    On: bool = 2\n}\n", &["--c-standard", "C11"]);
+
+        assert!(header.contains("#include <stdbool.h>\n#include <stddef.h>\n#include <stdint.h>\n"));
+    }
+
+    const GETTER_SCHEMA: &str = "enum Mode: u8 {\n    Idle = 0;\n    Running = 1\n}\n\nstruct Point {\n    X: i16 = 1\n}\n\nstruct Status {\n    Value: u16 = 1;\n    Current: Mode = 2;\n    Origin: Point = 3;\n    Readings: [u8; 4] = 4\n}\n";
+
+    #[test]
+    fn getters_return_primitives_and_enums_by_value() {
+        let header: String = schema_header(GETTER_SCHEMA, &["--emit-getters"]);
+
+        assert!(header.contains("static inline uint16_t status_get_value(const status_t* message) {\n    return message->value;\n}"));
+        assert!(header.contains("static inline mode_t status_get_current(const status_t* message) {\n    return message->current;\n}"));
+    }
+
+    #[test]
+    fn getters_return_structs_and_arrays_by_const_pointer() {
+        let header: String = schema_header(GETTER_SCHEMA, &["--emit-getters"]);
+
+        assert!(header.contains("static inline const point_t* status_get_origin(const status_t* message) {\n    return &message->origin;\n}"));
+        assert!(header.contains("static inline const uint8_t* status_get_readings(const status_t* message) {\n    return message->readings;\n}"));
+    }
+
+    #[test]
+    fn getters_are_only_output_when_requested() {
+        assert!(!schema_header(GETTER_SCHEMA, &[]).contains("_get_"));
+    }
+
+    #[test]
+    fn wide_integers_are_declared_as_byte_arrays() {
+        let header: String = schema_header("struct Wide {\n    Value: u128 = 1;\n    History: [i128; 3] = 2\n}\n", &[]);
+
+        assert!(header.contains("    uint8_t value[16];\n    uint8_t history[3][16];"));
+    }
+
+    #[test]
+    fn bitfield_wider_than_backing_type_is_rejected() {
+        let (mut file_descriptions, configurations): (Vec<RuneFileDescription>, CConfigurations) = parse_single_schema(BITFIELD_SCHEMA, &[]);
+
+        // The parser rejects bitfields wider than their backing type, so a member is widened after parsing
+        file_descriptions[0].definitions.bitfields[0].members[1].size = BitSize::Signed(8);
+
+        assert!(matches!(output_header(&file_descriptions[0], &file_descriptions, &configurations), Err(CompilerError::MalformedSource)));
+    }
+
+    const NESTED_SCHEMA: &str = "struct Point {\n    X: f32 = 1;\n    Y: f32 = 2\n}\n\nstruct Position {\n    Current: Point = 1;\n    History: [Point; 2] = 2;\n    Valid: bool = 3\n}\n\nstruct Track {\n    LastKnownPosition: Position = 1\n}\n";
+
+    #[test]
+    fn nested_structs_are_initialized_in_place_with_designators() {
+        let header: String = schema_header(NESTED_SCHEMA, &["--c-standard", "C23"]);
+
+        assert!(header.contains("#define POSITION_INIT (position_t) {"));
+        assert!(header.contains("    .current = { .x = 0.0f, .y = 0.0f },"));
+        assert!(header.contains("    .history = { { .x = 0.0f, .y = 0.0f } },"));
+        assert!(header.contains("    .last_known_position = { .current = { .x = 0.0f, .y = 0.0f }, .history = { { .x = 0.0f, .y = 0.0f } }, .valid = false } \\"));
+        assert!(!header.contains("= POINT_INIT"));
+    }
+
+    #[test]
+    fn nested_structs_are_initialized_in_place_without_compound_literals_before_c99() {
+        let header: String = schema_header(NESTED_SCHEMA, &["--c-standard", "C89"]);
+
+        assert!(header.contains("#define POSITION_INIT {"));
+        assert!(header.contains("    { 0.0f, 0.0f },"));
+        assert!(header.contains("    { { 0.0f, 0.0f }, { { 0.0f, 0.0f } }, RUNE_FALSE } \\"));
+        assert!(!header.contains("_t) {"));
+        assert!(!header.contains("POINT_INIT,"));
+    }
+
+    /// Get the lines of the _INIT macro of a struct, from its define up to its closing brace
+    fn initializer_lines<'a>(header: &'a str, define: &str) -> Vec<&'a str> {
+        let start: usize = header.find(define).expect("Initializer should be output");
+
+        header[start..].lines().take_while(|line| *line != "}").collect()
+    }
+
+    /// Check that every line of the _INIT macro of a struct ends with a backslash, all at the same column
+    fn assert_backslashes_aligned(header: &str, define: &str) {
+        let lines: Vec<&str> = initializer_lines(header, define);
+        let width: usize = lines[0].chars().count();
+
+        assert!(lines.len() > 1);
+
+        // The widest line is not padded
+        assert!(lines.iter().any(|line| !line.ends_with("  \\")));
+
+        for line in lines {
+            assert!(line.ends_with(" \\"), "{0:?} should end with a backslash", line);
+            assert_eq!(line.chars().count(), width, "{0:?} should align its backslash", line);
+        }
+    }
+
+    #[test]
+    fn initializer_backslashes_are_aligned_for_short_and_long_names() {
+        let schema: &str = "struct A {\n    X: u8 = 1\n}\n\nstruct Reading {\n    ExceptionallyLongMeasurementIdentifier: u32 = 1;\n    V: bool = 2\n}\n";
+
+        for c_standard in ["C89", "C99", "C23"] {
+            let header: String = schema_header(schema, &["--c-standard", c_standard]);
+
+            assert_backslashes_aligned(&header, "#define A_INIT");
+            assert_backslashes_aligned(&header, "#define READING_INIT");
+        }
+    }
+
+    #[test]
+    fn initializer_backslashes_follow_the_widest_line() {
+        let header: String = schema_header("struct A {\n    X: u8 = 1\n}\n", &["--c-standard", "C99"]);
+
+        assert_eq!(initializer_lines(&header, "#define A_INIT"), ["#define A_INIT (a_t) { \\", "    .x = 0             \\"]);
+    }
+
+    #[test]
+    fn comments_containing_terminators_do_not_end_the_block() {
+        let header: String = schema_header("// Point in space, see a*/b\nstruct Point {\n    // X coordinate */\n    X: f32 = 1\n}\n", &[]);
+
+        assert!(header.contains("/** Point in space, see a*\\/b */\n"));
+        assert!(header.contains("    /** X coordinate *\\/ */\n"));
+    }
+
+    #[test]
+    fn packed_enum_arrays_are_returned_untyped_unless_their_size_is_fixed() {
+        let schema: &str = "enum Mode: u8 {\n    Idle = 0;\n    Running = 1\n}\n\nstruct Outer {\n    Ms: [Mode; 4] = 1\n}\n";
+        let packed_getters: [&str; 4] = ["--pack-data", "--safe-packed-access", "--emit-getters", "--c-standard"];
+
+        // Before C23 the enum is int sized, whatever its backing type
+        let header: String = schema_header(schema, &[&packed_getters[..], &["C11"]].concat());
+        assert!(header.contains("const void* outer_get_ms(const outer_t* message) {"));
+
+        let header: String = schema_header(schema, &[&packed_getters[..], &["C11", "--strict-enum-width"]].concat());
+        assert!(header.contains("const uint8_t* outer_get_ms(const outer_t* message) {"));
+
+        let header: String = schema_header(schema, &[&packed_getters[..], &["C23"]].concat());
+        assert!(header.contains("const mode_t* outer_get_ms(const outer_t* message) {"));
+    }
+
+    const ALPHA_SCHEMA: &str = "include \"b.rune\";\n\nstruct Alpha {\n    X: u8 = 1\n}\n";
+    const BETA_SCHEMA: &str = "include \"a.rune\";\n\nstruct Beta {\n    Origin: Alpha = 1\n}\n";
+
+    /// Output the files generated from the given Rune files with the default arguments, and get the contents of the named one
+    fn generated_file(files: &[(&str, &str)], name: &str) -> String {
+        let output_files: Vec<(String, String)> = generate_to_memory(&parse_schema(files), &compile_configurations(&[])).unwrap();
+
+        output_files.into_iter().find(|(output_name, _)| output_name == name).expect("File should be generated").1
+    }
+
+    #[test]
+    fn include_of_a_cycle_is_output_after_the_definitions_when_nothing_is_used_from_it() {
+        let header: String = generated_file(&[("a.rune", ALPHA_SCHEMA), ("b.rune", BETA_SCHEMA)], "a.rune.h");
+
+        let definitions: usize = header.find("} alpha_t;").unwrap();
+        let include: usize = header.find("/* Included last, as these include this file back */\n#include \"b.rune.h\"").unwrap();
+
+        assert!(include > definitions);
+        assert_eq!(header.matches("#include \"b.rune.h\"").count(), 1);
+    }
+
+    #[test]
+    fn include_of_a_cycle_stays_at_the_top_when_its_types_are_used() {
+        let header: String = generated_file(&[("a.rune", ALPHA_SCHEMA), ("b.rune", BETA_SCHEMA)], "b.rune.h");
+
+        assert!(header.find("#include \"a.rune.h\"").unwrap() < header.find("typedef struct").unwrap());
+        assert!(!header.contains("Included last"));
+    }
+
+    #[test]
+    fn acyclic_includes_stay_at_the_top() {
+        let gamma: &str = "struct Gamma {\n    X: u8 = 1\n}\n";
+        let delta: &str = "include \"c.rune\";\n\nstruct Delta {\n    Value: u8 = 1\n}\n";
+        let header: String = generated_file(&[("c.rune", gamma), ("d.rune", delta)], "d.rune.h");
+
+        assert!(header.find("#include \"c.rune.h\"").unwrap() < header.find("typedef struct").unwrap());
+        assert!(!header.contains("Included last"));
+    }
+
+    const SENSOR_SCHEMA: &str = "struct Sensor {\n    Temperature: i32 = 1;\n    Level: u16 = 2;\n    History: [i16; 4] = 3\n}\n";
+    const SENSOR_FIXED_POINT_MEMBERS: &str = "Sensor.Temperature=Q16.16,Sensor.Level=UQ8.8,Sensor.History=Q4.12";
+
+    #[test]
+    fn fixed_point_members_get_conversion_macros() {
+        let header: String = schema_header(SENSOR_SCHEMA, &["--fixed-point-members", SENSOR_FIXED_POINT_MEMBERS]);
+
+        assert!(header.contains(
+            "#define SENSOR_TEMPERATURE_FRACTIONAL_BITS 16\n#define SENSOR_TEMPERATURE_TO_FLOAT(value) ((double)(value) / 65536.0)\n#define FLOAT_TO_SENSOR_TEMPERATURE(value) ((int32_t)((value) * 65536.0))\n"
+        ));
+        assert!(
+            header.contains(
+                "#define SENSOR_LEVEL_FRACTIONAL_BITS 8\n#define SENSOR_LEVEL_TO_FLOAT(value) ((double)(value) / 256.0)\n#define FLOAT_TO_SENSOR_LEVEL(value) ((uint16_t)((value) * 256.0))\n"
+            )
+        );
+        assert!(header.contains(
+            "#define SENSOR_HISTORY_FRACTIONAL_BITS 12\n#define SENSOR_HISTORY_TO_FLOAT(value) ((double)(value) / 4096.0)\n#define FLOAT_TO_SENSOR_HISTORY(value) ((int16_t)((value) * 4096.0))\n"
+        ));
+    }
+
+    #[test]
+    fn fixed_point_members_keep_the_struct_layout() {
+        let header: String = schema_header(SENSOR_SCHEMA, &["--fixed-point-members", SENSOR_FIXED_POINT_MEMBERS]);
+
+        // Leaving out the conversion macros and their comments gives the header without fixed-point members
+        let without_macros: String = header
+            .split_inclusive('\n')
+            .filter(|line| !line.contains("FRACTIONAL_BITS") && !line.contains("TO_FLOAT") && !line.contains("FLOAT_TO") && !line.contains("fixed-point values"))
+            .collect::<String>()
+            .replace("\n\n\n", "\n\n");
+
+        assert_eq!(without_macros, schema_header(SENSOR_SCHEMA, &[]));
+    }
+
+    #[test]
+    fn bitfield_members_follow_the_indentation() {
+        let header: String = schema_header(BITFIELD_SCHEMA, &["--indent", "tabs"]);
+
+        assert!(header.contains("typedef struct RUNIC_BITFIELD flags {\n\tuint8_t enabled : 1;\n\tint8_t  level   : 3;\n"));
+    }
+
+    #[test]
+    fn inlined_members_are_output_as_anonymous_structs() {
+        let schema: &str = "struct Point {\n    X: i16 = 1;\n    Y: i16 = 2\n}\n\nstruct Sample {\n    Origin: Point = 1;\n    Count: u8 = 2\n}\n";
+        let header: String = schema_header(schema, &["--inline-members", "Sample.Origin"]);
+
+        assert!(header.contains("typedef struct RUNIC_STRUCT sample {\n    struct RUNIC_STRUCT { /* origin */\n        int16_t x;\n        int16_t y;\n    };\n    uint8_t count;\n} sample_t;"));
+        assert!(header.contains("    .x     = 0,"));
+        assert!(!header.contains(".origin"));
+
+        // Without the option the member keeps its name
+        assert!(schema_header(schema, &[]).contains("    point_t origin;"));
+    }
+
+    const MSVC_SCHEMA: &str = "bitfield Flags: u8 {\n    Enabled: u1 = 0\n}\n\nstruct Frame {\n    Flag: u8 = 1;\n    Count: u32 = 2\n}\n";
+
+    #[test]
+    fn msvc_packs_declarations_with_pragmas() {
+        let header: String = schema_header(MSVC_SCHEMA, &["--compiler", "msvc", "--pack-data"]);
+
+        assert!(header.contains("#pragma pack(push, 1)\ntypedef struct RUNIC_STRUCT frame {"));
+        assert!(header.contains("} frame_t;\n#pragma pack(pop)"));
+        assert!(header.contains("#pragma pack(push, 1)\n#if defined __LITTLE_ENDIAN__\ntypedef struct RUNIC_BITFIELD flags {"));
+        assert!(header.contains("#endif // __BYTE_ORDER__\n#pragma pack(pop)"));
+        assert_eq!(header.matches("#pragma pack(push, 1)").count(), 2);
+        assert_eq!(header.matches("#pragma pack(pop)").count(), 2);
+    }
+
+    #[test]
+    fn msvc_only_packs_structs_with_packed_data() {
+        let header: String = schema_header(MSVC_SCHEMA, &["--compiler", "msvc"]);
+
+        // Bitfields are always packed
+        assert!(header.contains("#pragma pack(push, 1)\n#if defined __LITTLE_ENDIAN__\ntypedef struct RUNIC_BITFIELD flags {"));
+        assert!(!header.contains("#pragma pack(push, 1)\ntypedef struct RUNIC_STRUCT frame {"));
+        assert_eq!(header.matches("#pragma pack(push, 1)").count(), 1);
+
+        assert!(!schema_header(MSVC_SCHEMA, &["--pack-data"]).contains("#pragma pack"));
+    }
+
+    #[test]
+    fn pragma_once_replaces_the_include_guard() {
+        let header: String = schema_header(MSVC_SCHEMA, &["--include-guard-style", "pragma-once"]);
+
+        assert!(header.starts_with("#pragma once\n\n#ifdef __cplusplus\nextern \"C\" {\n#endif /* __cplusplus */\n"));
+        assert!(header.ends_with("#ifdef __cplusplus\n}\n#endif /* __cplusplus */\n"));
+        assert!(!header.contains("#ifndef SCHEMA_RUNE_H") && !header.contains("#define SCHEMA_RUNE_H"));
+
+        let guarded: String = schema_header(MSVC_SCHEMA, &[]);
+        assert!(guarded.starts_with("#ifndef SCHEMA_RUNE_H\n#define SCHEMA_RUNE_H\n"));
+        assert!(guarded.ends_with("#endif /* __cplusplus */\n\n#endif /* SCHEMA_RUNE_H */\n"));
+    }
+
+    const PACKED_SCHEMA: &str = "struct Frame {\n    Flag: u8 = 1;\n    Count: u32 = 2\n}\n";
+
+    #[test]
+    fn safe_packed_accessors_copy_members_with_memcpy() {
+        let header: String = schema_header(PACKED_SCHEMA, &["--pack-data", "--safe-packed-access"]);
+
+        assert!(header.contains("#include <string.h>"));
+        assert!(header.contains(
+            "RUNE_NODISCARD static inline uint32_t frame_get_count(const frame_t* message) {\n    uint32_t value;\n    memcpy(&value, &message->count, sizeof(value));\n    return value;\n}"
+        ));
+        assert!(header.contains("static inline void frame_set_count(frame_t* message, uint32_t value) {\n    memcpy(&message->count, &value, sizeof(value));\n}"));
+    }
+
+    #[test]
+    fn safe_packed_access_requires_packed_data() {
+        let header: String = schema_header(PACKED_SCHEMA, &["--safe-packed-access"]);
+
+        assert!(!header.contains("memcpy"));
+        assert!(!header.contains("#include <string.h>"));
+    }
 }
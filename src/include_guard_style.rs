@@ -0,0 +1,24 @@
+use crate::{compile_error::CompilerError, output::*};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum IncludeGuardStyle {
+    Ifndef,
+    PragmaOnce
+}
+
+impl IncludeGuardStyle {
+    pub fn from_string(string: &str) -> Result<IncludeGuardStyle, CompilerError> {
+        match string {
+            "ifndef" => Ok(IncludeGuardStyle::Ifndef),
+            "pragma-once" => Ok(IncludeGuardStyle::PragmaOnce),
+            _ => {
+                error!("Invalid include guard style passed. Got {0}, and valid values are: {1}", string, IncludeGuardStyle::valid_values());
+                Err(CompilerError::InvalidArgument)
+            }
+        }
+    }
+
+    fn valid_values() -> String {
+        String::from("ifndef, pragma-once")
+    }
+}
@@ -0,0 +1,32 @@
+use crate::{compile_error::CompilerError, output::*};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Indentation {
+    Tabs,
+    Spaces(usize)
+}
+
+impl Indentation {
+    pub fn from_string(string: &str) -> Result<Indentation, CompilerError> {
+        match (string, string.parse::<usize>()) {
+            ("tabs", _) => Ok(Indentation::Tabs),
+            (_, Ok(count)) if count > 0 => Ok(Indentation::Spaces(count)),
+            _ => {
+                error!("Invalid indentation passed. Got {0}, and valid values are: {1}", string, Indentation::valid_values());
+                Err(CompilerError::InvalidArgument)
+            }
+        }
+    }
+
+    fn valid_values() -> String {
+        String::from("tabs, or a positive number of spaces")
+    }
+
+    /// The string making up a single level of indentation
+    pub fn as_string(&self) -> String {
+        match self {
+            Indentation::Tabs => String::from("\t"),
+            Indentation::Spaces(count) => " ".repeat(*count)
+        }
+    }
+}
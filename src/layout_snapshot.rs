@@ -0,0 +1,143 @@
+use std::path::Path;
+
+use rune_parser::{
+    RuneFileDescription,
+    types::{ArrayType, FieldType, StructDefinition}
+};
+
+use crate::{
+    c_utilities::{CStructDefinition, CompileConfigurations, MemberLayout, sorted_struct_definitions},
+    compile_error::CompilerError,
+    output_file::{OutputFile, split_file_path}
+};
+
+/// Get the type of a member as it is written in Rune files
+fn rune_type_name(data_type: &FieldType) -> String {
+    match data_type {
+        FieldType::Empty => String::from("(empty)"),
+        FieldType::Primitive(primitive) => format!("{0:?}", primitive).to_ascii_lowercase(),
+        FieldType::UserDefined(name) => name.clone(),
+        FieldType::Array(array_type, array_size) => match array_type {
+            ArrayType::Primitive(primitive) => format!("[{0}; {1}]", format!("{0:?}", primitive).to_ascii_lowercase(), array_size),
+            ArrayType::UserDefined(name) => format!("[{0}; {1}]", name, array_size)
+        }
+    }
+}
+
+/// Outputs a human readable snapshot of the estimated layout of every struct, meant to be committed so layout changes show up in
+/// diffs. It must only depend on the schema and the layout affecting options, so regenerating an unchanged schema leaves it untouched
+pub fn output_layout_snapshot(file_descriptions: &Vec<RuneFileDescription>, configurations: &CompileConfigurations, snapshot_path: &str) -> Result<(), CompilerError> {
+    let (snapshot_folder, snapshot_name): (&Path, String) = split_file_path("--emit-layout-snapshot", snapshot_path)?;
+
+    let mut snapshot_file: OutputFile = OutputFile::new(snapshot_name, configurations);
+
+    // Description
+    // ————————————
+
+    snapshot_file.add_line(String::from("# Rune struct layout snapshot, generated by the Rune C compiler. Do not edit"));
+    snapshot_file.add_line(String::from("#"));
+    snapshot_file.add_line(String::from(
+        "# Commit this file together with the Rune files. A change to it means the in-memory layout or message indexes"
+    ));
+    snapshot_file.add_line(String::from("# of a struct changed, and that wire compatibility with peers using the previous schema may be broken"));
+    snapshot_file.add_newline();
+
+    snapshot_file.add_line(format!("C standard:        {0}", configurations.c_standard));
+    snapshot_file.add_line(format!("Architecture:      {0} bit", configurations.architecture.byte_size() * 8));
+    snapshot_file.add_line(format!("Packed:            {0}", configurations.pack_data));
+    snapshot_file.add_line(format!("Sorted:            {0}", configurations.sort));
+    snapshot_file.add_line(format!("Explicit padding:  {0}", configurations.explicit_padding));
+    snapshot_file.add_line(format!("Strict enum width: {0}", configurations.strict_enum_width));
+    snapshot_file.add_newline();
+
+    // Structs
+    // ————————
+
+    // Listed in message index order, which is alphabetical and thus independent of file discovery order
+    let struct_definitions: Vec<StructDefinition> = sorted_struct_definitions(file_descriptions);
+
+    for (message_index, struct_definition) in struct_definitions.iter().enumerate() {
+        let layout: Vec<MemberLayout> = struct_definition.estimate_layout(configurations)?;
+
+        snapshot_file.add_line(format!(
+            "{0} (message index {1}, size {2})",
+            struct_definition.name,
+            message_index,
+            struct_definition.estimate_size(configurations)?
+        ));
+        snapshot_file.add_line(format!("    {0:>6} {1:>6} {2:>6}  member", "offset", "size", "index"));
+
+        for member_layout in &layout {
+            snapshot_file.add_line(format!(
+                "    {0:>6} {1:>6} {2:>6}  {3}: {4}",
+                member_layout.offset,
+                member_layout.size,
+                member_layout.member.index.value(),
+                member_layout.member.identifier,
+                rune_type_name(&member_layout.member.data_type)
+            ));
+        }
+
+        snapshot_file.add_newline();
+    }
+
+    snapshot_file.output_file(snapshot_folder)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::{read_to_string, remove_dir_all},
+        path::PathBuf
+    };
+
+    use rune_parser::RuneFileDescription;
+
+    use super::output_layout_snapshot;
+    use crate::test_utilities::{compile_configurations, parse_schema, write_schema};
+
+    const POINT_SCHEMA: &str = "struct Point {\n    X: i16 = 1;\n    Y: i16 = 2\n}\n";
+    const FRAME_SCHEMA: &str = "struct Frame {\n    Flag: u8 = 1;\n    Count: u32 = 2;\n    Origin: Point = 3;\n    Raw: [u8; 3] = 4\n}\n";
+
+    /// Output the layout snapshot of the given Rune files with the given command line arguments
+    fn layout_snapshot(files: &[(&str, &str)], arguments: &[&str]) -> String {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(files);
+        let snapshot_folder: PathBuf = write_schema(&[]);
+        let snapshot_path: PathBuf = snapshot_folder.join("layout.txt");
+
+        output_layout_snapshot(&file_descriptions, &compile_configurations(arguments), snapshot_path.to_str().unwrap()).unwrap();
+
+        let snapshot: String = read_to_string(&snapshot_path).unwrap();
+        remove_dir_all(&snapshot_folder).unwrap();
+
+        snapshot
+    }
+
+    #[test]
+    fn structs_are_listed_in_message_index_order_with_their_layout() {
+        let snapshot: String = layout_snapshot(&[("frame.rune", &format!("{0}\n{1}", POINT_SCHEMA, FRAME_SCHEMA))], &[]);
+
+        assert!(snapshot.contains("C standard:        C23\nArchitecture:      32 bit\nPacked:            false\nSorted:            true\nExplicit padding:  false\nStrict enum width: false\n\n"));
+        assert!(snapshot.ends_with(
+            "Frame (message index 0, size 12)\n    offset   size  index  member\n         0      4      2  Count: u32\n         4      4      3  Origin: Point\n         8      1      1  Flag: u8\n         9      3      4  Raw: [u8; 3]\n\nPoint (message index 1, size 4)\n    offset   size  index  member\n         0      2      1  X: i16\n         2      2      2  Y: i16\n\n"
+        ));
+    }
+
+    #[test]
+    fn layout_options_change_the_snapshot() {
+        let snapshot: String = layout_snapshot(&[("frame.rune", &format!("{0}\n{1}", POINT_SCHEMA, FRAME_SCHEMA))], &["--unsorted"]);
+
+        assert!(snapshot.contains("Sorted:            false\n"));
+        assert!(snapshot.contains(
+            "Frame (message index 0, size 16)\n    offset   size  index  member\n         0      1      1  Flag: u8\n         4      4      2  Count: u32\n         8      4      3  Origin: Point\n        12      3      4  Raw: [u8; 3]\n"
+        ));
+    }
+
+    #[test]
+    fn snapshots_do_not_depend_on_the_file_layout() {
+        let single_file: String = layout_snapshot(&[("frame.rune", &format!("{0}\n{1}", POINT_SCHEMA, FRAME_SCHEMA))], &[]);
+        let split_files: String = layout_snapshot(&[("point.rune", POINT_SCHEMA), ("frame.rune", &format!("include \"point.rune\";\n\n{0}", FRAME_SCHEMA))], &[]);
+
+        assert_eq!(single_file, split_files);
+    }
+}
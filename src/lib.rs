@@ -0,0 +1,1119 @@
+#[macro_use]
+// Declare first because of macros
+pub mod output;
+
+pub mod abi_probe;
+pub mod amalgamation;
+pub mod architecture;
+pub mod c_standard;
+pub mod c_utilities;
+pub mod codec;
+pub mod compile_error;
+pub mod config_header;
+pub mod configuration_file;
+pub mod constants;
+pub mod cpp;
+pub mod depfile;
+pub mod endianness;
+pub mod fuzz;
+pub mod header;
+pub mod include_guard_style;
+pub mod indentation;
+pub mod layout_snapshot;
+pub mod line_ending;
+pub mod log_level;
+pub mod manifest;
+pub mod named_fields;
+pub mod output_file;
+pub mod proto;
+pub mod runic_definitions;
+pub mod runtime_header;
+pub mod schema_diff;
+pub mod selection;
+pub mod source;
+pub mod target_compiler;
+pub mod tlv;
+pub mod typescript;
+pub mod validation;
+pub mod verifier;
+
+#[cfg(test)]
+mod test_utilities;
+
+use std::{
+    collections::HashMap,
+    fs::{create_dir, read_to_string},
+    num::NonZeroUsize,
+    panic::resume_unwind,
+    path::{Component, Path},
+    thread::{ScopedJoinHandle, available_parallelism, scope}
+};
+
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser};
+use rune_parser::{RuneFileDescription, parser_rune_files};
+
+use crate::{
+    abi_probe::output_abi_probe,
+    amalgamation::{AMALGAMATED_NAME, amalgamate},
+    architecture::Architecture,
+    c_standard::CStandard,
+    c_utilities::{CConfigurations, CompileConfigurations, apply_path_namespaces, apply_prefix, banner_comment, link_user_definitions, output_base_name},
+    compile_error::CompilerError,
+    config_header::output_config_header,
+    configuration_file::ConfigurationFile,
+    constants::output_constants,
+    cpp::output_cpp_header,
+    depfile::output_depfile,
+    endianness::Endianness,
+    fuzz::output_fuzz_target,
+    header::output_header,
+    include_guard_style::IncludeGuardStyle,
+    indentation::Indentation,
+    layout_snapshot::output_layout_snapshot,
+    line_ending::LineEnding,
+    log_level::LogLevel,
+    manifest::output_manifest,
+    named_fields::{output_named_fields_header, output_named_fields_source},
+    output::*,
+    output_file::{OutputFile, clean_output_folder, output_archive, print_files, write_file},
+    proto::output_proto,
+    runic_definitions::output_runic_definitions,
+    runtime_header::output_rune_runtime_header,
+    schema_diff::diff_schemas,
+    selection::{declaring_files, filter_structs, is_excluded_file, read_ignore_patterns, remove_ignored_files, select_structs, validate_excluded_files},
+    source::output_source,
+    target_compiler::TargetCompiler,
+    tlv::{output_tlv_header, output_tlv_source},
+    typescript::output_typescript,
+    validation::{
+        validate_all_files_parsed, validate_bitfield_sizes, validate_fixed_point_members, validate_flattened_names, validate_identifiers, validate_inline_members, validate_keywords,
+        validate_member_alignments, validate_name_collisions, validate_prefix, validate_strict, validate_struct_sizes, validate_type_dependencies, validate_verifiers
+    }
+};
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    /// Path of folder where to find Rune files (subfolders will also be searched). Can be passed multiple times if files are spread over multiple different directories.
+    #[arg(long, short = 'i')]
+    input_folder: Vec<String>,
+
+    /// Path of folder where to output source code
+    #[arg(long, short = 'o')]
+    output_folder: Option<String>,
+
+    /// Path of a zip archive to output all source code into, instead of an output folder
+    #[arg(long)]
+    output_archive: Option<String>,
+
+    /// Whether to print the generated header and source of the files declaring the --only structs to standard output, instead of writing any file. Only errors are logged, to standard error - Defaults to false
+    #[arg(long, default_value = "false")]
+    stdout: bool,
+
+    /// Target architecture to optimize for - Defaults to 32 bit
+    #[arg(long, short = 'a', default_value = "32")]
+    architecture: usize,
+
+    /// Byte order of the target. Valid values are native, detected by the C compiler, little and big - Defaults to native
+    #[arg(long, default_value = "native")]
+    endianness: String,
+
+    /// Whether to pack (remove padding) from outputted sources - Defaults to false
+    #[arg(long, short = 'p', default_value = "false")]
+    pack_data: bool,
+
+    /// Whether to pack (remove padding) and size-optimize the outputted parsing metadata - Defaults to false
+    #[arg(long, short = 'm', default_value = "false")]
+    pack_metadata: bool,
+
+    /// Whether to store all Rune data in a specific section. By default no section is declared
+    #[arg(long, short = 'd')]
+    data_section: Option<String>,
+
+    /// Whether to avoid sorting struct field placement to optimize alignment - Defaults to false
+    #[arg(long, short = 'u', default_value = "false")]
+    unsorted: bool,
+
+    /// Whether to store enum fields as their backing integer type, guaranteeing their size at the cost of enum type safety - Defaults to false
+    #[arg(long, default_value = "false")]
+    strict_enum_width: bool,
+
+    /// Whether to check struct sizes against their estimates before C11, using a negative array size trick instead of static_assert - Defaults to false
+    #[arg(long, default_value = "false")]
+    strict_size_checks: bool,
+
+    /// Whether to output memcpy based member accessors for packed structs, avoiding unaligned access faults on strict alignment architectures - Defaults to false
+    #[arg(long, default_value = "false")]
+    safe_packed_access: bool,
+
+    /// Whether to output <struct>_get_<member> getters, returning single values by value and nested structs and arrays by const pointer - Defaults to false
+    #[arg(long, default_value = "false")]
+    emit_getters: bool,
+
+    /// Whether to output precondition checks into generated functions, compiled in when RUNE_DEBUG is defined - Defaults to false
+    #[arg(long, default_value = "false")]
+    emit_debug_checks: bool,
+
+    /// Whether to output tables of struct members by name, along with lookup functions for scripting bridges - Defaults to false
+    #[arg(long, default_value = "false")]
+    emit_named_fields: bool,
+
+    /// Whether to output <struct>_serialize and <struct>_deserialize functions, encoding the members back to back in field index order - Defaults to false
+    #[arg(long, default_value = "false")]
+    emit_codec: bool,
+
+    /// Whether to output <struct>_checksum functions, along with <struct>_compute_verifier and <struct>_verify functions for structs with a verifier field - Defaults to false
+    #[arg(long, default_value = "false")]
+    emit_verifiers: bool,
+
+    /// Whether to output a <enum>_to_string function for every enum, returning the Rune identifier of a value - Defaults to false
+    #[arg(long, default_value = "false")]
+    emit_enum_names: bool,
+
+    /// Whether to output a <file>.rune.hpp header per Rune file, wrapping every struct in a C++17 class with accessors and comparison operators - Defaults to false
+    #[arg(long, default_value = "false")]
+    emit_cpp: bool,
+
+    /// Whether to output struct padding as named reserved members, making the layout explicit and identical across compilers - Defaults to false
+    #[arg(long, default_value = "false")]
+    explicit_padding: bool,
+
+    /// Whether to output the padding of bitfields as an unnamed member, which cannot be accessed - Defaults to false
+    #[arg(long, default_value = "false")]
+    anonymous_bitfield_padding: bool,
+
+    /// Whether to output an abi_probe.c program, printing the actual struct layouts next to the ones estimated by the compiler - Defaults to false
+    #[arg(long, default_value = "false")]
+    emit_abi_probe: bool,
+
+    /// Whether to output a rune_fuzz.c libFuzzer target, feeding arbitrary bytes to the deserialize functions. Requires --emit-codec - Defaults to false
+    #[arg(long, default_value = "false")]
+    emit_fuzz: bool,
+
+    /// Whether to output a rune_config.h header recording the options the code was generated with - Defaults to false
+    #[arg(long, default_value = "false")]
+    emit_config_header: bool,
+
+    /// Whether to output a rune.h header defining the descriptor types, for using the generated code without the Rune library - Defaults to false
+    #[arg(long, default_value = "false")]
+    emit_runtime_header: bool,
+
+    /// Whether to adjust the output towards MISRA C compliance, such as suffixing unsigned literals and avoiding binary literals - Defaults to false
+    #[arg(long, default_value = "false")]
+    misra: bool,
+
+    /// Whether to output length-prefixed TLV descriptors and serialize/deserialize helpers, trading compactness for compatibility between schema versions - Defaults to false
+    #[arg(long, default_value = "false")]
+    tlv_mode: bool,
+
+    /// Whether to encode integer members of 16 bits or more as variable-length integers in TLV mode - Defaults to false
+    #[arg(long, default_value = "false")]
+    varint: bool,
+
+    /// Whether to also output a TypeScript module with the given name, containing interfaces mirroring the structs
+    #[arg(long)]
+    emit_ts: Option<String>,
+
+    /// Whether to also output a proto3 schema with the given name, mapping the structs to messages numbered by field index
+    #[arg(long)]
+    emit_proto: Option<String>,
+
+    /// Whether to only output a single header with the given name, containing the protocol constants as macros
+    #[arg(long)]
+    emit_constants_only: Option<String>,
+
+    /// Path where to output the runic definitions header, relative to the output folder - Defaults to runic_definitions.h
+    #[arg(long, default_value = "runic_definitions.h")]
+    definitions_output: String,
+
+    /// Names of the structs to output, separated by commas. Structs they depend on are output as well - Defaults to all structs
+    #[arg(long, value_delimiter = ',')]
+    only: Vec<String>,
+
+    /// Names of the structs to leave out of the output, separated by commas
+    #[arg(long, value_delimiter = ',')]
+    exclude: Vec<String>,
+
+    /// Glob patterns of Rune files to parse but not output, separated by commas or repeated. Patterns with a '/' match the path relative to the input folder, others only the file name - Defaults to empty
+    #[arg(long, value_delimiter = ',')]
+    exclude_files: Vec<String>,
+
+    /// Whether to output the comments of definitions with doxygen tags, marking their first line as the brief description - Defaults to false
+    #[arg(long, default_value = "false")]
+    doxygen_comments: bool,
+
+    /// Whether to output all files into the output folder itself instead of mirroring the directories of the Rune files, joining the directories into the file names with underscores - Defaults to false
+    #[arg(long, default_value = "false")]
+    flatten: bool,
+
+    /// Struct members to output as an anonymous struct within their parent, written as <Struct>.<Member> and separated by commas. Requires C11
+    #[arg(long, value_delimiter = ',')]
+    inline_members: Vec<String>,
+
+    /// Struct members to align beyond their natural alignment, written as <Struct>.<Member>=<Alignment> and separated by commas. Requires C11
+    #[arg(long, value_delimiter = ',')]
+    align_members: Vec<String>,
+
+    /// Integer struct members holding fixed-point values, written as <Struct>.<Member>=Q<Integer bits>.<Fractional bits> (UQ when unsigned) and separated by commas
+    #[arg(long, value_delimiter = ',')]
+    fixed_point_members: Vec<String>,
+
+    /// Whether to prefix the type names of each file with its directory, e.g. Header in net/ becomes net_header_t - Defaults to false
+    #[arg(long, default_value = "false")]
+    namespace_from_path: bool,
+
+    /// Prefix to add in front of every type name and header guard, e.g. acme makes Header acme_header_t - Defaults to None
+    #[arg(long)]
+    prefix: Option<String>,
+
+    /// Whether to output a single rune_all.h header and rune_all.c source for all Rune files, instead of one of each per Rune file - Defaults to false
+    #[arg(long, default_value = "false")]
+    amalgamate: bool,
+
+    /// Whether to leave out the members added by extension definitions, outputting the definitions as originally declared - Defaults to false
+    #[arg(long, default_value = "false")]
+    ignore_extensions: bool,
+
+    /// Whether to reject any schema whose output would rely on implementation defined C behavior - Defaults to false
+    #[arg(long, default_value = "false")]
+    strict: bool,
+
+    /// Whether to omit the struct descriptors, only outputting the data structures and their initializers - Defaults to false
+    #[arg(long, default_value = "false")]
+    no_descriptors: bool,
+
+    /// Whether to omit the verifier parsing data from descriptors. Only valid if no struct declares a verifier field - Defaults to false
+    #[arg(long, default_value = "false")]
+    no_verifier_support: bool,
+
+    /// Whether the program should avoid printing any output at all
+    #[arg(long, short = 's', default_value = "false")]
+    silent: bool,
+
+    /// Specifies which C standard the output source should comply with, either an ISO standard (e.g. C11) or its GNU dialect (e.g. GNU11) - Defaults to C23
+    #[arg(long, short = 'c', default_value = "C23")]
+    c_standard: String,
+
+    /// Whether the output may use GNU extensions such as __attribute__. Passing false makes the ISO standards output only what they allow - Defaults to true
+    #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
+    gnu_extensions: bool,
+
+    /// Specifies which compiler the output source is built with, as MSVC packs structs with #pragma pack instead of attributes. Valid values are gcc, clang and msvc - Defaults to gcc
+    #[arg(long, default_value = "gcc")]
+    compiler: String,
+
+    /// How headers are guarded against multiple inclusion. Valid values are ifndef and pragma-once - Defaults to ifndef
+    #[arg(long, default_value = "ifndef")]
+    include_guard_style: String,
+
+    /// Indentation of the generated code. Valid values are tabs, or a number of spaces - Defaults to 4
+    #[arg(long, default_value = "4")]
+    indent: String,
+
+    /// Line endings of the generated files. Valid values are lf and crlf - Defaults to lf
+    #[arg(long, default_value = "lf")]
+    line_endings: String,
+
+    /// Most verbose kind of message to print. Valid values are error, warn, info and debug - Defaults to info
+    #[arg(long, default_value = "info")]
+    log_level: String,
+
+    /// Whether to run the compiler in debug mode, which has significantly increases the number of output messages
+    #[arg(long, default_value = "false")]
+    debug: bool,
+
+    /// Path of folder with an older version of the Rune files. Instead of outputting source code, the changes from it are reported, failing if any breaks wire compatibility
+    #[arg(long)]
+    diff_schema: Option<String>,
+
+    /// Path of a text file whose contents are output as a comment block at the top of every generated file
+    #[arg(long)]
+    banner_file: Option<String>,
+
+    /// Whether to output the banner file contents verbatim, as they already contain their own comment markers - Defaults to false
+    #[arg(long, default_value = "false")]
+    banner_verbatim: bool,
+
+    /// Path of a Makefile syntax dependency file to output, listing the generated files and the Rune files they depend on
+    #[arg(long)]
+    depfile: Option<String>,
+
+    /// Path of a layout snapshot to output, recording the estimated layout of every struct so layout changes show up in diffs
+    #[arg(long)]
+    emit_layout_snapshot: Option<String>,
+
+    /// Path of a JSON manifest to output, listing every generated file, the Rune files it was generated from and the definitions it contains - Defaults to None
+    #[arg(long)]
+    manifest: Option<String>,
+
+    /// Whether to remove generated files in the output folder which this run does not output, such as those of deleted Rune files - Defaults to false
+    #[arg(long, default_value = "false")]
+    clean: bool,
+
+    /// Path of a TOML file with project configurations. Arguments passed on the command line take precedence over the file
+    #[arg(long)]
+    config: Option<String>
+}
+
+impl Args {
+    /// Compile configurations from the parsed arguments, with the banner comment read from the banner file, if any
+    fn compile_configurations(&self, banner_text: Option<String>) -> Result<CompileConfigurations, CompilerError> {
+        Ok(CompileConfigurations {
+            architecture:               Architecture::from_value(self.architecture)?,
+            endianness:                 Endianness::from_string(&self.endianness)?,
+            c_standard:                 CStandard::from_string(&self.c_standard, self.gnu_extensions)?,
+            compiler:                   TargetCompiler::from_string(&self.compiler)?,
+            include_guard_style:        IncludeGuardStyle::from_string(&self.include_guard_style)?,
+            indentation:                Indentation::from_string(&self.indent)?,
+            line_ending:                LineEnding::from_string(&self.line_endings)?,
+            pack_data:                  self.pack_data,
+            pack_metadata:              self.pack_metadata,
+            section:                    self.data_section.clone(),
+            sort:                       !self.unsorted,
+            verifier_support:           !self.no_verifier_support,
+            descriptors:                !self.no_descriptors,
+            strict_enum_width:          self.strict_enum_width,
+            strict_size_checks:         self.strict_size_checks,
+            safe_packed_access:         self.safe_packed_access,
+            getters:                    self.emit_getters,
+            debug_checks:               self.emit_debug_checks,
+            named_fields:               self.emit_named_fields,
+            codec:                      self.emit_codec,
+            verifiers:                  self.emit_verifiers,
+            enum_names:                 self.emit_enum_names,
+            cpp:                        self.emit_cpp,
+            explicit_padding:           self.explicit_padding,
+            anonymous_bitfield_padding: self.anonymous_bitfield_padding,
+            misra:                      self.misra,
+            banner:                     banner_text,
+            abi_probe:                  self.emit_abi_probe,
+            fuzz:                       self.emit_fuzz,
+            tlv:                        self.tlv_mode,
+            varint:                     self.varint,
+            config_header:              self.emit_config_header,
+            runtime_header:             self.emit_runtime_header,
+            definitions_output:         self.definitions_output.clone(),
+            only:                       self.only.clone(),
+            exclude:                    self.exclude.clone(),
+            exclude_files:              self.exclude_files.clone(),
+            doxygen_comments:           self.doxygen_comments,
+            flatten:                    self.flatten,
+            inline_members:             self.inline_members.clone(),
+            member_alignments:          self.align_members.clone(),
+            fixed_point_members:        self.fixed_point_members.clone(),
+            namespace_from_path:        self.namespace_from_path,
+            prefix:                     self.prefix.clone(),
+            amalgamate:                 self.amalgamate,
+            typescript:                 self.emit_ts.clone(),
+            proto:                      self.emit_proto.clone(),
+            constants_only:             self.emit_constants_only.clone(),
+            struct_sizes:               HashMap::new()
+        })
+    }
+
+    /// Most verbose kind of message to print. Debug mode is an alias for the debug log level
+    fn log_level(&self) -> Result<LogLevel, CompilerError> {
+        match (self.stdout, self.debug) {
+            // Logs are printed to standard output, so they would mix with the printed files
+            (true, _) => Ok(LogLevel::Error),
+            (false, true) => Ok(LogLevel::Debug),
+            (false, false) => LogLevel::from_string(&self.log_level)
+        }
+    }
+
+    /// Check the arguments of stdout mode, in which only the files declaring the requested structs are printed, and nothing is written
+    fn validate_stdout_mode(&self, configurations: &CompileConfigurations) -> Result<(), CompilerError> {
+        if !self.stdout {
+            return Ok(());
+        }
+
+        if configurations.only.is_empty() {
+            error!("--stdout requires the structs to print to be passed with --only");
+            return Err(CompilerError::ConfigurationError);
+        }
+
+        if self.output_archive.is_some() || self.depfile.is_some() || self.manifest.is_some() || self.clean {
+            error!("--stdout cannot be combined with --output-archive, --depfile, --manifest or --clean, as no file is written");
+            return Err(CompilerError::ConfigurationError);
+        }
+
+        Ok(())
+    }
+}
+
+/// Hook invoked with the path relative to the output folder and the contents of each generated file, allowing custom transformations
+/// such as license headers, extra macros or reformatting
+pub type PostProcessingHook = fn(path: &str, contents: &mut String);
+
+/// Default post-processing hook, leaving the generated files unchanged
+pub fn no_post_processing(_path: &str, _contents: &mut String) {}
+
+/// Run the compiler with the arguments passed on the command line
+pub fn run() -> Result<(), CompilerError> {
+    run_with_hook(no_post_processing)
+}
+
+/// Run the compiler with the arguments passed on the command line, passing every generated file through the post-processing hook
+/// before it is written
+pub fn run_with_hook(hook: PostProcessingHook) -> Result<(), CompilerError> {
+    // Parse arguments
+    // ————————————————
+
+    let matches: ArgMatches = Args::command().get_matches();
+    let mut args: Args = match Args::from_arg_matches(&matches) {
+        Ok(args) => args,
+        Err(error) => error.exit()
+    };
+
+    // Apply configuration file values for arguments not passed on the command line
+    if let Some(config_path) = &args.config {
+        ConfigurationFile::read(config_path)?.apply(&mut args, &matches);
+    }
+
+    // Disable print output if silent argument was passed
+    if args.silent {
+        enable_silent();
+    };
+
+    set_log_level(args.log_level()?);
+
+    let input_paths: Vec<&Path> = {
+        let mut input_paths = Vec::with_capacity(0x10);
+
+        for folder in &args.input_folder {
+            let path = Path::new(folder.as_str());
+
+            // If input folder does not exist, return an error
+            if !path.exists() {
+                error!("Input path \"{0}\" invalid!", folder);
+                return Err(CompilerError::InvalidInputPath);
+            }
+
+            input_paths.push(path);
+        }
+
+        input_paths
+    };
+    let banner_text: Option<String> = match &args.banner_file {
+        None => None,
+        Some(banner_path) => match read_to_string(banner_path) {
+            Ok(text) => Some(banner_comment(&text, args.banner_verbatim)?),
+            Err(error) => {
+                error!("Could not read banner file \"{0}\". Got error {1}", banner_path, error);
+                return Err(CompilerError::FileSystemError(error));
+            }
+        }
+    };
+
+    let configurations: CompileConfigurations = args.compile_configurations(banner_text)?;
+
+    // Validate arguments
+    // ———————————————————
+
+    if configurations.safe_packed_access && !configurations.pack_data {
+        warning!("--safe-packed-access has no effect without --pack-data, as struct members are then already aligned");
+    }
+
+    if configurations.pack_metadata && !configurations.allows_attributes() {
+        warning!("--pack-metadata has no effect with --compiler msvc or --gnu-extensions false, as the metadata structs are declared by the Rune library");
+    }
+
+    // Only GNU attributes and MSVC declarations can place data in a section, as ISO C has no way to do so
+    if configurations.section.is_some() && !configurations.allows_attributes() && configurations.compiler.allows_attributes() {
+        error!("--data-section cannot be combined with --gnu-extensions false, as ISO C has no way to place data in a section");
+        return Err(CompilerError::ConfigurationError);
+    }
+
+    if configurations.cpp && !configurations.codec {
+        warning!("--emit-cpp outputs no serialize and deserialize methods without --emit-codec, as they call the C functions");
+    }
+
+    // The fuzz target calls the deserialize functions, which only exist with the codec
+    if configurations.fuzz && !configurations.codec {
+        error!("--emit-fuzz requires --emit-codec, as the fuzz target calls the generated deserialize functions");
+        return Err(CompilerError::ConfigurationError);
+    }
+
+    if configurations.amalgamate && !configurations.exclude_files.is_empty() {
+        error!("--exclude-files cannot be combined with --amalgamate, as the amalgamated files cannot include the headers of the excluded files");
+        return Err(CompilerError::ConfigurationError);
+    }
+
+    if configurations.varint && !configurations.tlv {
+        warning!("--varint has no effect without --tlv-mode, as members are otherwise not encoded");
+    }
+
+    validate_definitions_output(&configurations)?;
+
+    // The parser always searches the input folders recursively. Its flags control whether extensions are appended to the definitions
+    // they extend, and whether it prints any output of its own
+    let append_extensions: bool = !args.ignore_extensions;
+
+    let ignore_patterns: Vec<Vec<String>> = input_paths.iter().map(|input_path| read_ignore_patterns(input_path)).collect::<Result<_, _>>()?;
+
+    let mut definitions_list: Vec<RuneFileDescription> = match parser_rune_files(&input_paths, append_extensions, args.silent || args.stdout) {
+        Ok(value) => value,
+        Err(error) => {
+            error!("Could not parser Rune files! Got error {0:?}", error);
+            return Err(CompilerError::ParsingError(error));
+        }
+    };
+
+    validate_all_files_parsed(&definitions_list, &input_paths, &ignore_patterns)?;
+    remove_ignored_files(&mut definitions_list, &input_paths, &ignore_patterns)?;
+    validate_excluded_files(&definitions_list, &configurations);
+    validate_prefix(&configurations)?;
+
+    if configurations.namespace_from_path {
+        apply_path_namespaces(&mut definitions_list);
+    }
+
+    if let Some(prefix) = &configurations.prefix {
+        apply_prefix(&mut definitions_list, prefix);
+    }
+
+    link_user_definitions(&mut definitions_list)?;
+
+    validate_flattened_names(&definitions_list, &configurations)?;
+    validate_identifiers(&definitions_list)?;
+    validate_name_collisions(&definitions_list)?;
+    validate_keywords(&definitions_list, &configurations)?;
+    validate_bitfield_sizes(&definitions_list)?;
+    validate_type_dependencies(&definitions_list)?;
+    validate_struct_sizes(&definitions_list, &configurations)?;
+    validate_inline_members(&definitions_list, &configurations)?;
+    validate_member_alignments(&definitions_list, &configurations)?;
+    validate_fixed_point_members(&definitions_list, &configurations)?;
+    validate_verifiers(&definitions_list, &configurations)?;
+
+    if args.strict {
+        validate_strict(&definitions_list, &configurations)?;
+    }
+
+    // Compare schemas
+    // ————————————————
+
+    if let Some(old_folder) = &args.diff_schema {
+        let old_path: &Path = Path::new(old_folder.as_str());
+
+        if !old_path.exists() {
+            error!("Old schema path \"{0}\" invalid!", old_folder);
+            return Err(CompilerError::InvalidInputPath);
+        }
+
+        let mut old_definitions_list: Vec<RuneFileDescription> = match parser_rune_files(&[old_path], append_extensions, true) {
+            Ok(value) => value,
+            Err(error) => {
+                error!("Could not parse old Rune files! Got error {0:?}", error);
+                return Err(CompilerError::ParsingError(error));
+            }
+        };
+
+        if configurations.namespace_from_path {
+            apply_path_namespaces(&mut old_definitions_list);
+        }
+
+        if let Some(prefix) = &configurations.prefix {
+            apply_prefix(&mut old_definitions_list, prefix);
+        }
+
+        link_user_definitions(&mut old_definitions_list)?;
+
+        return diff_schemas(&old_definitions_list, &definitions_list, &configurations);
+    }
+
+    args.validate_stdout_mode(&configurations)?;
+
+    let output_folder: String = match (args.output_folder, &args.output_archive) {
+        (Some(output_folder), None) => output_folder,
+        (None, Some(_)) => String::from("."),
+        (Some(_), Some(_)) => {
+            error!("Both an output folder and an output archive were given! Pass only one of --output-folder and --output-archive");
+            return Err(CompilerError::InvalidArgument);
+        },
+        (None, None) if args.stdout => String::from("."),
+        (None, None) => {
+            error!("No output folder given! Pass one with --output-folder or set output_folder in the configuration file");
+            return Err(CompilerError::InvalidArgument);
+        }
+    };
+    let output_path: &Path = Path::new(output_folder.as_str());
+
+    // If output folder does exist, create it
+    if args.output_archive.is_none()
+        && !args.stdout
+        && !output_path.is_dir()
+        && let Err(error) = create_dir(output_path)
+    {
+        error!("Cannot create directory {0:?}. Got error {1}", output_path, error);
+        return Err(CompilerError::FileSystemError(error));
+    }
+
+    // Create layout snapshot
+    // ————————————————————————
+
+    if let Some(snapshot_path) = &args.emit_layout_snapshot {
+        info!("Outputting layout snapshot to {0}", snapshot_path);
+        output_layout_snapshot(&definitions_list, &configurations, snapshot_path)?;
+    }
+
+    // Create source files
+    // ————————————————————
+
+    let output_files: Vec<(String, String)> = generate_to_memory_with_hook(&definitions_list, &configurations, hook)?;
+
+    if args.stdout {
+        let printed_names: Vec<String> = printed_file_names(&definitions_list, &configurations);
+
+        return print_files(&output_files, &printed_names);
+    }
+
+    if let Some(manifest_path) = &args.manifest {
+        info!("Outputting manifest to {0}", manifest_path);
+        output_manifest(&output_files, &definitions_list, &configurations, manifest_path)?;
+    }
+
+    // Create dependency file
+    // ————————————————————————
+
+    if let Some(depfile_path) = &args.depfile {
+        // In archive mode all generated files are stored in the archive, so none is written to the output folder
+        let folder_files: &[(String, String)] = match args.output_archive {
+            Some(_) => &[],
+            None => &output_files
+        };
+        let extra_targets: Vec<&str> = [args.output_archive.as_deref(), args.manifest.as_deref(), args.emit_layout_snapshot.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect();
+
+        output_depfile(&definitions_list, &input_paths, output_path, folder_files, &extra_targets, &configurations, depfile_path)?;
+    }
+
+    match &args.output_archive {
+        Some(archive_path) => {
+            info!("Outputting archive {0}", archive_path);
+            output_archive(&output_files, Path::new(archive_path.as_str()))?;
+        },
+        None => {
+            if args.clean {
+                clean_output_folder(output_path, &output_files)?;
+            }
+
+            for (name, contents) in &output_files {
+                write_file(output_path, name, contents)?;
+            }
+        }
+    }
+
+    info!("Rune C compiler is done!");
+    Ok(())
+}
+
+/// Check that the definitions header is placed inside of the output folder, as it is included relative to it
+fn validate_definitions_output(configurations: &CompileConfigurations) -> Result<(), CompilerError> {
+    let definitions_path: &Path = Path::new(&configurations.definitions_output);
+
+    if definitions_path.is_absolute() || definitions_path.components().any(|component| component == Component::ParentDir) || !configurations.definitions_output.ends_with(".h") {
+        error!(
+            "Definitions output \"{0}\" must be a header path relative to, and within, the output folder",
+            configurations.definitions_output
+        );
+        return Err(CompilerError::InvalidArgument);
+    }
+
+    Ok(())
+}
+
+/// Get the names of the files printed in stdout mode: the header, source and C++ wrapper of every file declaring a struct passed with
+/// --only, or the single output file when amalgamating or only outputting the constants
+fn printed_file_names(file_descriptions: &[RuneFileDescription], configurations: &CompileConfigurations) -> Vec<String> {
+    if let Some(file_name) = &configurations.constants_only {
+        return vec![file_name.clone()];
+    }
+
+    let printed_bases: Vec<String> = match configurations.amalgamate {
+        true => vec![String::from(AMALGAMATED_NAME)],
+        false => declaring_files(file_descriptions, &configurations.only)
+            .iter()
+            .map(|file| output_base_name(file, configurations))
+            .collect()
+    };
+
+    let mut printed_names: Vec<String> = Vec::with_capacity(printed_bases.len() * 3);
+
+    for base in &printed_bases {
+        printed_names.push(format!("{0}.{1}", base, configurations.output_extension("h")));
+        printed_names.push(format!("{0}.{1}", base, configurations.output_extension("c")));
+
+        if configurations.cpp {
+            printed_names.push(format!("{0}.{1}", base, configurations.output_extension("hpp")));
+        }
+    }
+
+    printed_names
+}
+
+/// Generate all output files without touching the filesystem, as pairs of their path relative to the output folder and their contents
+pub fn generate_to_memory(file_descriptions: &Vec<RuneFileDescription>, configurations: &CompileConfigurations) -> Result<Vec<(String, String)>, CompilerError> {
+    generate_to_memory_with_hook(file_descriptions, configurations, no_post_processing)
+}
+
+/// Generate all output files like generate_to_memory, then pass each of them through the post-processing hook in output order. The
+/// hook runs once all files are generated, and before any of them is written or printed
+pub fn generate_to_memory_with_hook(file_descriptions: &Vec<RuneFileDescription>, configurations: &CompileConfigurations, hook: PostProcessingHook) -> Result<Vec<(String, String)>, CompilerError> {
+    let output_files: Vec<OutputFile> = generate_c_files(file_descriptions, configurations)?;
+
+    let mut output_files: Vec<(String, String)> = output_files.into_iter().map(|output_file| (output_file.name().to_string(), output_file.into_contents())).collect();
+
+    for (name, contents) in &mut output_files {
+        hook(name, contents);
+    }
+
+    Ok(output_files)
+}
+
+/// Output the header, source and C++ wrapper of a single Rune file
+fn output_rune_file(file: &RuneFileDescription, file_descriptions: &Vec<RuneFileDescription>, configurations: &CConfigurations) -> Result<Vec<OutputFile>, CompilerError> {
+    let mut output_files: Vec<OutputFile> = Vec::with_capacity(3);
+
+    // Create header file
+    output_files.push(output_header(file, file_descriptions, configurations)?);
+
+    // Create source file
+    output_files.push(output_source(file, configurations)?);
+
+    // Create C++ wrapper header
+    if configurations.compiler_configurations.cpp {
+        output_files.push(output_cpp_header(file, file_descriptions, configurations)?);
+    }
+
+    Ok(output_files)
+}
+
+/// Output the files of every given Rune file. Each Rune file is output independently of the others, so they are split in chunks
+/// output on separate threads. The output files keep the order of the Rune files, and the error of the first failing file is returned
+fn output_rune_files(files: &[&RuneFileDescription], file_descriptions: &Vec<RuneFileDescription>, configurations: &CConfigurations) -> Result<Vec<OutputFile>, CompilerError> {
+    let thread_count: usize = available_parallelism().map(NonZeroUsize::get).unwrap_or(1);
+
+    output_rune_files_on_threads(files, file_descriptions, configurations, thread_count)
+}
+
+/// Output the files of every given Rune file, split over at most the given amount of threads
+fn output_rune_files_on_threads(
+    files: &[&RuneFileDescription],
+    file_descriptions: &Vec<RuneFileDescription>,
+    configurations: &CConfigurations,
+    thread_count: usize
+) -> Result<Vec<OutputFile>, CompilerError> {
+    let thread_count: usize = thread_count.min(files.len()).max(1);
+    let chunk_size: usize = files.len().div_ceil(thread_count).max(1);
+
+    let chunk_results: Vec<Result<Vec<OutputFile>, CompilerError>> = scope(|scope| {
+        let handles: Vec<ScopedJoinHandle<Result<Vec<OutputFile>, CompilerError>>> = files
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    let mut output_files: Vec<OutputFile> = Vec::with_capacity(chunk.len() * 3);
+
+                    for file in chunk {
+                        output_files.extend(output_rune_file(file, file_descriptions, configurations)?);
+                    }
+
+                    Ok(output_files)
+                })
+            })
+            .collect();
+
+        handles.into_iter().map(|handle| handle.join().unwrap_or_else(|panic| resume_unwind(panic))).collect()
+    });
+
+    let mut output_files: Vec<OutputFile> = Vec::with_capacity(files.len() * 3);
+
+    for chunk_result in chunk_results {
+        output_files.extend(chunk_result?);
+    }
+
+    Ok(output_files)
+}
+
+pub fn generate_c_files(file_descriptions: &Vec<RuneFileDescription>, configurations: &CompileConfigurations) -> Result<Vec<OutputFile>, CompilerError> {
+    // Only output the selected structs, and the structs they depend on
+    let selection: Vec<String> = select_structs(file_descriptions, configurations)?;
+    let selected_descriptions: Vec<RuneFileDescription> = filter_structs(file_descriptions, &selection);
+
+    // Output all files as one, with the definitions of included files first
+    let selected_descriptions: Vec<RuneFileDescription> = match configurations.amalgamate {
+        true => vec![amalgamate(&selected_descriptions)],
+        false => selected_descriptions
+    };
+
+    let c_configurations: CConfigurations = CConfigurations::parse(&selected_descriptions, configurations)?;
+
+    // Only output the protocol constants if requested. Message indexes are taken from all structs, to remain the same on every device
+    if let Some(file_name) = &configurations.constants_only {
+        info!("Outputting protocol constants to {0}", file_name);
+        return Ok(vec![output_constants(file_descriptions, &c_configurations, file_name, &selection)?]);
+    }
+
+    let mut output_files: Vec<OutputFile> = Vec::with_capacity(selected_descriptions.len() * 2 + 1);
+
+    // Create runic definitions file
+    info!("Outputting runic definitions");
+    output_files.push(output_runic_definitions(&c_configurations)?);
+
+    // Create source and header files matching the Rune files
+    match configurations.amalgamate {
+        true => info!("Outputting amalgamated header and source"),
+        false => info!("Outputting headers and sources for:")
+    }
+    let mut output_descriptions: Vec<&RuneFileDescription> = Vec::with_capacity(selected_descriptions.len());
+
+    for file in &selected_descriptions {
+        // Excluded files are still parsed and linked, so the other files can use their definitions
+        if is_excluded_file(file, configurations) {
+            info!("    {0}{1}.rune (excluded, skipped)", file.relative_path, file.name);
+            continue;
+        }
+
+        if !configurations.amalgamate {
+            info!("    {0}{1}.rune", file.relative_path, file.name);
+        }
+
+        output_descriptions.push(file);
+    }
+
+    output_files.extend(output_rune_files(&output_descriptions, &selected_descriptions, &c_configurations)?);
+
+    // Create TLV serializer
+    if configurations.tlv {
+        info!("Outputting TLV serializer");
+        output_files.push(output_tlv_header(&c_configurations));
+        output_files.push(output_tlv_source(&c_configurations));
+    }
+
+    // Create named field lookup
+    if configurations.named_fields {
+        info!("Outputting named field lookup");
+        output_files.push(output_named_fields_header(&c_configurations));
+        output_files.push(output_named_fields_source(&selected_descriptions, &c_configurations));
+    }
+
+    // Create TypeScript definitions
+    if let Some(file_name) = &configurations.typescript {
+        info!("Outputting TypeScript definitions to {0}", file_name);
+        output_files.push(output_typescript(&selected_descriptions, &c_configurations, file_name)?);
+    }
+
+    // Create proto schema
+    if let Some(file_name) = &configurations.proto {
+        info!("Outputting proto schema to {0}", file_name);
+        output_files.push(output_proto(&selected_descriptions, &c_configurations, file_name)?);
+    }
+
+    // Create layout probe
+    if configurations.abi_probe {
+        info!("Outputting ABI probe");
+        output_files.push(output_abi_probe(&selected_descriptions, &c_configurations)?);
+    }
+
+    // Create fuzz target
+    if configurations.fuzz {
+        info!("Outputting fuzz target");
+        output_files.push(output_fuzz_target(&selected_descriptions, &c_configurations, file_descriptions, &selection)?);
+    }
+
+    // Create configuration header
+    if configurations.config_header {
+        info!("Outputting configuration header");
+        output_files.push(output_config_header(&c_configurations));
+    }
+
+    // Create runtime header
+    if configurations.runtime_header {
+        info!("Outputting runtime header");
+        output_files.push(output_rune_runtime_header(&c_configurations)?);
+    }
+
+    Ok(output_files)
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+    use rune_parser::{
+        RuneFileDescription,
+        types::{BitSize, FieldType, Primitive}
+    };
+
+    use crate::{
+        Args,
+        c_utilities::{CConfigurations, CompileConfigurations},
+        compile_error::CompilerError,
+        generate_to_memory,
+        log_level::LogLevel,
+        output_file::OutputFile,
+        output_rune_files_on_threads, printed_file_names,
+        test_utilities::{compile_configurations, parse_schema},
+        validate_definitions_output
+    };
+
+    const SCHEMA: [(&str, &str); 2] = [("net/frame.rune", "struct Frame {\n    Value: u8 = 1\n}\n"), ("status.rune", "struct Status {\n    Code: u8 = 1\n}\n")];
+
+    /// Validate the stdout mode of the given command line arguments
+    fn validate_stdout_arguments(arguments: &[&str]) -> Result<(), CompilerError> {
+        let args: Args = Args::parse_from(["rune_c_compiler", "--stdout"].iter().chain(arguments));
+        let configurations: CompileConfigurations = args.compile_configurations(None).unwrap();
+
+        args.validate_stdout_mode(&configurations)
+    }
+
+    #[test]
+    fn stdout_mode_requires_the_printed_structs() {
+        assert!(matches!(validate_stdout_arguments(&[]), Err(CompilerError::ConfigurationError)));
+        assert!(validate_stdout_arguments(&["--only", "Frame"]).is_ok());
+    }
+
+    #[test]
+    fn stdout_mode_writes_no_files() {
+        for arguments in [["--manifest", "manifest.json"], ["--depfile", "rune.d"], ["--output-archive", "rune.zip"]] {
+            assert!(matches!(
+                validate_stdout_arguments(&[&["--only", "Frame"], &arguments[..]].concat()),
+                Err(CompilerError::ConfigurationError)
+            ));
+        }
+        assert!(matches!(validate_stdout_arguments(&["--only", "Frame", "--clean"]), Err(CompilerError::ConfigurationError)));
+    }
+
+    #[test]
+    fn only_the_files_declaring_the_printed_structs_are_printed() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&SCHEMA);
+
+        assert_eq!(
+            printed_file_names(&file_descriptions, &compile_configurations(&["--only", "Frame"])),
+            ["net/frame.rune.h", "net/frame.rune.c"]
+        );
+        assert_eq!(
+            printed_file_names(&file_descriptions, &compile_configurations(&["--only", "Status", "--emit-cpp"])),
+            ["status.rune.h", "status.rune.c", "status.rune.hpp"]
+        );
+    }
+
+    #[test]
+    fn single_output_files_are_printed_on_their_own() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&SCHEMA);
+
+        assert_eq!(
+            printed_file_names(&file_descriptions, &compile_configurations(&["--only", "Frame", "--amalgamate"])),
+            ["rune_all.h", "rune_all.c"]
+        );
+        assert_eq!(
+            printed_file_names(&file_descriptions, &compile_configurations(&["--only", "Frame", "--emit-constants-only", "constants.h"])),
+            ["constants.h"]
+        );
+    }
+
+    #[test]
+    fn includes_follow_the_definitions_output() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&SCHEMA);
+        let file = |arguments: &[&str], name: &str| -> String {
+            let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &compile_configurations(&[&["--definitions-output", "include/rune_defs.h"], arguments].concat())).unwrap();
+
+            assert!(output_files.iter().all(|(output_name, _)| output_name != "runic_definitions.h"));
+            output_files.into_iter().find(|(output_name, _)| output_name == name).expect("File should be generated").1
+        };
+
+        assert!(file(&[], "include/rune_defs.h").contains("#define RUNE_VERIFIER_SUPPORT"));
+        assert!(file(&["--emit-runtime-header"], "rune.h").contains("#include \"include/rune_defs.h\""));
+
+        // Without descriptors, headers include the definitions directly instead of the Rune library
+        let header: String = file(&["--no-descriptors"], "status.rune.h");
+        assert!(header.contains("#include \"include/rune_defs.h\""));
+        assert!(!header.contains("runic_definitions.h"));
+    }
+
+    #[test]
+    fn definitions_output_must_be_within_the_output_folder() {
+        assert!(validate_definitions_output(&compile_configurations(&["--definitions-output", "include/rune_defs.h"])).is_ok());
+
+        for definitions_output in ["/usr/include/rune_defs.h", "../rune_defs.h", "include/../../rune_defs.h", "rune_defs.txt"] {
+            assert!(
+                matches!(
+                    validate_definitions_output(&compile_configurations(&["--definitions-output", definitions_output])),
+                    Err(CompilerError::InvalidArgument)
+                ),
+                "{0} should be rejected",
+                definitions_output
+            );
+        }
+    }
+
+    #[test]
+    fn log_level_is_overridden_by_debug_and_stdout_modes() {
+        let log_level = |arguments: &[&str]| -> Result<LogLevel, CompilerError> { Args::parse_from(["rune_c_compiler"].iter().chain(arguments)).log_level() };
+
+        assert_eq!(log_level(&[]).unwrap(), LogLevel::Info);
+        assert_eq!(log_level(&["--log-level", "warn"]).unwrap(), LogLevel::Warning);
+        assert_eq!(log_level(&["--log-level", "warn", "--debug"]).unwrap(), LogLevel::Debug);
+        assert_eq!(log_level(&["--log-level", "debug", "--stdout"]).unwrap(), LogLevel::Error);
+        assert_eq!(log_level(&["--debug", "--stdout"]).unwrap(), LogLevel::Error);
+        assert!(matches!(log_level(&["--log-level", "verbose"]), Err(CompilerError::InvalidArgument)));
+    }
+
+    /// Parse a schema of many independent Rune files, so they are output in several chunks of several files each
+    fn parse_many_files(file_count: usize) -> (Vec<RuneFileDescription>, CConfigurations) {
+        let files: Vec<(String, String)> = (0..file_count)
+            .map(|index| {
+                (
+                    format!("file_{0:02}.rune", index),
+                    format!(
+                        "bitfield Flags{0}: u8 {{\n    Enabled: u1 = 0;\n    Level: u3 = 1\n}}\n\nstruct Message{0} {{\n    Value: u8 = 1\n}}\n",
+                        index
+                    )
+                )
+            })
+            .collect();
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&files.iter().map(|(name, contents)| (name.as_str(), contents.as_str())).collect::<Vec<(&str, &str)>>());
+        let configurations: CConfigurations = CConfigurations::parse(&file_descriptions, &compile_configurations(&[])).unwrap();
+
+        (file_descriptions, configurations)
+    }
+
+    #[test]
+    fn parallel_output_keeps_the_order_of_the_rune_files() {
+        let (mut file_descriptions, configurations): (Vec<RuneFileDescription>, CConfigurations) = parse_many_files(24);
+
+        // The files are output in the order they are given, whichever order they were found in
+        file_descriptions.reverse();
+
+        let files: Vec<&RuneFileDescription> = file_descriptions.iter().collect();
+        let output_files: Vec<OutputFile> = output_rune_files_on_threads(&files, &file_descriptions, &configurations, 4).unwrap();
+
+        let expected_names: Vec<String> = file_descriptions.iter().flat_map(|file| [format!("{0}.rune.h", file.name), format!("{0}.rune.c", file.name)]).collect();
+        assert_eq!(output_files.iter().map(|file| file.name().to_string()).collect::<Vec<String>>(), expected_names);
+    }
+
+    #[test]
+    fn parallel_output_returns_the_error_of_the_first_failing_file() {
+        let (mut file_descriptions, mut configurations): (Vec<RuneFileDescription>, CConfigurations) = parse_many_files(24);
+        file_descriptions.sort_by(|a, b| a.name.cmp(&b.name));
+
+        // A file of the first chunk fails with a malformed bitfield, which the parser would reject, and one of the last chunk with a 64 bit
+        // integer before C99
+        file_descriptions[3].definitions.bitfields[0].members[1].size = BitSize::Unsigned(8);
+        file_descriptions[20].definitions.structs[0].members[0].data_type = FieldType::Primitive(Primitive::U64);
+        configurations.compiler_configurations.c_standard = compile_configurations(&["--c-standard", "C89"]).c_standard;
+
+        let files: Vec<&RuneFileDescription> = file_descriptions.iter().collect();
+        assert!(matches!(
+            output_rune_files_on_threads(&files, &file_descriptions, &configurations, 4),
+            Err(CompilerError::MalformedSource)
+        ));
+
+        let late_files: Vec<&RuneFileDescription> = file_descriptions[4..].iter().collect();
+        assert!(matches!(
+            output_rune_files_on_threads(&late_files, &file_descriptions, &configurations, 4),
+            Err(CompilerError::SourceAndCStandardMismatch)
+        ));
+    }
+}
@@ -0,0 +1,31 @@
+use crate::{compile_error::CompilerError, output::*};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LineEnding {
+    Lf,
+    Crlf
+}
+
+impl LineEnding {
+    pub fn from_string(string: &str) -> Result<LineEnding, CompilerError> {
+        match string {
+            "lf" => Ok(LineEnding::Lf),
+            "crlf" => Ok(LineEnding::Crlf),
+            _ => {
+                error!("Invalid line ending passed. Got {0}, and valid values are: {1}", string, LineEnding::valid_values());
+                Err(CompilerError::InvalidArgument)
+            }
+        }
+    }
+
+    fn valid_values() -> String {
+        String::from("lf, crlf")
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n"
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use crate::{compile_error::CompilerError, output::*};
+
+/// Most verbose kind of message printed. Each level also prints the messages of the levels before it
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum LogLevel {
+    Error,
+    Warning,
+    Info,
+    Debug
+}
+
+impl LogLevel {
+    pub fn from_string(string: &str) -> Result<LogLevel, CompilerError> {
+        match string {
+            "error" => Ok(LogLevel::Error),
+            "warn" => Ok(LogLevel::Warning),
+            "info" => Ok(LogLevel::Info),
+            "debug" => Ok(LogLevel::Debug),
+            _ => {
+                error!("Invalid log level passed. Got {0}, and valid values are: {1}", string, LogLevel::valid_values());
+                Err(CompilerError::InvalidArgument)
+            }
+        }
+    }
+
+    fn valid_values() -> String {
+        String::from("error, warn, info, debug")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_levels_are_parsed() {
+        assert_eq!(LogLevel::from_string("error").unwrap(), LogLevel::Error);
+        assert_eq!(LogLevel::from_string("warn").unwrap(), LogLevel::Warning);
+        assert_eq!(LogLevel::from_string("info").unwrap(), LogLevel::Info);
+        assert_eq!(LogLevel::from_string("debug").unwrap(), LogLevel::Debug);
+    }
+
+    #[test]
+    fn invalid_levels_are_rejected() {
+        for level in ["", "warning", "Info", "trace"] {
+            assert!(matches!(LogLevel::from_string(level), Err(CompilerError::InvalidArgument)), "{0} should be rejected", level);
+        }
+    }
+
+    #[test]
+    fn levels_are_ordered_by_verbosity() {
+        assert!(LogLevel::Error < LogLevel::Warning);
+        assert!(LogLevel::Warning < LogLevel::Info);
+        assert!(LogLevel::Info < LogLevel::Debug);
+    }
+}
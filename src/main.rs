@@ -1,164 +1,10 @@
-#[macro_use]
-// Declare first because of macros
-mod output;
+use std::process::exit;
 
-mod architecture;
-mod c_standard;
-mod c_utilities;
-mod compile_error;
-mod header;
-mod output_file;
-mod runic_definitions;
-mod source;
+use rune_c_compiler::run;
 
-use std::{fs::create_dir, path::Path};
-
-use clap::Parser;
-use rune_parser::{RuneFileDescription, parser_rune_files};
-
-use crate::{
-    architecture::Architecture,
-    c_standard::CStandard,
-    c_utilities::{CConfigurations, CompileConfigurations},
-    compile_error::CompilerError,
-    header::output_header,
-    output::*,
-    runic_definitions::output_runic_definitions,
-    source::output_source
-};
-
-#[derive(Parser, Debug)]
-#[command(version, about, long_about = None)]
-struct Args {
-    /// Path of folder where to find Rune files (subfolders will also be searched). Can be passed multiple times if files are spread over multiple different directories.
-    #[arg(long, short = 'i')]
-    input_folder: Vec<String>,
-
-    /// Path of folder where to output source code
-    #[arg(long, short = 'o')]
-    output_folder: String,
-
-    /// Target architecture to optimize for - Defaults to 32 bit
-    #[arg(long, short = 'a', default_value = "32")]
-    architecture: usize,
-
-    /// Whether to pack (remove padding) from outputted sources - Defaults to false
-    #[arg(long, short = 'p', default_value = "false")]
-    pack_data: bool,
-
-    /// Whether to pack (remove padding) and size-optimize the outputted parsing metadata - Defaults to false
-    #[arg(long, short = 'm', default_value = "false")]
-    pack_metadata: bool,
-
-    /// Whether to store all Rune data in a specific section. By default no section is declared
-    #[arg(long, short = 'd')]
-    data_section: Option<String>,
-
-    /// Whether to avoid sorting struct field placement to optimize alignment - Defaults to false
-    #[arg(long, short = 'u', default_value = "false")]
-    unsorted: bool,
-
-    /// Whether the program should avoid printing any output at all
-    #[arg(long, short = 's', default_value = "false")]
-    silent: bool,
-
-    /// Specifies which C standard the output source should comply with - Defaults to C23
-    #[arg(long, short = 'c', default_value = "C23")]
-    c_standard: String,
-
-    /// Whether to run the compiler in debug mode, which has significantly increases the number of output messages
-    #[arg(long, default_value = "false")]
-    debug: bool
-}
-
-fn main() -> Result<(), CompilerError> {
-    // Parse arguments
-    // ————————————————
-
-    let args: Args = Args::parse();
-
-    // Disable print output if silent argument was passed
-    if args.silent {
-        enable_silent();
-    };
-
-    // Enable debug messages
-    if args.debug {
-        enable_debug();
+fn main() {
+    // Errors are logged where they occur, so only the exit code is left to report
+    if let Err(error) = run() {
+        exit(error.exit_code());
     }
-
-    let input_paths: Vec<&Path> = {
-        let mut input_paths = Vec::with_capacity(0x10);
-
-        for folder in &args.input_folder {
-            let path = Path::new(folder.as_str());
-
-            // If input folder does not exist, return an error
-            if !path.exists() {
-                error!("Input path \"{0}\" invalid!", folder);
-                return Err(CompilerError::InvalidInputPath);
-            }
-
-            input_paths.push(path);
-        }
-
-        input_paths
-    };
-    let output_path: &Path = Path::new(args.output_folder.as_str());
-
-    let configurations: CompileConfigurations = CompileConfigurations {
-        architecture:  Architecture::from_value(args.architecture)?,
-        c_standard:    CStandard::from_string(&args.c_standard)?,
-        pack_data:     args.pack_data,
-        pack_metadata: args.pack_metadata,
-        section:       args.data_section,
-        sort:          !args.unsorted
-    };
-
-    // Validate arguments
-    // ———————————————————
-
-    // If output folder does exist, create it
-    if !output_path.is_dir()
-        && let Err(error) = create_dir(output_path)
-    {
-        error!("Cannot create directory {0:?}. Got error {1}", output_path, error);
-        return Err(CompilerError::FileSystemError(error));
-    }
-
-    let definitions_list: Vec<RuneFileDescription> = match parser_rune_files(&input_paths, true, false) {
-        Ok(value) => value,
-        Err(error) => {
-            error!("Could not parser Rune files! Got error {0:?}", error);
-            return Err(CompilerError::ParsingError(error));
-        }
-    };
-
-    // Create source files
-    // ————————————————————
-
-    output_c_files(definitions_list, output_path, configurations)
-}
-
-pub fn output_c_files(file_descriptions: Vec<RuneFileDescription>, output_path: &Path, configurations: CompileConfigurations) -> Result<(), CompilerError> {
-    let c_configurations: CConfigurations = CConfigurations::parse(&file_descriptions, &configurations)?;
-
-    // Create runic definitions file
-    info!("Outputting runic definitions");
-    output_runic_definitions(&file_descriptions, &c_configurations, output_path)?;
-
-    // Create source and header files matching the Rune files
-    info!("Outputting headers and sources for:");
-    for file in &file_descriptions {
-        info!("    {0}{1}.rune", file.relative_path, file.name);
-
-        // Create header file
-        output_header(file, &c_configurations, output_path)?;
-
-        // Create source file
-        output_source(file, &c_configurations, output_path)?;
-    }
-
-    info!("Rune C compiler is done!");
-    Ok(())
 }
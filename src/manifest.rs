@@ -0,0 +1,259 @@
+use std::path::Path;
+
+use rune_parser::{RuneFileDescription, types::Definitions};
+
+use crate::{
+    amalgamation::amalgamate,
+    c_utilities::{CompileConfigurations, output_base_name},
+    compile_error::CompilerError,
+    endianness::Endianness,
+    output_file::{OutputFile, split_file_path},
+    selection::{filter_structs, select_structs},
+    target_compiler::TargetCompiler
+};
+
+/// Version of the manifest format, increased whenever existing keys change meaning or are removed
+const MANIFEST_VERSION: u32 = 1;
+
+/// Format a string as a JSON string literal
+fn json_string(string: &str) -> String {
+    let mut literal: String = String::with_capacity(string.len() + 2);
+
+    literal.push('"');
+    for character in string.chars() {
+        match character {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            character if (character as u32) < 0x20 => literal.push_str(&format!("\\u{0:04x}", character as u32)),
+            character => literal.push(character)
+        }
+    }
+    literal.push('"');
+
+    literal
+}
+
+/// Format a list of strings as a JSON array
+fn json_array(strings: &[String]) -> String {
+    format!("[{0}]", strings.iter().map(|string| json_string(string)).collect::<Vec<String>>().join(", "))
+}
+
+/// Path of a Rune file relative to its input folder
+fn rune_path(file: &RuneFileDescription) -> String {
+    format!("{0}{1}.rune", file.relative_path, file.name)
+}
+
+/// The Rune files an output file was generated from, and the definitions it contains. Files mirroring a single Rune file come from
+/// that file alone, while all other files are generated from every Rune file, and contain no definitions of their own
+fn output_origin(output_name: &str, file_descriptions: &[RuneFileDescription], configurations: &CompileConfigurations) -> (Vec<String>, Option<Definitions>) {
+    // Sorted, as the order in which files are found depends on the filesystem
+    let mut all_sources: Vec<String> = file_descriptions.iter().map(rune_path).collect();
+    all_sources.sort();
+
+    let mirrors = |file: &RuneFileDescription| -> bool {
+        ["h", "c", "hpp"]
+            .iter()
+            .any(|extension| output_name == format!("{0}.{1}", output_base_name(file, configurations), configurations.output_extension(extension)))
+    };
+
+    match configurations.amalgamate {
+        true => {
+            let amalgamated_file: RuneFileDescription = amalgamate(file_descriptions);
+
+            match mirrors(&amalgamated_file) {
+                true => (all_sources, Some(amalgamated_file.definitions)),
+                false => (all_sources, None)
+            }
+        },
+        false => match file_descriptions.iter().find(|file| mirrors(file)) {
+            Some(file) => (vec![rune_path(file)], Some(file.definitions.clone())),
+            None => (all_sources, None)
+        }
+    }
+}
+
+/// Outputs a JSON manifest listing every generated file, the Rune files it was generated from and the definitions it contains,
+/// together with the options the files were generated with, for build tools to track the generated files without parsing logs
+pub fn output_manifest(output_files: &[(String, String)], file_descriptions: &Vec<RuneFileDescription>, configurations: &CompileConfigurations, manifest_path: &str) -> Result<(), CompilerError> {
+    let (manifest_folder, manifest_name): (&Path, String) = split_file_path("--manifest", manifest_path)?;
+
+    let mut manifest_file: OutputFile = OutputFile::new(manifest_name, configurations);
+
+    // Only the selected structs are output
+    let selection: Vec<String> = select_structs(file_descriptions, configurations)?;
+    let selected_descriptions: Vec<RuneFileDescription> = filter_structs(file_descriptions, &selection);
+
+    // Configuration
+    // ——————————————
+
+    manifest_file.add_line(String::from("{"));
+    manifest_file.add_indented_line(1, format!("\"version\": {0},", MANIFEST_VERSION));
+    manifest_file.add_indented_line(1, String::from("\"configuration\": {"));
+    manifest_file.add_indented_line(2, format!("\"c_standard\": {0},", json_string(&configurations.c_standard.to_string())));
+    manifest_file.add_indented_line(2, format!("\"architecture\": {0},", configurations.architecture.byte_size() * 8));
+    manifest_file.add_indented_line(
+        2,
+        format!(
+            "\"endianness\": {0},",
+            json_string(match configurations.endianness {
+                Endianness::Native => "native",
+                Endianness::Little => "little",
+                Endianness::Big => "big"
+            })
+        )
+    );
+    manifest_file.add_indented_line(
+        2,
+        format!(
+            "\"compiler\": {0},",
+            json_string(match configurations.compiler {
+                TargetCompiler::Gcc => "gcc",
+                TargetCompiler::Clang => "clang",
+                TargetCompiler::Msvc => "msvc"
+            })
+        )
+    );
+    manifest_file.add_indented_line(2, format!("\"pack_data\": {0},", configurations.pack_data));
+    manifest_file.add_indented_line(2, format!("\"pack_metadata\": {0}", configurations.pack_metadata));
+    manifest_file.add_indented_line(1, String::from("},"));
+
+    // Files
+    // ——————
+
+    manifest_file.add_indented_line(1, String::from("\"files\": ["));
+
+    for (position, (output_name, _)) in output_files.iter().enumerate() {
+        let (sources, definitions): (Vec<String>, Option<Definitions>) = output_origin(output_name, &selected_descriptions, configurations);
+        let definitions: Definitions = definitions.unwrap_or_default();
+
+        manifest_file.add_indented_line(2, String::from("{"));
+        manifest_file.add_indented_line(3, format!("\"path\": {0},", json_string(output_name)));
+        manifest_file.add_indented_line(3, format!("\"sources\": {0},", json_array(&sources)));
+        manifest_file.add_indented_line(
+            3,
+            format!(
+                "\"structs\": {0},",
+                json_array(&definitions.structs.iter().map(|definition| definition.name.clone()).collect::<Vec<String>>())
+            )
+        );
+        manifest_file.add_indented_line(
+            3,
+            format!(
+                "\"enums\": {0},",
+                json_array(&definitions.enums.iter().map(|definition| definition.name.clone()).collect::<Vec<String>>())
+            )
+        );
+        manifest_file.add_indented_line(
+            3,
+            format!(
+                "\"bitfields\": {0}",
+                json_array(&definitions.bitfields.iter().map(|definition| definition.name.clone()).collect::<Vec<String>>())
+            )
+        );
+        manifest_file.add_indented_line(
+            2,
+            match position == output_files.len() - 1 {
+                true => String::from("}"),
+                false => String::from("},")
+            }
+        );
+    }
+
+    manifest_file.add_indented_line(1, String::from("]"));
+    manifest_file.add_line(String::from("}"));
+
+    manifest_file.output_file(manifest_folder)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::{read_to_string, remove_dir_all},
+        path::PathBuf
+    };
+
+    use rune_parser::RuneFileDescription;
+    use serde_json::{Value, json};
+
+    use super::{json_string, output_manifest};
+    use crate::{
+        c_utilities::CompileConfigurations,
+        generate_to_memory,
+        test_utilities::{compile_configurations, parse_schema, write_schema}
+    };
+
+    const SCHEMA: [(&str, &str); 2] = [
+        (
+            "net/header.rune",
+            "enum Kind: u8 {\n    Data = 0;\n    Ack = 1\n}\n\nstruct Header {\n    Id: u16 = 1;\n    Type: Kind = 2\n}\n"
+        ),
+        (
+            "device.rune",
+            "include \"net/header.rune\";\n\nbitfield Flags: u8 {\n    Enabled: u1 = 0\n}\n\nstruct Device {\n    Head: Header = 1;\n    State: Flags = 2\n}\n"
+        )
+    ];
+
+    /// Generate the files of the schema with the given arguments, and parse the manifest listing them
+    fn manifest(arguments: &[&str]) -> (Vec<(String, String)>, Value) {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&SCHEMA);
+        let configurations: CompileConfigurations = compile_configurations(arguments);
+        let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &configurations).unwrap();
+
+        let manifest_folder: PathBuf = write_schema(&[]);
+        let manifest_path: PathBuf = manifest_folder.join("manifest.json");
+        output_manifest(&output_files, &file_descriptions, &configurations, manifest_path.to_str().unwrap()).unwrap();
+
+        let manifest: String = read_to_string(&manifest_path).unwrap();
+        remove_dir_all(&manifest_folder).unwrap();
+
+        (output_files, serde_json::from_str(&manifest).expect("Manifest should be valid JSON"))
+    }
+
+    fn manifest_entry<'a>(manifest: &'a Value, path: &str) -> &'a Value {
+        manifest["files"].as_array().unwrap().iter().find(|entry| entry["path"] == path).expect("File should be listed")
+    }
+
+    #[test]
+    fn every_output_file_is_listed_with_its_sources() {
+        let (output_files, manifest): (Vec<(String, String)>, Value) = manifest(&["--pack-data"]);
+
+        assert_eq!(manifest["version"], 1);
+        assert_eq!(manifest["configuration"]["pack_data"], true);
+        assert_eq!(manifest["configuration"]["compiler"], "gcc");
+
+        let listed_paths: Vec<&str> = manifest["files"].as_array().unwrap().iter().map(|entry| entry["path"].as_str().unwrap()).collect();
+        assert_eq!(listed_paths, output_files.iter().map(|(name, _)| name.as_str()).collect::<Vec<&str>>());
+
+        let header: &Value = manifest_entry(&manifest, "net/header.rune.h");
+        assert_eq!(header["sources"], json!(["net/header.rune"]));
+        assert_eq!(header["structs"], json!(["Header"]));
+        assert_eq!(header["enums"], json!(["Kind"]));
+
+        let device: &Value = manifest_entry(&manifest, "device.rune.c");
+        assert_eq!(device["sources"], json!(["device.rune"]));
+        assert_eq!(device["structs"], json!(["Device"]));
+        assert_eq!(device["bitfields"], json!(["Flags"]));
+
+        // Files generated from the whole schema are listed with every Rune file, sorted
+        let definitions: &Value = manifest_entry(&manifest, "runic_definitions.h");
+        assert_eq!(definitions["sources"], json!(["device.rune", "net/header.rune"]));
+        assert_eq!(definitions["structs"], json!([]));
+    }
+
+    #[test]
+    fn amalgamated_files_come_from_every_rune_file() {
+        let (_, manifest): (Vec<(String, String)>, Value) = manifest(&["--amalgamate"]);
+
+        let header: &Value = manifest_entry(&manifest, "rune_all.h");
+        assert_eq!(header["sources"], json!(["device.rune", "net/header.rune"]));
+        assert_eq!(header["structs"], json!(["Header", "Device"]));
+    }
+
+    #[test]
+    fn strings_are_escaped() {
+        assert_eq!(json_string("a\"b\\c\nd\u{1}"), "\"a\\\"b\\\\c\\nd\\u0001\"");
+    }
+}
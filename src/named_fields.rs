@@ -0,0 +1,355 @@
+use rune_parser::{
+    RuneFileDescription,
+    types::{ArrayType, FieldType, Primitive, StructDefinition, StructMember, UserDefinitionLink}
+};
+
+use crate::{
+    c_utilities::{CConfigurations, CStructMember, offset_member_name, output_base_name, output_debug_checks, output_disclaimer, pascal_to_snake_case},
+    compile_error::CompilerError,
+    output_file::OutputFile
+};
+
+// Named field tables
+// ———————————————————
+//
+// Every struct gets a table listing its members by their Rune identifier, together with their offset, size and a type code, so
+// scripting bridges can read and write members by name at runtime. Members are listed in index order, and are looked up by a linear
+// search, as the tables are meant for occasional access from a scripting language rather than hot paths.
+
+const NAMED_FIELDS_HEADER_LINES: &[&str] = &[
+    "/** Type codes of named fields. Arrays have RUNE_TYPE_CODE_ARRAY set on top of the type code of their elements */",
+    "#define RUNE_TYPE_CODE_BOOL     0x01",
+    "#define RUNE_TYPE_CODE_CHAR     0x02",
+    "#define RUNE_TYPE_CODE_I8       0x03",
+    "#define RUNE_TYPE_CODE_U8       0x04",
+    "#define RUNE_TYPE_CODE_I16      0x05",
+    "#define RUNE_TYPE_CODE_U16      0x06",
+    "#define RUNE_TYPE_CODE_I32      0x07",
+    "#define RUNE_TYPE_CODE_U32      0x08",
+    "#define RUNE_TYPE_CODE_I64      0x09",
+    "#define RUNE_TYPE_CODE_U64      0x0A",
+    "#define RUNE_TYPE_CODE_F32      0x0B",
+    "#define RUNE_TYPE_CODE_F64      0x0C",
+    "#define RUNE_TYPE_CODE_I128     0x0D",
+    "#define RUNE_TYPE_CODE_U128     0x0E",
+    "#define RUNE_TYPE_CODE_ENUM     0x0F",
+    "#define RUNE_TYPE_CODE_BITFIELD 0x10",
+    "#define RUNE_TYPE_CODE_STRUCT   0x11",
+    "#define RUNE_TYPE_CODE_ARRAY    0x80",
+    "",
+    "/** Name, location and type of a single struct member */",
+    "typedef struct rune_named_field_s {",
+    "    /** Identifier of the member, as declared in the Rune file */",
+    "    const char* name;",
+    "",
+    "    /** Offset of the member within the struct */",
+    "    RUNE_FIELD_OFFSET_TYPE offset;",
+    "",
+    "    /** Size of the member */",
+    "    RUNE_FIELD_SIZE_TYPE size;",
+    "",
+    "    /** Type of the member, one of RUNE_TYPE_CODE_* */",
+    "    unsigned char type_code;",
+    "} rune_named_field_t;",
+    "",
+    "/** Named fields of a struct */",
+    "typedef struct rune_named_fields_s {",
+    "    const rune_named_field_t* fields;",
+    "    size_t                    field_count;",
+    "} rune_named_fields_t;",
+    "",
+    "/** Find the member with the given name in the named fields of a struct. Returns NULL if there is no such member */",
+    "RUNE_NODISCARD const rune_named_field_t* rune_named_field_find(const rune_named_fields_t* named_fields, const char* name);"
+];
+
+/// Outputs the header declaring the named field types and the lookup functions
+pub fn output_named_fields_header(configurations: &CConfigurations) -> OutputFile {
+    let mut header_file: OutputFile = OutputFile::new(String::from("rune_named_fields.h"), &configurations.compiler_configurations);
+
+    output_disclaimer(&mut header_file, &configurations.compiler_configurations);
+
+    header_file.add_line(String::from("#ifndef RUNE_NAMED_FIELDS_H"));
+    header_file.add_line(String::from("#define RUNE_NAMED_FIELDS_H"));
+    header_file.add_newline();
+
+    header_file.add_line(String::from("#ifdef __cplusplus"));
+    header_file.add_line(String::from("extern \"C\" {"));
+    header_file.add_line(String::from("#endif /* __cplusplus */"));
+    header_file.add_newline();
+
+    header_file.add_line(String::from("#include <stddef.h>"));
+    header_file.add_newline();
+
+    // The descriptor based lookup needs the Rune library types
+    match configurations.compiler_configurations.descriptors {
+        true => header_file.add_line(String::from("#include \"rune.h\"")),
+        false => header_file.add_line(format!("#include \"{0}\"", configurations.compiler_configurations.definitions_output))
+    }
+    header_file.add_newline();
+
+    for line in NAMED_FIELDS_HEADER_LINES {
+        header_file.add_reindented_line(line);
+    }
+
+    if configurations.compiler_configurations.descriptors {
+        header_file.add_newline();
+        header_file.add_line(String::from(
+            "/** Find the member with the given name in the struct described by the descriptor. Returns NULL if there is no such member, or"
+        ));
+        header_file.add_line(String::from(" *  if the descriptor is not one of the generated ones */"));
+        header_file.add_line(String::from(
+            "RUNE_NODISCARD const rune_named_field_t* rune_field_by_name(const rune_descriptor_t* descriptor, const char* name);"
+        ));
+    }
+
+    header_file.add_newline();
+    header_file.add_line(String::from("#ifdef __cplusplus"));
+    header_file.add_line(String::from("}"));
+    header_file.add_line(String::from("#endif /* __cplusplus */"));
+    header_file.add_newline();
+
+    header_file.add_line(String::from("#endif /* RUNE_NAMED_FIELDS_H */"));
+
+    header_file
+}
+
+/// Outputs the source implementing the lookup functions, along with the registry mapping descriptors to named fields
+pub fn output_named_fields_source(file_descriptions: &Vec<RuneFileDescription>, configurations: &CConfigurations) -> OutputFile {
+    let mut source_file: OutputFile = OutputFile::new(String::from("rune_named_fields.c"), &configurations.compiler_configurations);
+
+    output_disclaimer(&mut source_file, &configurations.compiler_configurations);
+
+    source_file.add_line(String::from("#include \"rune_named_fields.h\""));
+    source_file.add_newline();
+    source_file.add_line(String::from("#include <string.h>"));
+    source_file.add_newline();
+
+    // Lookup by table
+    // ————————————————
+
+    source_file.add_line(String::from(
+        "const rune_named_field_t* rune_named_field_find(const rune_named_fields_t* named_fields, const char* name) {"
+    ));
+    source_file.add_indented_line(1, String::from("size_t i;"));
+    source_file.add_newline();
+
+    if configurations.compiler_configurations.debug_checks {
+        output_debug_checks(
+            &mut source_file,
+            &["named_fields != NULL", "named_fields->field_count == 0 || named_fields->fields != NULL", "name != NULL"]
+        );
+        source_file.add_newline();
+    }
+
+    source_file.add_indented_line(1, String::from("for (i = 0; i < named_fields->field_count; i++) {"));
+    source_file.add_indented_line(2, String::from("if (strcmp(named_fields->fields[i].name, name) == 0) {"));
+    source_file.add_indented_line(3, String::from("return &named_fields->fields[i];"));
+    source_file.add_indented_line(2, String::from("}"));
+    source_file.add_indented_line(1, String::from("}"));
+    source_file.add_newline();
+    source_file.add_indented_line(1, String::from("return NULL;"));
+    source_file.add_line(String::from("}"));
+
+    if !configurations.compiler_configurations.descriptors {
+        return source_file;
+    }
+
+    // Lookup by descriptor
+    // —————————————————————
+
+    let struct_names: Vec<String> = file_descriptions
+        .iter()
+        .flat_map(|file| file.definitions.structs.iter().map(|definition| pascal_to_snake_case(&definition.name)))
+        .collect();
+
+    source_file.add_newline();
+
+    for file in file_descriptions {
+        if !file.definitions.structs.is_empty() {
+            source_file.add_line(format!(
+                "#include \"{0}.{1}\"",
+                output_base_name(file, &configurations.compiler_configurations),
+                configurations.compiler_configurations.output_extension("h")
+            ));
+        }
+    }
+    source_file.add_newline();
+
+    source_file.add_line(String::from("/* Named fields of every generated struct, by descriptor */"));
+    source_file.add_line(String::from("static const struct {"));
+    source_file.add_indented_line(1, String::from("const rune_descriptor_t*   descriptor;"));
+    source_file.add_indented_line(1, String::from("const rune_named_fields_t* named_fields;"));
+    source_file.add_line(format!("}} rune_named_fields_registry[{0}] = {{", struct_names.len().max(1)));
+
+    match struct_names.is_empty() {
+        true => source_file.add_indented_line(1, String::from("{ NULL, NULL }")),
+        false => {
+            for (counter, struct_name) in struct_names.iter().enumerate() {
+                let end: &'static str = match counter == struct_names.len() - 1 {
+                    false => ",",
+                    true => ""
+                };
+
+                source_file.add_indented_line(1, format!("{{ &{0}_descriptor, &{0}_named_fields }}{1}", struct_name, end));
+            }
+        },
+    }
+
+    source_file.add_line(String::from("};"));
+    source_file.add_newline();
+
+    source_file.add_line(String::from("const rune_named_field_t* rune_field_by_name(const rune_descriptor_t* descriptor, const char* name) {"));
+    source_file.add_indented_line(1, String::from("size_t i;"));
+    source_file.add_newline();
+
+    if configurations.compiler_configurations.debug_checks {
+        output_debug_checks(&mut source_file, &["name != NULL"]);
+        source_file.add_newline();
+    }
+
+    source_file.add_indented_line(1, String::from("for (i = 0; i < sizeof(rune_named_fields_registry) / sizeof(rune_named_fields_registry[0]); i++) {"));
+    source_file.add_indented_line(2, String::from("if (descriptor != NULL && rune_named_fields_registry[i].descriptor == descriptor) {"));
+    source_file.add_indented_line(3, String::from("return rune_named_field_find(rune_named_fields_registry[i].named_fields, name);"));
+    source_file.add_indented_line(2, String::from("}"));
+    source_file.add_indented_line(1, String::from("}"));
+    source_file.add_newline();
+    source_file.add_indented_line(1, String::from("return NULL;"));
+    source_file.add_line(String::from("}"));
+
+    source_file
+}
+
+/// Get the type code of a primitive
+fn primitive_type_code(primitive: &Primitive) -> &'static str {
+    match primitive {
+        Primitive::Bool => "RUNE_TYPE_CODE_BOOL",
+        Primitive::Char => "RUNE_TYPE_CODE_CHAR",
+        Primitive::I8 => "RUNE_TYPE_CODE_I8",
+        Primitive::U8 => "RUNE_TYPE_CODE_U8",
+        Primitive::I16 => "RUNE_TYPE_CODE_I16",
+        Primitive::U16 => "RUNE_TYPE_CODE_U16",
+        Primitive::I32 => "RUNE_TYPE_CODE_I32",
+        Primitive::U32 => "RUNE_TYPE_CODE_U32",
+        Primitive::I64 => "RUNE_TYPE_CODE_I64",
+        Primitive::U64 => "RUNE_TYPE_CODE_U64",
+        Primitive::F32 => "RUNE_TYPE_CODE_F32",
+        Primitive::F64 => "RUNE_TYPE_CODE_F64",
+        Primitive::I128 => "RUNE_TYPE_CODE_I128",
+        Primitive::U128 => "RUNE_TYPE_CODE_U128"
+    }
+}
+
+/// Get the type code of a user defined type
+fn user_definition_type_code(link: &UserDefinitionLink) -> &'static str {
+    match link {
+        UserDefinitionLink::EnumLink(_) => "RUNE_TYPE_CODE_ENUM",
+        UserDefinitionLink::BitfieldLink(_) => "RUNE_TYPE_CODE_BITFIELD",
+        _ => "RUNE_TYPE_CODE_STRUCT"
+    }
+}
+
+/// Outputs the named fields of a struct into the source file
+pub fn output_named_fields(source_file: &mut OutputFile, configurations: &CConfigurations, struct_definition: &StructDefinition) -> Result<(), CompilerError> {
+    let struct_name: String = pascal_to_snake_case(&struct_definition.name);
+
+    // Fields are listed in index order
+    let mut members: Vec<StructMember> = struct_definition.members.iter().filter(|member| member.data_type != FieldType::Empty).cloned().collect();
+    members.sort_by_key(|member| member.index.value());
+
+    if members.is_empty() {
+        source_file.add_line(format!("const rune_named_fields_t {0}_named_fields = {{ NULL, 0 }};", struct_name));
+        source_file.add_newline();
+        return Ok(());
+    }
+
+    source_file.add_line(format!("static const rune_named_field_t {0}_named_field_entries[{1}] = {{", struct_name, members.len()));
+
+    for (counter, member) in members.iter().enumerate() {
+        let type_code: String = match &member.data_type {
+            FieldType::Primitive(primitive) => String::from(primitive_type_code(primitive)),
+            FieldType::UserDefined(_) => String::from(user_definition_type_code(&member.user_definition_link)),
+            FieldType::Array(ArrayType::Primitive(primitive), _) => format!("RUNE_TYPE_CODE_ARRAY | {0}", primitive_type_code(primitive)),
+            FieldType::Array(ArrayType::UserDefined(_), _) => format!("RUNE_TYPE_CODE_ARRAY | {0}", user_definition_type_code(&member.user_definition_link)),
+            FieldType::Empty => unreachable!("Empty members are filtered out above!")
+        };
+
+        let end: &'static str = match counter == members.len() - 1 {
+            false => ",",
+            true => ""
+        };
+
+        source_file.add_indented_line(
+            1,
+            format!(
+                "{{ \"{0}\", offsetof({1}_t, {2}), {3}, {4} }}{5}",
+                member.identifier,
+                struct_name,
+                offset_member_name(configurations, &struct_definition.name, member)?,
+                member
+                    .storage_member(&configurations.compiler_configurations)
+                    .c_size_definition(&configurations.compiler_configurations)?,
+                type_code,
+                end
+            )
+        );
+    }
+
+    source_file.add_line(String::from("};"));
+    source_file.add_newline();
+
+    source_file.add_line(format!("const rune_named_fields_t {0}_named_fields = {{ {0}_named_field_entries, {1} }};", struct_name, members.len()));
+    source_file.add_newline();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rune_parser::RuneFileDescription;
+
+    use crate::{
+        generate_to_memory,
+        test_utilities::{compile_configurations, parse_schema}
+    };
+
+    const SCHEMA: &str = "enum Mode: u8 {\n    Idle = 0\n}\n\nstruct Sample {\n    Valid: bool = 2;\n    CurrentMode: Mode = 1;\n    Readings: [u16; 3] = 3\n}\n";
+
+    /// Output the files generated from the schema with named fields and the given command line arguments, and get the named one
+    fn generated_file(arguments: &[&str], name: &str) -> String {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("sample.rune", SCHEMA)]);
+        let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &compile_configurations(&[&["--emit-named-fields"], arguments].concat())).unwrap();
+
+        output_files.into_iter().find(|(output_name, _)| output_name == name).expect("File should be generated").1
+    }
+
+    #[test]
+    fn fields_are_listed_in_index_order_by_their_rune_identifier() {
+        let source: String = generated_file(&[], "sample.rune.c");
+
+        assert!(source.contains(
+            "static const rune_named_field_t sample_named_field_entries[3] = {\n    { \"CurrentMode\", offsetof(sample_t, current_mode), sizeof(mode_t), RUNE_TYPE_CODE_ENUM },\n    { \"Valid\", offsetof(sample_t, valid), sizeof(bool), RUNE_TYPE_CODE_BOOL },\n    { \"Readings\", offsetof(sample_t, readings), (sizeof(uint16_t) * 3), RUNE_TYPE_CODE_ARRAY | RUNE_TYPE_CODE_U16 }\n};"
+        ));
+        assert!(source.contains("const rune_named_fields_t sample_named_fields = { sample_named_field_entries, 3 };"));
+    }
+
+    #[test]
+    fn fields_are_found_by_name_through_the_descriptor() {
+        let header: String = generated_file(&[], "rune_named_fields.h");
+        let source: String = generated_file(&[], "rune_named_fields.c");
+
+        assert!(header.contains("const rune_named_field_t* rune_field_by_name(const rune_descriptor_t* descriptor, const char* name);"));
+        assert!(source.contains("#include \"sample.rune.h\""));
+        assert!(source.contains("} rune_named_fields_registry[1] = {\n    { &sample_descriptor, &sample_named_fields }\n};"));
+        assert!(source.contains("            return rune_named_field_find(rune_named_fields_registry[i].named_fields, name);"));
+    }
+
+    #[test]
+    fn fields_are_only_found_through_their_table_without_descriptors() {
+        let header: String = generated_file(&["--no-descriptors"], "rune_named_fields.h");
+        let source: String = generated_file(&["--no-descriptors"], "rune_named_fields.c");
+
+        assert!(source.contains("const rune_named_field_t* rune_named_field_find(const rune_named_fields_t* named_fields, const char* name) {"));
+        assert!(!header.contains("rune_field_by_name"));
+        assert!(!source.contains("rune_field_by_name"));
+    }
+}
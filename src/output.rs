@@ -1,5 +1,7 @@
+use crate::log_level::LogLevel;
+
 static mut SILENT: bool = false;
-static mut DEBUG: bool = false;
+static mut LOG_LEVEL: LogLevel = LogLevel::Info;
 
 pub fn enable_silent() {
     unsafe {
@@ -7,18 +9,15 @@ pub fn enable_silent() {
     }
 }
 
-pub fn enable_debug() {
+pub fn set_log_level(log_level: LogLevel) {
     unsafe {
-        DEBUG = true;
+        LOG_LEVEL = log_level;
     }
 }
 
-pub fn is_silent() -> bool {
-    unsafe { SILENT }
-}
-
-pub fn is_debugging() -> bool {
-    unsafe { DEBUG }
+/// Whether messages of the given level are printed. Nothing is printed when silent
+pub fn is_logging(log_level: LogLevel) -> bool {
+    unsafe { !SILENT && log_level <= LOG_LEVEL }
 }
 
 // Reset  - "\u{001B}[0m"
@@ -34,7 +33,7 @@ pub fn is_debugging() -> bool {
 #[macro_export]
 macro_rules! debug {
     ($($value: expr), *) => {
-        if !is_silent() && is_debugging() {
+        if is_logging($crate::log_level::LogLevel::Debug) {
             print!("\u{001B}[0;32m");
             print!($($value),*);
             println!("\u{001B}[0m");
@@ -45,7 +44,7 @@ macro_rules! debug {
 #[macro_export]
 macro_rules! info {
     ($($value: expr), *) => {
-        if !is_silent() {
+        if is_logging($crate::log_level::LogLevel::Info) {
             println!($($value),*);
         }
     };
@@ -54,7 +53,7 @@ macro_rules! info {
 #[macro_export]
 macro_rules! warning {
     ($($value: expr), *) => {
-        if !is_silent() {
+        if is_logging($crate::log_level::LogLevel::Warning) {
             print!("\u{001B}[0;33m");
             print!($($value),*);
             println!("\u{001B}[0m");
@@ -65,7 +64,7 @@ macro_rules! warning {
 #[macro_export]
 macro_rules! error {
     ($($value: expr), *) => {
-        if !is_silent() {
+        if is_logging($crate::log_level::LogLevel::Error) {
             eprint!("\u{001B}[0;31m");
             eprint!($($value),*);
             eprintln!("\u{001B}[0m");
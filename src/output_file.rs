@@ -1,103 +1,587 @@
 use std::{
-    fs::{File, create_dir, remove_file},
-    io::Write,
-    path::Path
+    ffi::OsString,
+    fs::{DirEntry, File, FileType, ReadDir, create_dir, read_dir, remove_file, rename},
+    io::{StdoutLock, Write, stdout},
+    path::{Path, PathBuf}
 };
 
-use crate::{compile_error::CompilerError, output::*};
+use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+use crate::{
+    c_utilities::{CompileConfigurations, format_doc_comment},
+    compile_error::CompilerError,
+    output::*
+};
+
+/// Join a path relative to the output folder onto a base path. Relative paths use '/' as separator, as in includes and archives,
+/// and are split into their components so the result uses the separator of the platform. Redundant separators of the base path,
+/// such as a trailing '/', are dropped
+pub fn join_relative_path(base: &Path, relative: &str) -> PathBuf {
+    relative
+        .split(['/', '\\'])
+        .filter(|component| !component.is_empty())
+        .fold(base.components().collect(), |path: PathBuf, component| path.join(component))
+}
+
+/// Split the path of a file written outside of the output folder, as given to the named option, into its folder and file name.
+/// Paths without a file name, such as ones ending in "..", cannot be written to
+pub fn split_file_path<'a>(option: &str, file_path: &'a str) -> Result<(&'a Path, String), CompilerError> {
+    let path: &Path = Path::new(file_path);
+
+    let file_name: &str = match path.file_name() {
+        None => {
+            error!("Path \"{0}\" given to {1} does not end in a file name", file_path, option);
+            return Err(CompilerError::InvalidArgument);
+        },
+        Some(file_name) => match file_name.to_str() {
+            None => {
+                error!("File name of \"{0}\" given to {1} is not valid UTF-8", path.display(), option);
+                return Err(CompilerError::InvalidArgument);
+            },
+            Some(file_name) => file_name
+        }
+    };
+
+    let folder: &Path = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new(".")
+    };
+
+    Ok((folder, String::from(file_name)))
+}
 
 pub struct OutputFile {
-    path:          String,
-    name:          String,
-    string_buffer: String
+    name:             String,
+    string_buffer:    String,
+    indent:           String,
+    line_ending:      &'static str,
+    doxygen_comments: bool
 }
 
 impl OutputFile {
-    pub fn new(output_path: String, file_name: String) -> OutputFile {
+    pub fn new(file_name: String, configurations: &CompileConfigurations) -> OutputFile {
         // Create string buffer
         let string_buffer: String = String::with_capacity(0x2000);
 
         OutputFile {
-            path: output_path,
             name: match file_name.strip_prefix("/") {
                 None => file_name,
                 Some(stripped) => String::from(stripped)
             },
-            string_buffer
+            string_buffer,
+            indent: configurations.indentation.as_string(),
+            line_ending: configurations.line_ending.as_str(),
+            doxygen_comments: configurations.doxygen_comments
         }
     }
 
+    /// Get the indentation for the given depth
+    pub fn indent(&self, depth: usize) -> String {
+        self.indent.repeat(depth)
+    }
+
+    /// Name of the file, relative to the output folder
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Take the contents of the file, without writing it anywhere
+    pub fn into_contents(self) -> String {
+        self.string_buffer
+    }
+
     pub fn add_line(&mut self, string: String) {
-        self.string_buffer.push_str(format!("{0}\n", string).as_str());
+        self.string_buffer.push_str(&string);
+        self.string_buffer.push_str(self.line_ending);
+    }
+
+    pub fn add_indented_line(&mut self, depth: usize, string: String) {
+        self.add_line(format!("{0}{1}", self.indent(depth), string));
+    }
+
+    /// Add a line written with four spaces per indentation level, using the configured indentation instead
+    pub fn add_reindented_line(&mut self, line: &str) {
+        let stripped: &str = line.trim_start_matches("    ");
+        let depth: usize = (line.len() - stripped.len()) / 4;
+
+        self.add_indented_line(depth, String::from(stripped));
+    }
+
+    /// Add the comment of a definition from a Rune file as a doc comment block
+    pub fn add_doc_comment(&mut self, depth: usize, comment: &str) {
+        for line in format_doc_comment(comment, self.doxygen_comments) {
+            self.add_indented_line(depth, line);
+        }
     }
 
     pub fn add_newline(&mut self) {
-        self.string_buffer.push('\n');
+        self.string_buffer.push_str(self.line_ending);
+    }
+
+    pub fn output_file(&self, output_path: &Path) -> Result<(), CompilerError> {
+        write_file(output_path, &self.name, &self.string_buffer)
     }
+}
+
+fn create_folder(path: &Path) -> Result<(), CompilerError> {
+    if path.exists() {
+        // If path already exists, do nothing and return
+        return Ok(());
+    }
+
+    match path.parent() {
+        None => Ok(()),
+        Some(parent) => {
+            create_folder(parent)?;
 
-    fn create_folder(path: &Path) -> Result<(), CompilerError> {
-        if path.exists() {
-            // If path already exists, do nothing and return
-            return Ok(());
+            match create_dir(path) {
+                Err(error) => {
+                    error!("Could not create directory {0:?}. Got error {1}", path, error);
+                    Err(CompilerError::FileSystemError(error))
+                },
+                Ok(_) => Ok(())
+            }
         }
+    }
+}
 
-        match path.parent() {
-            None => Ok(()),
-            Some(parent) => {
-                OutputFile::create_folder(parent)?;
-
-                match create_dir(path) {
-                    Err(error) => {
-                        error!("Could not create directory {0:?}. Got error {1}", path, error);
-                        Err(CompilerError::FileSystemError(error))
-                    },
-                    Ok(_) => Ok(())
-                }
+/// Write the contents of a file, named relative to the output folder, replacing any existing file. The contents are written to a
+/// temporary file next to it first, which is renamed over the existing file once complete, so a failed write never leaves a
+/// truncated file behind
+pub fn write_file(output_path: &Path, name: &str, contents: &str) -> Result<(), CompilerError> {
+    let full_file_path: PathBuf = join_relative_path(output_path, name);
+
+    let output_file_path: &Path = full_file_path.as_path();
+
+    let mut temporary_file_name: OsString = output_file_path.as_os_str().to_os_string();
+    temporary_file_name.push(".tmp");
+
+    let temporary_file_path: &Path = Path::new(&temporary_file_name);
+
+    // Create parent folders if any
+    if let Some(parent) = output_file_path.parent() {
+        create_folder(parent)?;
+    }
+
+    let mut output_file: File = match File::create(temporary_file_path) {
+        Err(error) => {
+            error!("Could not create output file \"{0}\". Got error {1}", temporary_file_path.display(), error);
+            return Err(CompilerError::FileSystemError(error));
+        },
+        Ok(file_result) => file_result
+    };
+
+    let result: Result<(), std::io::Error> = match output_file.write_all(contents.as_bytes()) {
+        Err(error) => {
+            error!("Could not write to \"{0}\" file. Got error {1}", name, error);
+            Err(error)
+        },
+        Ok(_) => match output_file.flush() {
+            Err(error) => {
+                error!("Could not flush to \"{0}\" file. Got error {1}", name, error);
+                Err(error)
+            },
+            Ok(_) => match rename(temporary_file_path, output_file_path) {
+                Err(error) => {
+                    error!("Could not replace \"{0}\" file. Got error {1}", output_file_path.display(), error);
+                    Err(error)
+                },
+                Ok(_) => Ok(())
             }
         }
+    };
+
+    // Leave no temporary file behind on failure. The original error is the one worth reporting, so a failed removal is ignored
+    if let Err(error) = result {
+        drop(output_file);
+        let _ = remove_file(temporary_file_path);
+        return Err(CompilerError::FileSystemError(error));
     }
 
-    pub fn output_file(&self) -> Result<(), CompilerError> {
-        let full_file_name: String = format!("{0}/{1}", self.path, self.name);
+    Ok(())
+}
 
-        let relative_file_path: &Path = Path::new(&self.name);
+/// Prints the listed files to standard output instead of writing them, each preceded by a comment with its name
+pub fn print_files(output_files: &[(String, String)], names: &[String]) -> Result<(), CompilerError> {
+    let mut standard_output: StdoutLock = stdout().lock();
 
-        let output_file_path: &Path = Path::new(&full_file_name);
+    write_printed_files(&mut standard_output, output_files, names)
+}
+
+/// Writes the listed files one after another, each preceded by a comment with its name, in the order they were generated
+fn write_printed_files(output: &mut impl Write, output_files: &[(String, String)], names: &[String]) -> Result<(), CompilerError> {
+    for (index, (name, contents)) in output_files.iter().filter(|(name, _)| names.contains(name)).enumerate() {
+        let separator: &str = match index {
+            0 => "",
+            _ => "\n"
+        };
 
-        // Create parent folders if any
-        if relative_file_path.parent().is_some() {
-            // println!("Calling create folder on {0:?}", output_file_path);
-            OutputFile::create_folder(output_file_path.parent().unwrap())?;
+        if let Err(error) = write!(output, "{0}// {1}\n{2}", separator, name, contents) {
+            error!("Could not print file {0} to standard output. Got error {1}", name, error);
+            return Err(CompilerError::FileSystemError(error));
         }
+    }
 
-        // Check if file already exists
-        if output_file_path.exists()
-            && let Err(error) = remove_file(output_file_path)
-        {
-            error!("Could not delete existing {0} file. Got error {1}", output_file_path.to_str().unwrap(), error);
+    Ok(())
+}
+
+/// Outputs all files into a single zip archive, preserving their relative paths
+pub fn output_archive(output_files: &[(String, String)], archive_path: &Path) -> Result<(), CompilerError> {
+    // Create parent folders if any
+    if let Some(parent) = archive_path.parent() {
+        create_folder(parent)?;
+    }
+
+    let archive_file: File = match File::create(archive_path) {
+        Err(error) => {
+            error!("Could not create output archive \"{0}\". Got error {1}", archive_path.display(), error);
+            return Err(CompilerError::FileSystemError(error));
+        },
+        Ok(file_result) => file_result
+    };
+
+    let mut archive: ZipWriter<File> = ZipWriter::new(archive_file);
+    let options: SimpleFileOptions = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for (name, contents) in output_files {
+        if let Err(error) = archive.start_file(name.as_str(), options) {
+            error!("Could not add \"{0}\" to output archive. Got error {1}", name, error);
+            return Err(CompilerError::FileSystemError(error.into()));
+        }
+
+        if let Err(error) = archive.write_all(contents.as_bytes()) {
+            error!("Could not write \"{0}\" to output archive. Got error {1}", name, error);
             return Err(CompilerError::FileSystemError(error));
         }
+    }
+
+    match archive.finish() {
+        Err(error) => {
+            error!("Could not finish output archive \"{0}\". Got error {1}", archive_path.display(), error);
+            Err(CompilerError::FileSystemError(error.into()))
+        },
+        Ok(_) => Ok(())
+    }
+}
 
-        let mut output_file: File = match File::create(output_file_path) {
+/// Whether a file is one the compiler outputs, so it may be removed when cleaning the output folder. Only generated sources and
+/// headers, with a ".rune." infix, and the fixed names of the runic definitions and parser are owned by the compiler
+fn is_compiler_owned(file_name: &str) -> bool {
+    match file_name {
+        "runic_definitions.h" | "runic_parser.c" => true,
+        _ => file_name.contains(".rune.") && (file_name.ends_with(".c") || file_name.ends_with(".h"))
+    }
+}
+
+/// Removes all files the compiler owns under the output folder which are not among the files about to be output, such as the
+/// outputs of Rune files which were renamed or deleted since the last run
+pub fn clean_output_folder(output_path: &Path, output_files: &[(String, String)]) -> Result<(), CompilerError> {
+    let kept_paths: Vec<PathBuf> = output_files.iter().map(|(name, _)| join_relative_path(output_path, name)).collect();
+
+    clean_folder(output_path, &kept_paths)
+}
+
+fn clean_folder(folder_path: &Path, kept_paths: &[PathBuf]) -> Result<(), CompilerError> {
+    let entries: ReadDir = match read_dir(folder_path) {
+        Err(error) => {
+            error!("Could not read directory {0:?}. Got error {1}", folder_path, error);
+            return Err(CompilerError::FileSystemError(error));
+        },
+        Ok(entries) => entries
+    };
+
+    for entry in entries {
+        let entry: DirEntry = match entry {
             Err(error) => {
-                error!("Could not create output file \"{0}\". Got error {1}", output_file_path.to_str().unwrap(), error);
+                error!("Could not read directory {0:?}. Got error {1}", folder_path, error);
                 return Err(CompilerError::FileSystemError(error));
             },
-            Ok(file_result) => file_result
+            Ok(entry) => entry
         };
 
-        match output_file.write_all(self.string_buffer.as_bytes()) {
+        let entry_path: PathBuf = entry.path();
+
+        // Symbolic links are never followed nor removed, as what they point to is not owned by the compiler
+        let file_type: FileType = match entry.file_type() {
             Err(error) => {
-                error!("Could not write to \"{0}\" file. Got error {1}", self.name, error);
-                Err(CompilerError::FileSystemError(error))
+                error!("Could not read file type of {0:?}. Got error {1}", entry_path, error);
+                return Err(CompilerError::FileSystemError(error));
             },
-            Ok(_) => match output_file.flush() {
-                Err(error) => {
-                    error!("Could not flush to \"{0}\" file. Got error {1}", self.name, error);
-                    Err(CompilerError::FileSystemError(error))
-                },
-                Ok(_) => Ok(())
+            Ok(file_type) => file_type
+        };
+
+        if file_type.is_dir() {
+            clean_folder(&entry_path, kept_paths)?;
+            continue;
+        }
+
+        let is_stale: bool = file_type.is_file() && entry.file_name().to_str().is_some_and(is_compiler_owned) && !kept_paths.contains(&entry_path);
+
+        if is_stale {
+            info!("Removing stale file {0}", entry_path.display());
+            if let Err(error) = remove_file(&entry_path) {
+                error!("Could not remove stale file \"{0}\". Got error {1}", entry_path.display(), error);
+                return Err(CompilerError::FileSystemError(error));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::{DirEntry, File, create_dir, read_dir, read_to_string, remove_dir_all},
+        io::Read,
+        path::{Path, PathBuf}
+    };
+
+    use rune_parser::RuneFileDescription;
+    use zip::ZipArchive;
+
+    use crate::{
+        compile_error::CompilerError,
+        generate_to_memory,
+        output_file::{OutputFile, clean_output_folder, join_relative_path, output_archive, split_file_path, write_file, write_printed_files},
+        test_utilities::{compile_configurations, parse_schema, write_schema}
+    };
+
+    #[test]
+    fn lines_end_in_line_feed_by_default() {
+        let mut output_file: OutputFile = OutputFile::new(String::from("test.h"), &compile_configurations(&[]));
+        output_file.add_line(String::from("#define TEST 1"));
+        output_file.add_newline();
+
+        assert_eq!(output_file.into_contents(), "#define TEST 1\n\n");
+    }
+
+    #[test]
+    fn every_generated_line_ends_in_crlf() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("frame.rune", "struct Frame {\n    Flag: u8 = 1\n}\n")]);
+        let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &compile_configurations(&["--line-endings", "crlf", "--tlv-mode"])).unwrap();
+
+        for (name, contents) in &output_files {
+            assert!(contents.ends_with("\r\n"), "{0} does not end in CRLF", name);
+            assert_eq!(contents.matches('\n').count(), contents.matches("\r\n").count(), "{0} has lines ending in LF only", name);
+        }
+    }
+
+    #[test]
+    fn every_generated_line_follows_the_configured_indentation() {
+        let schema: &str = "enum Mode: u8 {\n    Idle = 0;\n    Running = 1\n}\n\nbitfield Flags: u8 {\n    Enabled: u1 = 0;\n    Level: i3 = 1\n}\n\nstruct Point {\n    X: i16 = 1;\n    Y: i16 = 2\n}\n\nstruct Sample {\n    Valid: bool = 1;\n    Current: Mode = 2;\n    Options: Flags = 3;\n    Origin: Point = 4;\n    Readings: [u16; 3] = 5\n}\n";
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("sample.rune", schema)]);
+
+        let option_sets: [&[&str]; 3] = [
+            &[
+                "--emit-getters",
+                "--emit-debug-checks",
+                "--emit-named-fields",
+                "--emit-codec",
+                "--emit-enum-names",
+                "--emit-cpp",
+                "--emit-abi-probe",
+                "--emit-config-header",
+                "--emit-runtime-header",
+                "--emit-ts",
+                "sample.ts",
+                "--emit-proto",
+                "sample.proto",
+                "--tlv-mode"
+            ],
+            &[
+                "--emit-verifiers",
+                "--explicit-padding",
+                "--safe-packed-access",
+                "--emit-getters",
+                "--tlv-mode",
+                "--varint",
+                "--emit-debug-checks"
+            ],
+            &[
+                "--c-standard",
+                "C89",
+                "--emit-getters",
+                "--emit-codec",
+                "--emit-debug-checks",
+                "--tlv-mode",
+                "--varint",
+                "--emit-named-fields",
+                "--emit-enum-names"
+            ]
+        ];
+
+        for options in option_sets {
+            let arguments: Vec<&str> = ["--indent", "tabs"].iter().chain(options).copied().collect();
+            let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &compile_configurations(&arguments)).unwrap();
+
+            for (name, contents) in &output_files {
+                for line in contents.lines() {
+                    assert!(!line.starts_with("    "), "{0} is indented with spaces in {1}: {2:?}", name, options.join(" "), line);
+                }
+            }
+
+            let header: &str = &output_files.iter().find(|(name, _)| name == "sample.rune.h").unwrap().1;
+            assert!(header.contains("\tIDLE    = 0,"));
+        }
+    }
+
+    #[test]
+    fn file_paths_split_into_folder_and_name() {
+        let (folder, name) = split_file_path("--manifest", "build/rune/manifest.json").unwrap();
+        assert_eq!((folder, name.as_str()), (Path::new("build/rune"), "manifest.json"));
+
+        let (folder, name) = split_file_path("--manifest", "manifest.json").unwrap();
+        assert_eq!((folder, name.as_str()), (Path::new("."), "manifest.json"));
+    }
+
+    #[test]
+    fn file_paths_without_a_file_name_are_rejected() {
+        assert!(matches!(split_file_path("--manifest", "out/.."), Err(CompilerError::InvalidArgument)));
+        assert!(matches!(split_file_path("--manifest", "/"), Err(CompilerError::InvalidArgument)));
+    }
+
+    /// Join the paths, as displayed in messages and depfiles. Paths compare by their components, which ignores redundant separators
+    fn joined_path(base: &str, relative: &str) -> String {
+        join_relative_path(Path::new(base), relative).display().to_string()
+    }
+
+    #[test]
+    fn trailing_separators_of_the_output_folder_are_dropped() {
+        let expected: String = Path::new("out").join("schema.rune.h").display().to_string();
+
+        assert_eq!(joined_path("out/", "schema.rune.h"), expected);
+        assert_eq!(joined_path("out///", "schema.rune.h"), expected);
+    }
+
+    #[test]
+    fn nested_relative_paths_use_the_platform_separator() {
+        let expected: String = Path::new("out").join("sub").join("dir").join("file.rune.h").display().to_string();
+
+        assert_eq!(joined_path("out", "sub\\dir/file.rune.h"), expected);
+        assert_eq!(joined_path("out", "/sub//dir/file.rune.h"), expected);
+    }
+
+    /// Names of the files in the folder, sorted
+    fn folder_entries(folder: &Path) -> Vec<String> {
+        let mut names: Vec<String> = read_dir(folder).unwrap().map(|entry| entry.unwrap().file_name().into_string().unwrap()).collect();
+        names.sort();
+
+        names
+    }
+
+    #[test]
+    fn written_files_replace_the_existing_ones() {
+        let output_folder: PathBuf = write_schema(&[("frame.rune.h", "old contents")]);
+
+        write_file(&output_folder, "frame.rune.h", "new contents").unwrap();
+        write_file(&output_folder, "sub/frame.rune.c", "source").unwrap();
+
+        assert_eq!(read_to_string(output_folder.join("frame.rune.h")).unwrap(), "new contents");
+        assert_eq!(read_to_string(output_folder.join("sub").join("frame.rune.c")).unwrap(), "source");
+        assert_eq!(folder_entries(&output_folder), ["frame.rune.h", "sub"]);
+
+        remove_dir_all(&output_folder).unwrap();
+    }
+
+    #[test]
+    fn failed_writes_leave_no_temporary_file_behind() {
+        let output_folder: PathBuf = write_schema(&[]);
+
+        // A file cannot be renamed over a folder, so the write fails once the temporary file is complete
+        create_dir(output_folder.join("frame.rune.h")).unwrap();
+
+        assert!(matches!(write_file(&output_folder, "frame.rune.h", "contents"), Err(CompilerError::FileSystemError(_))));
+        assert_eq!(folder_entries(&output_folder), ["frame.rune.h"]);
+        assert!(output_folder.join("frame.rune.h").is_dir());
+
+        remove_dir_all(&output_folder).unwrap();
+    }
+
+    #[test]
+    fn cleaning_only_removes_stale_compiler_owned_files() {
+        let output_folder: PathBuf = write_schema(&[
+            ("frame.rune.h", ""),
+            ("frame.rune.c", ""),
+            ("renamed.rune.h", ""),
+            ("sub/renamed.rune.c", ""),
+            ("runic_parser.c", ""),
+            ("user.h", ""),
+            ("user.c", ""),
+            ("notes.rune.txt", ""),
+            ("sub/user.h", "")
+        ]);
+        let output_files: Vec<(String, String)> = vec![(String::from("frame.rune.h"), String::new()), (String::from("frame.rune.c"), String::new())];
+
+        clean_output_folder(&output_folder, &output_files).unwrap();
+
+        assert_eq!(folder_entries(&output_folder), ["frame.rune.c", "frame.rune.h", "notes.rune.txt", "sub", "user.c", "user.h"]);
+        assert_eq!(folder_entries(&output_folder.join("sub")), ["user.h"]);
+
+        remove_dir_all(&output_folder).unwrap();
+    }
+
+    /// Paths of all files under the folder relative to it, with '/' as separator, sorted
+    fn relative_file_paths(folder: &Path, prefix: &str, paths: &mut Vec<String>) {
+        for entry in read_dir(folder).unwrap() {
+            let entry: DirEntry = entry.unwrap();
+            let name: String = format!("{0}{1}", prefix, entry.file_name().into_string().unwrap());
+
+            match entry.file_type().unwrap().is_dir() {
+                true => relative_file_paths(&entry.path(), &format!("{0}/", name), paths),
+                false => paths.push(name)
             }
         }
+
+        paths.sort();
+    }
+
+    #[test]
+    fn archive_paths_match_the_written_output_folder() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[
+            ("net/link/frame.rune", "struct Frame {\n    Value: u8 = 1\n}\n"),
+            ("app.rune", "include \"net/link/frame.rune\";\n\nstruct App {\n    Last: Frame = 1\n}\n")
+        ]);
+        let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &compile_configurations(&[])).unwrap();
+
+        let output_folder: PathBuf = write_schema(&[]);
+        let archive_path: PathBuf = output_folder.join("rune.zip");
+        output_archive(&output_files, &archive_path).unwrap();
+
+        let folder_path: PathBuf = output_folder.join("out");
+        for (name, contents) in &output_files {
+            write_file(&folder_path, name, contents).unwrap();
+        }
+
+        let mut archive: ZipArchive<File> = ZipArchive::new(File::open(&archive_path).unwrap()).unwrap();
+        let mut archive_names: Vec<String> = archive.file_names().map(String::from).collect();
+        archive_names.sort();
+
+        let mut folder_names: Vec<String> = Vec::new();
+        relative_file_paths(&folder_path, "", &mut folder_names);
+
+        assert_eq!(archive_names, folder_names);
+        assert!(archive_names.contains(&String::from("net/link/frame.rune.h")));
+
+        // Archived files hold the same contents as the written ones
+        let mut archived_header: String = String::new();
+        archive.by_name("net/link/frame.rune.h").unwrap().read_to_string(&mut archived_header).unwrap();
+        assert_eq!(archived_header, read_to_string(folder_path.join("net").join("link").join("frame.rune.h")).unwrap());
+
+        remove_dir_all(&output_folder).unwrap();
+    }
+
+    #[test]
+    fn printed_files_are_separated_by_their_names() {
+        let output_files: Vec<(String, String)> = vec![
+            (String::from("runic_definitions.h"), String::from("#define A 1\n")),
+            (String::from("frame.rune.h"), String::from("header\n")),
+            (String::from("frame.rune.c"), String::from("source\n")),
+        ];
+        let mut printed: Vec<u8> = Vec::new();
+
+        write_printed_files(&mut printed, &output_files, &[String::from("frame.rune.c"), String::from("frame.rune.h")]).unwrap();
+
+        assert_eq!(String::from_utf8(printed).unwrap(), "// frame.rune.h\nheader\n\n// frame.rune.c\nsource\n");
     }
 }
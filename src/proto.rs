@@ -0,0 +1,273 @@
+use rune_parser::{
+    RuneFileDescription,
+    scanner::NumericLiteral,
+    types::{ArrayType, BitSize, BitfieldDefinition, EnumDefinition, FieldType, Primitive, StructDefinition, StructMember}
+};
+
+use crate::{
+    c_utilities::{CConfigurations, output_disclaimer, pascal_to_snake_case, pascal_to_uppercase},
+    compile_error::CompilerError,
+    output::*,
+    output_file::OutputFile
+};
+
+/// Get the proto scalar type of a primitive. 128 bit integers have no scalar type, and are exported as bytes
+fn proto_primitive_type(primitive: &Primitive) -> &'static str {
+    match primitive {
+        Primitive::Bool => "bool",
+        Primitive::Char | Primitive::U8 | Primitive::U16 | Primitive::U32 => "uint32",
+        Primitive::I8 | Primitive::I16 | Primitive::I32 => "int32",
+        Primitive::U64 => "uint64",
+        Primitive::I64 => "int64",
+        Primitive::F32 => "float",
+        Primitive::F64 => "double",
+        Primitive::I128 | Primitive::U128 => "bytes"
+    }
+}
+
+/// Get the proto type of a struct member. Byte and char arrays are bytes and strings, other arrays are repeated fields
+fn proto_field_type(member: &StructMember, file_descriptions: &Vec<RuneFileDescription>) -> Option<String> {
+    match &member.data_type {
+        FieldType::Empty => None,
+        FieldType::Primitive(primitive) => Some(String::from(proto_primitive_type(primitive))),
+        FieldType::UserDefined(name) => Some(proto_user_type(name, file_descriptions)),
+        FieldType::Array(array_type, _) => Some(match array_type {
+            ArrayType::Primitive(Primitive::Char) => String::from("string"),
+            ArrayType::Primitive(Primitive::U8 | Primitive::I8) => String::from("bytes"),
+            ArrayType::Primitive(primitive) => format!("repeated {0}", proto_primitive_type(primitive)),
+            ArrayType::UserDefined(name) => format!("repeated {0}", proto_user_type(name, file_descriptions))
+        })
+    }
+}
+
+/// Get the proto type of a user defined type. Bitfields have no proto equivalent, and are exported as their backing integer
+fn proto_user_type(name: &str, file_descriptions: &Vec<RuneFileDescription>) -> String {
+    for file in file_descriptions {
+        if let Some(bitfield_definition) = file.definitions.bitfields.iter().find(|definition| definition.name == name) {
+            return String::from(proto_primitive_type(&bitfield_definition.backing_type));
+        }
+    }
+
+    String::from(name)
+}
+
+fn output_proto_enum(proto_file: &mut OutputFile, enum_definition: &EnumDefinition) {
+    if let Some(comment) = &enum_definition.comment {
+        proto_file.add_doc_comment(0, comment);
+    }
+
+    // Enum values share the scope of their enum's parent, so they are prefixed with the enum name as the proto style guide suggests
+    let prefix: String = pascal_to_uppercase(&enum_definition.name);
+
+    proto_file.add_line(format!("enum {0} {{", enum_definition.name));
+
+    // The first value of a proto3 enum must be zero, as it is the default
+    let has_zero: bool = enum_definition.members.iter().any(|member| matches!(member.value, NumericLiteral::PositiveInteger(0, _)));
+
+    if !has_zero {
+        warning!(
+            "Enum {0} has no member with value 0, which proto3 requires. Exporting it with {1}_UNSPECIFIED = 0",
+            enum_definition.name,
+            prefix
+        );
+        proto_file.add_indented_line(1, format!("{0}_UNSPECIFIED = 0;", prefix));
+    }
+
+    let mut members = enum_definition.members.clone();
+    members.sort_by_key(|member| !matches!(member.value, NumericLiteral::PositiveInteger(0, _)));
+
+    for member in &members {
+        let value: i64 = match member.value {
+            NumericLiteral::PositiveInteger(value, _) if value <= i32::MAX as u64 => value as i64,
+            NumericLiteral::NegativeInteger(value, _) if value >= i32::MIN as i64 => value,
+            _ => {
+                warning!(
+                    "Value of enum member {0}.{1} does not fit a proto enum value, which is 32 bits, and is not exported",
+                    enum_definition.name,
+                    member.identifier
+                );
+                proto_file.add_indented_line(1, format!("// Not exported: {0} = {1} does not fit 32 bits", member.identifier, member.value));
+                continue;
+            }
+        };
+
+        if let Some(comment) = &member.comment {
+            proto_file.add_doc_comment(1, comment);
+        }
+
+        proto_file.add_indented_line(1, format!("{0}_{1} = {2};", prefix, pascal_to_uppercase(&member.identifier), value));
+    }
+
+    proto_file.add_line(String::from("}"));
+    proto_file.add_newline();
+}
+
+fn output_proto_bitfield_note(proto_file: &mut OutputFile, bitfield_definition: &BitfieldDefinition) {
+    warning!(
+        "Bitfield {0} has no proto equivalent, and is exported as its backing integer {1}",
+        bitfield_definition.name,
+        proto_primitive_type(&bitfield_definition.backing_type)
+    );
+
+    proto_file.add_line(format!(
+        "// Bitfield {0} has no proto equivalent, and is exported as its backing integer {1}. Its members are, from the least significant bit:",
+        bitfield_definition.name,
+        proto_primitive_type(&bitfield_definition.backing_type)
+    ));
+
+    let mut members = bitfield_definition.members.clone();
+    members.sort_by_key(|member| member.index);
+
+    for member in &members {
+        let size: String = match member.size {
+            BitSize::Signed(size) => format!("i{0}", size),
+            BitSize::Unsigned(size) => format!("u{0}", size)
+        };
+
+        proto_file.add_line(format!("//     {0}: {1}", member.identifier, size));
+    }
+
+    proto_file.add_newline();
+}
+
+fn output_proto_message(proto_file: &mut OutputFile, struct_definition: &StructDefinition, file_descriptions: &Vec<RuneFileDescription>) {
+    if let Some(comment) = &struct_definition.comment {
+        proto_file.add_doc_comment(0, comment);
+    }
+
+    proto_file.add_line(format!("message {0} {{", struct_definition.name));
+
+    let mut members: Vec<StructMember> = struct_definition.members.clone();
+    members.sort_by_key(|member| member.index.value());
+
+    for member in &members {
+        let member_type: String = match proto_field_type(member, file_descriptions) {
+            None => continue,
+            Some(member_type) => member_type
+        };
+
+        let member_name: String = pascal_to_snake_case(&member.identifier);
+
+        // Proto field numbers start at 1, while the verifier field uses index 0
+        if member.index.value() == 0 {
+            warning!(
+                "Field {0}.{1} uses index 0, which is not a valid proto field number, and is not exported",
+                struct_definition.name,
+                member.identifier
+            );
+            proto_file.add_indented_line(1, format!("// Not exported: {0} {1} uses index 0, which is not a valid proto field number", member_type, member_name));
+            continue;
+        }
+
+        if let Some(comment) = &member.comment {
+            proto_file.add_doc_comment(1, comment);
+        }
+
+        if let FieldType::Primitive(Primitive::I128 | Primitive::U128) = member.data_type {
+            warning!(
+                "Field {0}.{1} is a 128 bit integer, which is exported as 16 bytes in host byte order",
+                struct_definition.name,
+                member.identifier
+            );
+            proto_file.add_indented_line(1, String::from("// 128 bit integer, as 16 bytes in host byte order"));
+        }
+
+        proto_file.add_indented_line(1, format!("{0} {1} = {2};", member_type, member_name, member.index.value()));
+    }
+
+    proto_file.add_line(String::from("}"));
+    proto_file.add_newline();
+}
+
+/// Outputs a proto3 schema with the structs as messages using the field indexes as field numbers, and the enums as enums
+pub fn output_proto(file_descriptions: &Vec<RuneFileDescription>, configurations: &CConfigurations, file_name: &str) -> Result<OutputFile, CompilerError> {
+    let mut proto_file: OutputFile = OutputFile::new(String::from(file_name), &configurations.compiler_configurations);
+
+    // Disclaimers
+    // ————————————
+
+    output_disclaimer(&mut proto_file, &configurations.compiler_configurations);
+
+    proto_file.add_line(String::from("// Protocol Buffers export of the Rune schema. Field numbers are the Rune field indexes"));
+    proto_file.add_newline();
+
+    proto_file.add_line(String::from("syntax = \"proto3\";"));
+    proto_file.add_newline();
+
+    for file in file_descriptions {
+        proto_file.add_line(format!("// {0}{1}.rune", file.relative_path, file.name));
+        proto_file.add_newline();
+
+        // Enums, bitfields & structs
+        // ———————————————————————————
+
+        for enum_definition in &file.definitions.enums {
+            output_proto_enum(&mut proto_file, enum_definition);
+        }
+
+        for bitfield_definition in &file.definitions.bitfields {
+            output_proto_bitfield_note(&mut proto_file, bitfield_definition);
+        }
+
+        for struct_definition in &file.definitions.structs {
+            output_proto_message(&mut proto_file, struct_definition, file_descriptions);
+        }
+    }
+
+    Ok(proto_file)
+}
+
+#[cfg(test)]
+mod tests {
+    use rune_parser::RuneFileDescription;
+
+    use crate::{
+        generate_to_memory,
+        test_utilities::{compile_configurations, parse_schema}
+    };
+
+    const SCHEMA: &str = "enum Mode: u8 {\n    Idle = 0;\n    Running = 2\n}\n\nbitfield Flags: u8 {\n    Enabled: u1 = 0;\n    Level: u3 = 1\n}\n\nstruct Point {\n    X: f32 = 1\n}\n\nstruct Frame {\n    Stamp: u64 = 2;\n    Valid: bool = 1;\n    Offset: i16 = 3;\n    Name: [char; 8] = 4;\n    Path: [Point; 2] = 5;\n    Current: Mode = 6;\n    State: Flags = 7;\n    Raw: [u8; 3] = 8;\n    Levels: [u16; 2] = 9;\n    Origin: Point = 10\n}\n";
+
+    /// Output the proto schema generated from the given Rune file
+    fn proto_schema(schema: &str) -> String {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("schema.rune", schema)]);
+        let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &compile_configurations(&["--emit-proto", "schema.proto"])).unwrap();
+
+        output_files.into_iter().find(|(name, _)| name == "schema.proto").expect("Proto schema should be generated").1
+    }
+
+    #[test]
+    fn fields_are_numbered_by_their_rune_index() {
+        let proto: String = proto_schema(SCHEMA);
+
+        assert!(proto.contains("syntax = \"proto3\";"));
+        assert!(proto.contains("message Point {\n    float x = 1;\n}"));
+        assert!(proto.contains(
+            "message Frame {\n    bool valid = 1;\n    uint64 stamp = 2;\n    int32 offset = 3;\n    string name = 4;\n    repeated Point path = 5;\n    Mode current = 6;\n    uint32 state = 7;\n    bytes raw = 8;\n    repeated uint32 levels = 9;\n    Point origin = 10;\n}"
+        ));
+    }
+
+    #[test]
+    fn enums_and_bitfields_are_exported() {
+        let proto: String = proto_schema(SCHEMA);
+
+        assert!(proto.contains("enum Mode {\n    MODE_IDLE = 0;\n    MODE_RUNNING = 2;\n}"));
+        assert!(proto.contains(
+            "// Bitfield Flags has no proto equivalent, and is exported as its backing integer uint32. Its members are, from the least significant bit:\n//     Enabled: u1\n//     Level: u3\n"
+        ));
+    }
+
+    #[test]
+    fn enums_without_zero_gain_an_unspecified_value() {
+        let proto: String = proto_schema("enum Mode: u8 {\n    Idle = 1;\n    Running = 2\n}\n");
+
+        assert!(proto.contains("enum Mode {\n    MODE_UNSPECIFIED = 0;\n    MODE_IDLE = 1;\n    MODE_RUNNING = 2;\n}"));
+    }
+
+    #[test]
+    fn verifier_fields_are_not_exported() {
+        let proto: String = proto_schema("struct Frame {\n    Value: u8 = 1;\n    Crc: u16 = verifier\n}\n");
+
+        assert!(proto.contains("message Frame {\n    // Not exported: uint32 crc uses index 0, which is not a valid proto field number\n    uint32 value = 1;\n}"));
+    }
+}
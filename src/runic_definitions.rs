@@ -1,19 +1,15 @@
-use std::path::Path;
-
-use rune_parser::{
-    RuneFileDescription,
-    types::{Primitive, StructDefinition}
-};
+use rune_parser::types::Primitive;
 
 use crate::{
     c_standard::CStandard,
-    c_utilities::{CConfigurations, CPrimitive},
+    c_utilities::{CConfigurations, CPrimitive, output_disclaimer},
     compile_error::CompilerError,
+    endianness::Endianness,
     output::*,
     output_file::OutputFile
 };
 
-fn type_from_size(size: usize, c_standard: &CStandard) -> Result<String, CompilerError> {
+pub fn type_from_size(size: usize, c_standard: &CStandard) -> Result<String, CompilerError> {
     match size {
         1 => Primitive::U8.to_c_type(c_standard),
         2 => Primitive::U16.to_c_type(c_standard),
@@ -26,7 +22,7 @@ fn type_from_size(size: usize, c_standard: &CStandard) -> Result<String, Compile
     }
 }
 
-pub fn output_runic_definitions(file_descriptions: &Vec<RuneFileDescription>, configurations: &CConfigurations, output_path: &Path) -> Result<(), CompilerError> {
+pub fn output_runic_definitions(configurations: &CConfigurations) -> Result<OutputFile, CompilerError> {
     let c_standard: &CStandard = &configurations.compiler_configurations.c_standard;
 
     let mut bitfield_attributes: String = String::with_capacity(0x100);
@@ -111,27 +107,30 @@ pub fn output_runic_definitions(file_descriptions: &Vec<RuneFileDescription>, co
         false => format!("__attribute__(({0}))", metadata_attributes)
     };
 
-    // Create a list with all declared structs across all files
-    let mut struct_definitions: Vec<StructDefinition> = Vec::with_capacity(0x40);
-
-    for file in file_descriptions {
-        if !file.definitions.structs.is_empty() {
-            struct_definitions.append(&mut file.definitions.structs.clone());
-        }
-    }
-
-    // Sort the list alphabetically
-    struct_definitions.sort_by(|a, b| a.name.to_ascii_uppercase().cmp(&b.name.to_ascii_uppercase()));
+    // MSVC and the ISO C standards do not support attributes, so structs are packed by #pragma pack around their declarations. MSVC
+    // is given the section with __declspec(allocate), which requires the section to be declared first
+    let (runic_bitfield_string, runic_enum_string, runic_parser_string, runic_struct_string, runic_metadata_string): (String, String, String, String, String) =
+        match configurations.compiler_configurations.allows_attributes() {
+            true => (runic_bitfield_string, runic_enum_string, runic_parser_string, runic_struct_string, runic_metadata_string),
+            false => {
+                let parser_string: String = match &configurations.compiler_configurations.section {
+                    Some(section_name) => format!("__declspec(allocate(\"{0}\"))", section_name),
+                    None => String::new()
+                };
+
+                (String::new(), String::new(), parser_string, String::new(), String::new())
+            }
+        };
 
     // Create output file
-    let definitions_file_string: String = String::from("runic_definitions.h");
+    let definitions_file_string: String = configurations.compiler_configurations.definitions_output.clone();
 
-    let mut definitions_file: OutputFile = OutputFile::new(String::from(output_path.to_str().unwrap()), definitions_file_string);
+    let mut definitions_file: OutputFile = OutputFile::new(definitions_file_string, &configurations.compiler_configurations);
 
     // Disclaimers
     // ————————————
 
-    // ...
+    output_disclaimer(&mut definitions_file, &configurations.compiler_configurations);
 
     // Definitions
     // ————————————
@@ -144,10 +143,20 @@ pub fn output_runic_definitions(file_descriptions: &Vec<RuneFileDescription>, co
     definitions_file.add_line("// ———————————————————".to_string());
     definitions_file.add_newline();
 
-    definitions_file.add_line("#define RUNE_FIELD_INDEX_BITS 0x1F".to_string());
-    definitions_file.add_line("#define RUNE_PACKAGING_BITS   0xE0".to_string());
+    definitions_file.add_line(format!("#define RUNE_FIELD_INDEX_BITS 0x1F{0}", configurations.compiler_configurations.unsigned_suffix()));
+    definitions_file.add_line(format!("#define RUNE_PACKAGING_BITS   0xE0{0}", configurations.compiler_configurations.unsigned_suffix()));
     definitions_file.add_newline();
 
+    // Booleans are only standardized from C99, so give them a named type before that
+    if !c_standard.allows_boolean() {
+        definitions_file.add_line("/** Boolean type, as <stdbool.h> is not available before C99 */".to_string());
+        definitions_file.add_line("typedef unsigned char rune_bool_t;".to_string());
+        definitions_file.add_newline();
+        definitions_file.add_line(format!("#define RUNE_TRUE  1{0}", configurations.compiler_configurations.unsigned_suffix()));
+        definitions_file.add_line(format!("#define RUNE_FALSE 0{0}", configurations.compiler_configurations.unsigned_suffix()));
+        definitions_file.add_newline();
+    }
+
     definitions_file.add_line("// Configuration dependent definitions".to_string());
     definitions_file.add_line("// ————————————————————————————————————".to_string());
     definitions_file.add_newline();
@@ -155,12 +164,87 @@ pub fn output_runic_definitions(file_descriptions: &Vec<RuneFileDescription>, co
     definitions_file.add_line("/* These definitions are based on the configurations passed by user to get code generator, such as packing, specific data sections, or other */".to_string());
     definitions_file.add_newline();
 
+    if !configurations.compiler_configurations.allows_attributes() {
+        match configurations.compiler_configurations.compiler.allows_attributes() {
+            true => definitions_file.add_line("/* Structs and bitfields are packed with #pragma pack around their declarations, as ISO C does not support attributes */".to_string()),
+            false => definitions_file.add_line("/* Structs and bitfields are packed with #pragma pack around their declarations, as MSVC does not support attributes */".to_string())
+        }
+
+        if let Some(section_name) = &configurations.compiler_configurations.section {
+            definitions_file.add_line(format!("#pragma section(\"{0}\", read)", section_name));
+        }
+        definitions_file.add_newline();
+    }
+
     definitions_file.add_line(format!("#define RUNIC_BITFIELD {0}", runic_bitfield_string));
     definitions_file.add_line(format!("#define RUNIC_ENUM     {0}", runic_enum_string));
     definitions_file.add_line(format!("#define RUNIC_PARSER   {0}", runic_parser_string));
     definitions_file.add_line(format!("#define RUNIC_STRUCT   {0}", runic_struct_string));
     definitions_file.add_newline();
 
+    // Bitfields only have the member order of the chosen byte order, so building for the other one must fail
+    let opposite_byte_order: Option<(&'static str, &'static str, &'static str)> = match configurations.compiler_configurations.endianness {
+        Endianness::Native => None,
+        Endianness::Little => Some(("little", "__BIG_ENDIAN__", "__ORDER_BIG_ENDIAN__")),
+        Endianness::Big => Some(("big", "__LITTLE_ENDIAN__", "__ORDER_LITTLE_ENDIAN__"))
+    };
+
+    if let Some((endianness, endian_macro, order_macro)) = opposite_byte_order {
+        definitions_file.add_line(format!("/* Generated for {0} endian targets */", endianness));
+        definitions_file.add_line(format!(
+            "#if defined {0} || (defined __BYTE_ORDER__ && defined {1} && __BYTE_ORDER__ == {1})",
+            endian_macro, order_macro
+        ));
+        definitions_file.add_line(format!("#error \"Rune code was generated for {0} endian targets\"", endianness));
+        definitions_file.add_line("#endif".to_string());
+        definitions_file.add_newline();
+    }
+
+    definitions_file.add_line("/** Defines whether descriptors contain the verifier parsing data */".to_string());
+    definitions_file.add_line(format!("#define RUNE_VERIFIER_SUPPORT {0}", configurations.compiler_configurations.verifier_support as usize));
+    definitions_file.add_newline();
+
+    // Alignment specifiers are underscored keywords before C23, which C++ does not have
+    if !configurations.compiler_configurations.member_alignments.is_empty() && !c_standard.allows_alignas_keyword() {
+        definitions_file.add_line("/** Alignment specifier of struct members, spelled as the keyword of the language including the header */".to_string());
+        definitions_file.add_line("#ifdef __cplusplus".to_string());
+        definitions_file.add_line("#define RUNE_ALIGNAS(alignment) alignas(alignment)".to_string());
+        definitions_file.add_line("#else".to_string());
+        definitions_file.add_line("#define RUNE_ALIGNAS(alignment) _Alignas(alignment)".to_string());
+        definitions_file.add_line("#endif".to_string());
+        definitions_file.add_newline();
+    }
+
+    if configurations.compiler_configurations.emits_functions() {
+        definitions_file.add_line("/** Marks generated functions whose return value should not be ignored. Placed before the declaration */".to_string());
+
+        // Standard attribute syntax is only valid from C23
+        if c_standard.allows_standard_attributes() {
+            definitions_file.add_line("#if defined __has_c_attribute".to_string());
+            definitions_file.add_line("#if __has_c_attribute(nodiscard)".to_string());
+            definitions_file.add_line("#define RUNE_NODISCARD [[nodiscard]]".to_string());
+            definitions_file.add_line("#endif".to_string());
+            definitions_file.add_line("#endif".to_string());
+        }
+
+        definitions_file.add_line("#if !defined RUNE_NODISCARD && (defined __GNUC__ || defined __clang__)".to_string());
+        definitions_file.add_line("#define RUNE_NODISCARD __attribute__((warn_unused_result))".to_string());
+        definitions_file.add_line("#endif".to_string());
+        definitions_file.add_line("#ifndef RUNE_NODISCARD".to_string());
+        definitions_file.add_line("#define RUNE_NODISCARD".to_string());
+        definitions_file.add_line("#endif".to_string());
+        definitions_file.add_newline();
+
+        if configurations.compiler_configurations.debug_checks {
+            definitions_file.add_line("/** Assertion used by the checks of generated functions, which are compiled in when RUNE_DEBUG is defined. Define it to replace it */".to_string());
+            definitions_file.add_line("#ifndef RUNE_ASSERT".to_string());
+            definitions_file.add_line("#include <assert.h>".to_string());
+            definitions_file.add_line("#define RUNE_ASSERT(condition) assert(condition)".to_string());
+            definitions_file.add_line("#endif".to_string());
+            definitions_file.add_newline();
+        }
+    }
+
     definitions_file.add_line("// Message dependent definitions".to_string());
     definitions_file.add_line("// ——————————————————————————————".to_string());
     definitions_file.add_newline();
@@ -198,18 +282,135 @@ pub fn output_runic_definitions(file_descriptions: &Vec<RuneFileDescription>, co
     ));
     definitions_file.add_line(format!(
         "#define RUNE_FIELD_INFO_COUNT {0}",
-        match c_standard.allows_flexible_array_members() {
+        match c_standard.allows_flexible_array_initializers() {
             true => String::new(),
-            false => (configurations.largest_message_index + 1).to_string()
+            false => format!("{0}{1}", configurations.largest_message_index + 1, configurations.compiler_configurations.unsigned_suffix())
         }
     ));
     definitions_file.add_newline();
 
+    // Every struct of the generated files has exactly one descriptor
+    if configurations.compiler_configurations.descriptors {
+        definitions_file.add_line("/** Amount of struct descriptors across all generated files */".to_string());
+        definitions_file.add_line(format!(
+            "#define RUNE_PARSER_COUNT {0}{1}",
+            configurations.message_count,
+            configurations.compiler_configurations.unsigned_suffix()
+        ));
+        definitions_file.add_newline();
+    }
+
     definitions_file.add_line("/** Defines whether and how metadata generated by the rune compiler should be packed optimized */".to_string());
     definitions_file.add_line(format!("#define RUNIC_METADATA {0}", runic_metadata_string));
     definitions_file.add_newline();
 
     definitions_file.add_line("#endif // RUNIC_DEFINITIONS_H".to_string());
 
-    definitions_file.output_file()
+    Ok(definitions_file)
+}
+
+#[cfg(test)]
+mod tests {
+    use rune_parser::RuneFileDescription;
+
+    use crate::{
+        c_utilities::CConfigurations,
+        generate_to_memory,
+        runic_definitions::output_runic_definitions,
+        test_utilities::{compile_configurations, parse_schema, parse_single_schema}
+    };
+
+    const SWITCH_SCHEMA: &str = "struct Switch {\n    Active: bool = 1\n}\n";
+
+    fn runic_definitions(arguments: &[&str]) -> String {
+        let (_, configurations): (Vec<RuneFileDescription>, CConfigurations) = parse_single_schema(SWITCH_SCHEMA, arguments);
+
+        output_runic_definitions(&configurations).unwrap().into_contents()
+    }
+
+    #[test]
+    fn boolean_type_is_defined_before_c99() {
+        let definitions: String = runic_definitions(&["--c-standard", "C89"]);
+
+        assert!(definitions.contains("typedef unsigned char rune_bool_t;"));
+        assert!(definitions.contains("#define RUNE_TRUE  1"));
+        assert!(definitions.contains("#define RUNE_FALSE 0"));
+    }
+
+    #[test]
+    fn boolean_type_is_not_defined_from_c99() {
+        assert!(!runic_definitions(&["--c-standard", "C99"]).contains("rune_bool_t"));
+    }
+
+    const COMMON_SCHEMA: &str = "struct Point {\n    X: i16 = 1\n}\n\nstruct Size {\n    Width: u16 = 1\n}\n";
+    const DEVICE_SCHEMA: &str = "include \"common.rune\";\n\nstruct Device {\n    Origin: Point = 1\n}\n";
+
+    /// Generate the files of a schema split over two files, and get the generated runic_definitions.h
+    fn generated_definitions(arguments: &[&str]) -> String {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("common.rune", COMMON_SCHEMA), ("device.rune", DEVICE_SCHEMA)]);
+        let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &compile_configurations(arguments)).unwrap();
+
+        output_files.into_iter().find(|(name, _)| name == "runic_definitions.h").unwrap().1
+    }
+
+    #[test]
+    fn parser_count_is_the_amount_of_structs_across_all_files() {
+        assert!(generated_definitions(&[]).contains("#define RUNE_PARSER_COUNT 3\n"));
+    }
+
+    #[test]
+    fn parser_count_follows_the_selected_structs() {
+        // Device depends on Point, so both are output
+        assert!(generated_definitions(&["--only", "Device"]).contains("#define RUNE_PARSER_COUNT 2\n"));
+    }
+
+    #[test]
+    fn parser_count_is_left_out_without_descriptors() {
+        assert!(!generated_definitions(&["--no-descriptors"]).contains("RUNE_PARSER_COUNT"));
+    }
+
+    #[test]
+    fn verifier_support_flag_follows_the_configuration() {
+        assert!(runic_definitions(&[]).contains("#define RUNE_VERIFIER_SUPPORT 1"));
+        assert!(runic_definitions(&["--no-verifier-support"]).contains("#define RUNE_VERIFIER_SUPPORT 0"));
+    }
+
+    #[test]
+    fn nodiscard_is_only_defined_when_functions_are_emitted() {
+        assert!(!runic_definitions(&[]).contains("RUNE_NODISCARD"));
+
+        for arguments in [
+            &["--emit-codec"][..],
+            &["--emit-getters"],
+            &["--emit-named-fields"],
+            &["--emit-verifiers"],
+            &["--tlv-mode"],
+            &["--pack-data", "--safe-packed-access"]
+        ] {
+            assert!(
+                runic_definitions(arguments).contains("#ifndef RUNE_NODISCARD\n#define RUNE_NODISCARD\n#endif"),
+                "{0:?} should define RUNE_NODISCARD",
+                arguments
+            );
+        }
+    }
+
+    #[test]
+    fn nodiscard_uses_the_standard_attribute_from_c23() {
+        let definitions: String = runic_definitions(&["--emit-codec", "--c-standard", "C23"]);
+
+        assert!(definitions.contains(
+            "#if defined __has_c_attribute\n#if __has_c_attribute(nodiscard)\n#define RUNE_NODISCARD [[nodiscard]]\n#endif\n#endif\n#if !defined RUNE_NODISCARD && (defined __GNUC__ || defined __clang__)\n#define RUNE_NODISCARD __attribute__((warn_unused_result))\n#endif\n#ifndef RUNE_NODISCARD\n#define RUNE_NODISCARD\n#endif\n"
+        ));
+    }
+
+    #[test]
+    fn nodiscard_falls_back_on_the_gcc_attribute_before_c23() {
+        let definitions: String = runic_definitions(&["--emit-codec", "--c-standard", "C11"]);
+
+        assert!(!definitions.contains("[[nodiscard]]"));
+        assert!(definitions.contains(
+            "#if !defined RUNE_NODISCARD && (defined __GNUC__ || defined __clang__)\n#define RUNE_NODISCARD __attribute__((warn_unused_result))\n#endif\n#ifndef RUNE_NODISCARD\n#define RUNE_NODISCARD\n#endif\n"
+        ));
+    }
 }
@@ -0,0 +1,89 @@
+use crate::{
+    c_utilities::{CConfigurations, CompileConfigurations, output_disclaimer},
+    compile_error::CompilerError,
+    output_file::OutputFile,
+    runic_definitions::type_from_size
+};
+
+/// Outputs a rune.h header defining the descriptor types the generated sources fill in, for using the generated code without
+/// the Rune library. The field types follow the sizes chosen for the runic definitions
+pub fn output_rune_runtime_header(configurations: &CConfigurations) -> Result<OutputFile, CompilerError> {
+    let compiler_configurations: &CompileConfigurations = &configurations.compiler_configurations;
+    let c_standard = &compiler_configurations.c_standard;
+
+    let mut runtime_file: OutputFile = OutputFile::new(String::from("rune.h"), compiler_configurations);
+
+    // Disclaimers
+    // ————————————
+
+    output_disclaimer(&mut runtime_file, compiler_configurations);
+
+    runtime_file.add_line(String::from("#ifndef RUNE_H"));
+    runtime_file.add_line(String::from("#define RUNE_H"));
+    runtime_file.add_newline();
+
+    runtime_file.add_line(String::from("#ifdef __cplusplus"));
+    runtime_file.add_line(String::from("extern \"C\" {"));
+    runtime_file.add_line(String::from("#endif /* __cplusplus */"));
+    runtime_file.add_newline();
+
+    runtime_file.add_line(String::from("#include <stddef.h>"));
+    if c_standard.allows_integer_types() {
+        runtime_file.add_line(String::from("#include <stdint.h>"));
+    }
+    if c_standard.allows_boolean() {
+        runtime_file.add_line(String::from("#include <stdbool.h>"));
+    }
+    runtime_file.add_newline();
+
+    runtime_file.add_line(format!("#include \"{0}\"", compiler_configurations.definitions_output));
+    runtime_file.add_newline();
+
+    // Descriptor types
+    // —————————————————
+
+    let boolean_type: &'static str = match c_standard.allows_boolean() {
+        true => "bool",
+        false => "rune_bool_t"
+    };
+
+    runtime_file.add_line(String::from("/** Placement of a single field within its struct. Fields of size 0 are not part of the struct */"));
+    runtime_file.add_line(String::from("typedef struct RUNIC_METADATA rune_field_info_s {"));
+    runtime_file.add_indented_line(1, String::from("RUNE_FIELD_OFFSET_TYPE offset;"));
+    runtime_file.add_indented_line(1, String::from("RUNE_FIELD_SIZE_TYPE size;"));
+    runtime_file.add_line(String::from("} rune_field_info_t;"));
+    runtime_file.add_newline();
+
+    runtime_file.add_line(String::from("#if RUNE_VERIFIER_SUPPORT"));
+    runtime_file.add_line(String::from("/** Data used while parsing a struct */"));
+    runtime_file.add_line(String::from("typedef struct RUNIC_METADATA rune_parsing_data_s {"));
+    runtime_file.add_indented_line(1, format!("{0} has_verification;", boolean_type));
+    runtime_file.add_line(String::from("} rune_parsing_data_t;"));
+    runtime_file.add_line(String::from("#endif"));
+    runtime_file.add_newline();
+
+    runtime_file.add_line(String::from("/** Describes the fields of a struct, and the descriptors of the structs nested in it */"));
+    runtime_file.add_line(String::from("typedef struct RUNIC_METADATA rune_descriptor_s {"));
+    runtime_file.add_indented_line(1, String::from("/** Bit set for every field index holding a nested struct */"));
+    runtime_file.add_indented_line(1, format!("{0} descriptor_flags;", type_from_size(4, c_standard)?));
+    runtime_file.add_indented_line(1, String::from("/** Descriptors of the nested structs, in field index order. NULL if there are none */"));
+    runtime_file.add_indented_line(1, String::from("const struct rune_descriptor_s* (*field_descriptors)[];"));
+    runtime_file.add_indented_line(1, String::from("RUNE_MESSAGE_SIZE_TYPE size;"));
+    runtime_file.add_indented_line(1, format!("{0} largest_field;", type_from_size(1, c_standard)?));
+    runtime_file.add_line(String::from("#if RUNE_VERIFIER_SUPPORT"));
+    runtime_file.add_indented_line(1, String::from("rune_parsing_data_t parsing_data;"));
+    runtime_file.add_line(String::from("#endif"));
+    runtime_file.add_indented_line(1, String::from("/** Placement of every field, indexed by field index */"));
+    runtime_file.add_indented_line(1, String::from("rune_field_info_t field_info[RUNE_FIELD_INFO_COUNT];"));
+    runtime_file.add_line(String::from("} rune_descriptor_t;"));
+    runtime_file.add_newline();
+
+    runtime_file.add_line(String::from("#ifdef __cplusplus"));
+    runtime_file.add_line(String::from("}"));
+    runtime_file.add_line(String::from("#endif /* __cplusplus */"));
+    runtime_file.add_newline();
+
+    runtime_file.add_line(String::from("#endif /* RUNE_H */"));
+
+    Ok(runtime_file)
+}
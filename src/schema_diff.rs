@@ -0,0 +1,344 @@
+use rune_parser::{
+    RuneFileDescription,
+    types::{EnumDefinition, StructDefinition, StructMember}
+};
+
+use crate::{
+    c_utilities::{CPrimitive, CStructDefinition, CStructMember, CompileConfigurations, sorted_struct_definitions},
+    compile_error::CompilerError,
+    output::*
+};
+
+/// How a schema change affects communication between the old and the new schema
+#[derive(Debug, PartialEq)]
+enum ChangeSeverity {
+    /// Old and new messages remain wire compatible
+    Compatible,
+    /// Wire compatible, but values may be interpreted differently
+    Warning,
+    /// Old and new messages are not wire compatible
+    Breaking
+}
+
+struct SchemaChange {
+    severity:    ChangeSeverity,
+    description: String
+}
+
+/// Changes found for a single definition
+struct DefinitionChanges {
+    definition: String,
+    changes:    Vec<SchemaChange>
+}
+
+impl DefinitionChanges {
+    fn new(definition: String) -> DefinitionChanges {
+        DefinitionChanges {
+            definition,
+            changes: Vec::with_capacity(0x10)
+        }
+    }
+
+    fn add(&mut self, severity: ChangeSeverity, description: String) {
+        self.changes.push(SchemaChange { severity, description });
+    }
+}
+
+fn collect_enum_definitions(file_descriptions: &Vec<RuneFileDescription>) -> Vec<EnumDefinition> {
+    let mut enum_definitions: Vec<EnumDefinition> = Vec::with_capacity(0x40);
+
+    for file in file_descriptions {
+        enum_definitions.append(&mut file.definitions.enums.clone());
+    }
+
+    enum_definitions
+}
+
+/// Compare the members of a struct present in both schemas
+fn diff_struct_members(old_struct: &StructDefinition, new_struct: &StructDefinition, changes: &mut DefinitionChanges) -> Result<(), CompilerError> {
+    for old_member in &old_struct.members {
+        let new_member: &StructMember = match new_struct.members.iter().find(|member| member.identifier == old_member.identifier) {
+            Some(new_member) => new_member,
+            None => {
+                changes.add(ChangeSeverity::Breaking, format!("field {0} removed from index {1}", old_member.identifier, old_member.index.value()));
+                continue;
+            }
+        };
+
+        if old_member.index.value() != new_member.index.value() || old_member.index.is_verifier() != new_member.index.is_verifier() {
+            changes.add(
+                ChangeSeverity::Breaking,
+                format!("field {0} moved from index {1} to {2}", old_member.identifier, old_member.index.value(), new_member.index.value())
+            );
+        }
+
+        if old_member.data_type != new_member.data_type {
+            let old_size: u64 = old_member.c_size()?;
+            let new_size: u64 = new_member.c_size()?;
+
+            match old_size == new_size {
+                true => changes.add(
+                    ChangeSeverity::Warning,
+                    format!(
+                        "field {0} changed type from {1:?} to {2:?} with the same width",
+                        old_member.identifier, old_member.data_type, new_member.data_type
+                    )
+                ),
+                false => changes.add(
+                    ChangeSeverity::Breaking,
+                    format!(
+                        "field {0} changed type from {1:?} ({2} bytes) to {3:?} ({4} bytes)",
+                        old_member.identifier, old_member.data_type, old_size, new_member.data_type, new_size
+                    )
+                )
+            }
+        }
+    }
+
+    for new_member in &new_struct.members {
+        if old_struct.members.iter().any(|member| member.identifier == new_member.identifier) {
+            continue;
+        }
+
+        // Reusing the index of another field makes old senders populate the new field with unrelated data
+        match old_struct.members.iter().find(|member| member.index.value() == new_member.index.value()) {
+            Some(old_member) => changes.add(
+                ChangeSeverity::Breaking,
+                format!(
+                    "field {0} reuses index {1}, previously used by {2}",
+                    new_member.identifier,
+                    new_member.index.value(),
+                    old_member.identifier
+                )
+            ),
+            None => changes.add(ChangeSeverity::Compatible, format!("field {0} added at index {1}", new_member.identifier, new_member.index.value()))
+        }
+    }
+
+    Ok(())
+}
+
+/// Compare the members of an enum present in both schemas
+fn diff_enum_members(old_enum: &EnumDefinition, new_enum: &EnumDefinition, changes: &mut DefinitionChanges) {
+    if old_enum.backing_type.c_size() != new_enum.backing_type.c_size() {
+        changes.add(
+            ChangeSeverity::Breaking,
+            format!("backing type changed from {0} to {1} bytes", old_enum.backing_type.c_size(), new_enum.backing_type.c_size())
+        );
+    }
+
+    for old_member in &old_enum.members {
+        match new_enum.members.iter().find(|member| member.identifier == old_member.identifier) {
+            None => changes.add(ChangeSeverity::Breaking, format!("member {0} removed", old_member.identifier)),
+            Some(new_member) if new_member.value != old_member.value => changes.add(
+                ChangeSeverity::Breaking,
+                format!("member {0} changed value from {1} to {2}", old_member.identifier, old_member.value, new_member.value)
+            ),
+            Some(_) => ()
+        }
+    }
+
+    for new_member in &new_enum.members {
+        if !old_enum.members.iter().any(|member| member.identifier == new_member.identifier) {
+            changes.add(ChangeSeverity::Compatible, format!("member {0} added with value {1}", new_member.identifier, new_member.value));
+        }
+    }
+}
+
+/// Compare an old schema against the current one, collecting the changes per definition
+fn find_schema_changes(
+    old_descriptions: &Vec<RuneFileDescription>,
+    new_descriptions: &Vec<RuneFileDescription>,
+    configurations: &CompileConfigurations
+) -> Result<Vec<DefinitionChanges>, CompilerError> {
+    let mut definition_changes: Vec<DefinitionChanges> = Vec::with_capacity(0x40);
+
+    // Structs
+    // ————————
+
+    let old_structs: Vec<StructDefinition> = sorted_struct_definitions(old_descriptions);
+    let new_structs: Vec<StructDefinition> = sorted_struct_definitions(new_descriptions);
+
+    for old_struct in &old_structs {
+        if !new_structs.iter().any(|definition| definition.name == old_struct.name) {
+            let mut changes: DefinitionChanges = DefinitionChanges::new(format!("struct {0}", old_struct.name));
+            changes.add(ChangeSeverity::Breaking, String::from("struct removed"));
+            definition_changes.push(changes);
+        }
+    }
+
+    for (new_index, new_struct) in new_structs.iter().enumerate() {
+        let mut changes: DefinitionChanges = DefinitionChanges::new(format!("struct {0}", new_struct.name));
+
+        match old_structs.iter().position(|definition| definition.name == new_struct.name) {
+            None => changes.add(ChangeSeverity::Compatible, format!("struct added with message index {0}", new_index)),
+            Some(old_index) => {
+                let old_struct: &StructDefinition = &old_structs[old_index];
+
+                // Message indexes follow the alphabetical order of all structs
+                if old_index != new_index {
+                    changes.add(ChangeSeverity::Breaking, format!("message index changed from {0} to {1}", old_index, new_index));
+                }
+
+                diff_struct_members(old_struct, new_struct, &mut changes)?;
+
+                let old_size: u64 = old_struct.estimate_size(configurations)?;
+                let new_size: u64 = new_struct.estimate_size(configurations)?;
+
+                // Buffers and tables sized for the old struct no longer match the new one
+                if old_size != new_size {
+                    changes.add(ChangeSeverity::Warning, format!("size changed from {0} to {1} bytes", old_size, new_size));
+                }
+            }
+        }
+
+        if !changes.changes.is_empty() {
+            definition_changes.push(changes);
+        }
+    }
+
+    // Enums
+    // ——————
+
+    let old_enums: Vec<EnumDefinition> = collect_enum_definitions(old_descriptions);
+    let new_enums: Vec<EnumDefinition> = collect_enum_definitions(new_descriptions);
+
+    for old_enum in &old_enums {
+        let mut changes: DefinitionChanges = DefinitionChanges::new(format!("enum {0}", old_enum.name));
+
+        match new_enums.iter().find(|definition| definition.name == old_enum.name) {
+            None => changes.add(ChangeSeverity::Breaking, String::from("enum removed")),
+            Some(new_enum) => diff_enum_members(old_enum, new_enum, &mut changes)
+        }
+
+        if !changes.changes.is_empty() {
+            definition_changes.push(changes);
+        }
+    }
+
+    for new_enum in &new_enums {
+        if !old_enums.iter().any(|definition| definition.name == new_enum.name) {
+            let mut changes: DefinitionChanges = DefinitionChanges::new(format!("enum {0}", new_enum.name));
+            changes.add(ChangeSeverity::Compatible, String::from("enum added"));
+            definition_changes.push(changes);
+        }
+    }
+
+    Ok(definition_changes)
+}
+
+/// Compare an old schema against the current one, printing the changes per definition. The report is the requested output, so it is
+/// printed regardless of the log level. Returns an error if any change breaks wire compatibility
+pub fn diff_schemas(old_descriptions: &Vec<RuneFileDescription>, new_descriptions: &Vec<RuneFileDescription>, configurations: &CompileConfigurations) -> Result<(), CompilerError> {
+    let definition_changes: Vec<DefinitionChanges> = find_schema_changes(old_descriptions, new_descriptions, configurations)?;
+
+    if definition_changes.is_empty() {
+        println!("No schema changes found");
+        return Ok(());
+    }
+
+    let mut breaking_changes: usize = 0;
+
+    println!("Schema changes:");
+    for definition in &definition_changes {
+        println!("    {0}", definition.definition);
+
+        for change in &definition.changes {
+            let tag: &'static str = match change.severity {
+                ChangeSeverity::Compatible => "[compatible]",
+                ChangeSeverity::Warning => "[warning]   ",
+                ChangeSeverity::Breaking => "[breaking]  "
+            };
+
+            if change.severity == ChangeSeverity::Breaking {
+                breaking_changes += 1;
+            }
+
+            println!("        {0} {1}", tag, change.description);
+        }
+    }
+
+    match breaking_changes {
+        0 => {
+            println!("Schema changes are wire compatible");
+            Ok(())
+        },
+        _ => {
+            error!("Found {0} wire incompatible schema changes!", breaking_changes);
+            Err(CompilerError::IncompatibleSchema)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rune_parser::RuneFileDescription;
+
+    use crate::{
+        schema_diff::{ChangeSeverity, DefinitionChanges, find_schema_changes},
+        test_utilities::{compile_configurations, parse_schema}
+    };
+
+    const OLD_SCHEMA: &str = "struct Reading {\n    Value: u16 = 1;\n    Unit: u8 = 2;\n    Flags: u8 = 3\n}\n";
+
+    /// Severity and description of every change between the old schema and the given new one
+    fn changes(new_schema: &str) -> Vec<(ChangeSeverity, String)> {
+        let old_descriptions: Vec<RuneFileDescription> = parse_schema(&[("reading.rune", OLD_SCHEMA)]);
+        let new_descriptions: Vec<RuneFileDescription> = parse_schema(&[("reading.rune", new_schema)]);
+
+        let definition_changes: Vec<DefinitionChanges> = find_schema_changes(&old_descriptions, &new_descriptions, &compile_configurations(&[])).unwrap();
+
+        definition_changes
+            .into_iter()
+            .flat_map(|definition| definition.changes)
+            .map(|change| (change.severity, change.description))
+            .collect()
+    }
+
+    #[test]
+    fn unchanged_schema_has_no_changes() {
+        assert!(changes(OLD_SCHEMA).is_empty());
+    }
+
+    #[test]
+    fn reused_index_is_breaking() {
+        let changes: Vec<(ChangeSeverity, String)> = changes("struct Reading {\n    Value: u16 = 1;\n    Unit: u8 = 2;\n    Mode: u8 = 3\n}\n");
+
+        assert!(changes.contains(&(ChangeSeverity::Breaking, String::from("field Flags removed from index 3"))));
+        assert!(changes.contains(&(ChangeSeverity::Breaking, String::from("field Mode reuses index 3, previously used by Flags"))));
+    }
+
+    #[test]
+    fn width_change_is_breaking() {
+        let changes: Vec<(ChangeSeverity, String)> = changes("struct Reading {\n    Value: u32 = 1;\n    Unit: u8 = 2;\n    Flags: u8 = 3\n}\n");
+
+        assert!(
+            changes
+                .iter()
+                .any(|(severity, description)| *severity == ChangeSeverity::Breaking && description.starts_with("field Value changed type"))
+        );
+    }
+
+    #[test]
+    fn same_width_type_change_is_warning() {
+        let changes: Vec<(ChangeSeverity, String)> = changes("struct Reading {\n    Value: i16 = 1;\n    Unit: u8 = 2;\n    Flags: u8 = 3\n}\n");
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].0, ChangeSeverity::Warning);
+    }
+
+    #[test]
+    fn removed_field_is_breaking() {
+        let changes: Vec<(ChangeSeverity, String)> = changes("struct Reading {\n    Value: u16 = 1;\n    Unit: u8 = 2\n}\n");
+
+        assert!(changes.contains(&(ChangeSeverity::Breaking, String::from("field Flags removed from index 3"))));
+    }
+
+    #[test]
+    fn size_change_is_warning() {
+        let changes: Vec<(ChangeSeverity, String)> = changes("struct Reading {\n    Value: u16 = 1;\n    Unit: u8 = 2;\n    Flags: u8 = 3;\n    Extra: u32 = 4\n}\n");
+
+        assert!(changes.contains(&(ChangeSeverity::Compatible, String::from("field Extra added at index 4"))));
+        assert!(changes.contains(&(ChangeSeverity::Warning, String::from("size changed from 4 to 8 bytes"))));
+    }
+}
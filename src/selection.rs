@@ -0,0 +1,413 @@
+use std::{
+    fs::read_to_string,
+    path::{Path, PathBuf}
+};
+
+use rune_parser::{
+    RuneFileDescription,
+    types::{StructDefinition, UserDefinitionLink}
+};
+
+use crate::{
+    c_utilities::{CompileConfigurations, is_included_file, sorted_struct_definitions},
+    compile_error::CompilerError,
+    output::*,
+    output_file::join_relative_path
+};
+
+/// Name of the file listing the patterns of the Rune files to ignore in an input folder
+const RUNE_IGNORE_NAME: &str = ".runeignore";
+
+/// Check that every struct name passed to an option is declared
+fn validate_struct_names(option: &str, names: &[String], struct_definitions: &[StructDefinition]) -> Result<(), CompilerError> {
+    for name in names {
+        if !struct_definitions.iter().any(|definition| &definition.name == name) {
+            error!("Struct {0} passed to {1} is not declared in any Rune file!", name, option);
+            return Err(CompilerError::InvalidArgument);
+        }
+    }
+
+    Ok(())
+}
+
+/// Add the struct and every struct it transitively depends on to the selection
+fn select_with_dependencies(name: &str, struct_definitions: &[StructDefinition], selection: &mut Vec<String>) {
+    if selection.iter().any(|selected| selected == name) {
+        return;
+    }
+
+    selection.push(String::from(name));
+
+    let struct_definition: &StructDefinition = match struct_definitions.iter().find(|definition| definition.name == name) {
+        Some(definition) => definition,
+        None => return
+    };
+
+    for member in &struct_definition.members {
+        if let UserDefinitionLink::StructLink(dependency) = &member.user_definition_link {
+            select_with_dependencies(&dependency.name, struct_definitions, selection);
+        }
+    }
+}
+
+/// Get the files declaring any of the given structs
+pub fn declaring_files<'a>(file_descriptions: &'a [RuneFileDescription], names: &[String]) -> Vec<&'a RuneFileDescription> {
+    file_descriptions
+        .iter()
+        .filter(|file| file.definitions.structs.iter().any(|definition| names.contains(&definition.name)))
+        .collect()
+}
+
+/// Get the names of the structs to output. These are the structs passed with --only (or all of them), except the ones passed with
+/// --exclude, together with all structs they depend on
+pub fn select_structs(file_descriptions: &Vec<RuneFileDescription>, configurations: &CompileConfigurations) -> Result<Vec<String>, CompilerError> {
+    let struct_definitions: Vec<StructDefinition> = sorted_struct_definitions(file_descriptions);
+
+    validate_struct_names("--only", &configurations.only, &struct_definitions)?;
+    validate_struct_names("--exclude", &configurations.exclude, &struct_definitions)?;
+
+    let mut selection: Vec<String> = Vec::with_capacity(struct_definitions.len());
+
+    for struct_definition in &struct_definitions {
+        let requested: bool = configurations.only.is_empty() || configurations.only.contains(&struct_definition.name);
+
+        if requested && !configurations.exclude.contains(&struct_definition.name) {
+            select_with_dependencies(&struct_definition.name, &struct_definitions, &mut selection);
+        }
+    }
+
+    // Dependencies cannot be left out, as the structs using them would not compile
+    for name in &configurations.exclude {
+        if selection.contains(name) {
+            warning!("Struct {0} was excluded, but is still output as other selected structs depend on it", name);
+        }
+    }
+
+    Ok(selection)
+}
+
+/// Check whether a path matches a glob pattern. '*' and '?' match within a directory, while '**' also matches across directories
+fn glob_matches(pattern: &[u8], path: &[u8]) -> bool {
+    match pattern {
+        [] => path.is_empty(),
+        // A '**/' also matches no directory at all, so a/**/b matches a/b
+        [b'*', b'*', b'/', rest @ ..] => (0..=path.len()).filter(|&i| i == 0 || path[i - 1] == b'/').any(|i| glob_matches(rest, &path[i..])),
+        [b'*', b'*', rest @ ..] => (0..=path.len()).any(|i| glob_matches(rest, &path[i..])),
+        [b'*', rest @ ..] => (0..=path.len()).take_while(|&i| i == 0 || path[i - 1] != b'/').any(|i| glob_matches(rest, &path[i..])),
+        [b'?', rest @ ..] => matches!(path, [character, path_rest @ ..] if *character != b'/' && glob_matches(rest, path_rest)),
+        [character, rest @ ..] => matches!(path, [path_character, path_rest @ ..] if path_character == character && glob_matches(rest, path_rest))
+    }
+}
+
+/// Check whether the path of a Rune file, relative to its input folder, matches a file pattern. Patterns containing a '/' match the
+/// whole path, and all other patterns only the file name. A leading '/' anchors the pattern to the input folder
+fn matches_path_pattern(path: &str, pattern: &str) -> bool {
+    let file_name: &str = path.rsplit('/').next().unwrap_or(path);
+
+    match (pattern.strip_prefix('/'), pattern.contains('/')) {
+        (Some(anchored_pattern), _) => glob_matches(anchored_pattern.as_bytes(), path.as_bytes()),
+        (None, true) => glob_matches(pattern.as_bytes(), path.as_bytes()),
+        (None, false) => glob_matches(pattern.as_bytes(), file_name.as_bytes())
+    }
+}
+
+/// Check whether a file matches an --exclude-files pattern
+fn matches_file_pattern(file: &RuneFileDescription, pattern: &str) -> bool {
+    matches_path_pattern(&format!("{0}{1}.rune", file.relative_path, file.name), pattern)
+}
+
+/// Read the patterns of the .runeignore file in an input folder, one per line. Blank lines and lines starting with '#' are skipped.
+/// Folders without a .runeignore file ignore nothing
+pub fn read_ignore_patterns(input_path: &Path) -> Result<Vec<String>, CompilerError> {
+    let ignore_path: PathBuf = input_path.join(RUNE_IGNORE_NAME);
+
+    if !ignore_path.is_file() {
+        return Ok(Vec::new());
+    }
+
+    let contents: String = match read_to_string(&ignore_path) {
+        Err(error) => {
+            error!("Could not read {0:?}. Got error {1}", ignore_path, error);
+            return Err(CompilerError::FileSystemError(error));
+        },
+        Ok(contents) => contents
+    };
+
+    Ok(contents.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')).map(String::from).collect())
+}
+
+/// Whether a Rune file, given by its path relative to the input folders, is ignored by the .runeignore file of the input folder it is in
+pub fn is_ignored_path(path: &str, input_paths: &[&Path], ignore_patterns: &[Vec<String>]) -> bool {
+    input_paths
+        .iter()
+        .zip(ignore_patterns)
+        .any(|(input_path, patterns)| join_relative_path(input_path, path).is_file() && patterns.iter().any(|pattern| matches_path_pattern(path, pattern)))
+}
+
+/// Remove the files ignored by a .runeignore file. The Rune parser searches the input folders itself, so ignored files are parsed,
+/// and only dropped afterwards. Files still including an ignored file are rejected, as their definitions could not be output
+pub fn remove_ignored_files(file_descriptions: &mut Vec<RuneFileDescription>, input_paths: &[&Path], ignore_patterns: &[Vec<String>]) -> Result<(), CompilerError> {
+    let (ignored_files, kept_files): (Vec<RuneFileDescription>, Vec<RuneFileDescription>) = file_descriptions
+        .drain(..)
+        .partition(|file| is_ignored_path(&format!("{0}{1}.rune", file.relative_path, file.name), input_paths, ignore_patterns));
+
+    for file in &kept_files {
+        for include_definition in &file.definitions.includes {
+            if let Some(ignored_file) = ignored_files.iter().find(|ignored_file| is_included_file(ignored_file, include_definition)) {
+                error!(
+                    "File {0}{1}.rune includes {2}{3}.rune, which is ignored by {4}!",
+                    file.relative_path, file.name, ignored_file.relative_path, ignored_file.name, RUNE_IGNORE_NAME
+                );
+                return Err(CompilerError::ConfigurationError);
+            }
+        }
+    }
+
+    for file in &ignored_files {
+        debug!("Ignoring {0}{1}.rune, as it matches a pattern in {2}", file.relative_path, file.name, RUNE_IGNORE_NAME);
+    }
+
+    *file_descriptions = kept_files;
+
+    Ok(())
+}
+
+/// Whether the output of a file is skipped, as it matches one of the --exclude-files patterns
+pub fn is_excluded_file(file: &RuneFileDescription, configurations: &CompileConfigurations) -> bool {
+    configurations.exclude_files.iter().any(|pattern| matches_file_pattern(file, pattern))
+}
+
+/// Warn about --exclude-files patterns matching no file, which are most likely misspelled
+pub fn validate_excluded_files(file_descriptions: &[RuneFileDescription], configurations: &CompileConfigurations) {
+    for pattern in &configurations.exclude_files {
+        if !file_descriptions.iter().any(|file| matches_file_pattern(file, pattern)) {
+            warning!("Pattern \"{0}\" passed to --exclude-files matches no Rune file", pattern);
+        }
+    }
+}
+
+/// Get a copy of the file descriptions, without the structs which are not part of the selection
+pub fn filter_structs(file_descriptions: &[RuneFileDescription], selection: &[String]) -> Vec<RuneFileDescription> {
+    let mut filtered_descriptions: Vec<RuneFileDescription> = file_descriptions.to_vec();
+
+    for file in &mut filtered_descriptions {
+        file.definitions.structs.retain(|definition| {
+            let selected: bool = selection.contains(&definition.name);
+
+            if !selected {
+                debug!("Omitting struct {0}, as it is not selected", definition.name);
+            }
+
+            selected
+        });
+    }
+
+    filtered_descriptions
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::remove_dir_all,
+        path::{Path, PathBuf}
+    };
+
+    use rune_parser::{RuneFileDescription, parser_rune_files};
+
+    use crate::{
+        compile_error::CompilerError,
+        generate_to_memory,
+        selection::{filter_structs, glob_matches, is_excluded_file, is_ignored_path, read_ignore_patterns, remove_ignored_files, select_structs},
+        test_utilities::{compile_configurations, parse_schema, write_schema}
+    };
+
+    const IGNORE_FILE: &str = "# Vendored schemas\n/legacy.rune\n\n   \n**/draft_*.rune\n";
+    const STRUCT_SCHEMA: &str = "struct Value {\n    X: u8 = 1\n}\n";
+
+    #[test]
+    fn ignore_files_skip_comments_and_blank_lines() {
+        let schema_folder: PathBuf = write_schema(&[(".runeignore", IGNORE_FILE)]);
+
+        assert_eq!(read_ignore_patterns(&schema_folder).unwrap(), ["/legacy.rune", "**/draft_*.rune"]);
+
+        remove_dir_all(&schema_folder).unwrap();
+    }
+
+    #[test]
+    fn folders_without_an_ignore_file_ignore_nothing() {
+        let schema_folder: PathBuf = write_schema(&[]);
+
+        assert!(read_ignore_patterns(&schema_folder).unwrap().is_empty());
+
+        remove_dir_all(&schema_folder).unwrap();
+    }
+
+    #[test]
+    fn ignore_patterns_match_from_their_input_folder() {
+        let schema_folder: PathBuf = write_schema(&[
+            (".runeignore", IGNORE_FILE),
+            ("legacy.rune", STRUCT_SCHEMA),
+            ("sub/legacy.rune", STRUCT_SCHEMA),
+            ("draft_top.rune", STRUCT_SCHEMA),
+            ("drafts/deep/draft_nested.rune", STRUCT_SCHEMA),
+            ("drafts/final.rune", STRUCT_SCHEMA)
+        ]);
+        let input_paths: [&Path; 1] = [&schema_folder];
+        let ignore_patterns: Vec<Vec<String>> = vec![read_ignore_patterns(&schema_folder).unwrap()];
+
+        // A leading '/' anchors the pattern to the input folder
+        assert!(is_ignored_path("legacy.rune", &input_paths, &ignore_patterns));
+        assert!(!is_ignored_path("sub/legacy.rune", &input_paths, &ignore_patterns));
+
+        // '**/' matches any amount of directories, including none
+        assert!(is_ignored_path("draft_top.rune", &input_paths, &ignore_patterns));
+        assert!(is_ignored_path("drafts/deep/draft_nested.rune", &input_paths, &ignore_patterns));
+        assert!(!is_ignored_path("drafts/final.rune", &input_paths, &ignore_patterns));
+
+        // Files which are not in the input folder, and thus in another one, are not ignored by its patterns
+        assert!(!is_ignored_path("draft_elsewhere.rune", &input_paths, &ignore_patterns));
+
+        remove_dir_all(&schema_folder).unwrap();
+    }
+
+    /// Parse the Rune files, and remove the ones ignored by the .runeignore file among them
+    fn remove_ignored_schema_files(files: &[(&str, &str)]) -> Result<Vec<String>, CompilerError> {
+        let schema_folder: PathBuf = write_schema(files);
+        let input_paths: [&Path; 1] = [&schema_folder];
+
+        let mut file_descriptions: Vec<RuneFileDescription> = parser_rune_files(&input_paths, true, true).expect("Schema should parse");
+        let result: Result<(), CompilerError> = remove_ignored_files(&mut file_descriptions, &input_paths, &[read_ignore_patterns(&schema_folder).unwrap()]);
+        remove_dir_all(&schema_folder).unwrap();
+
+        let mut names: Vec<String> = file_descriptions.iter().map(|file| format!("{0}{1}", file.relative_path, file.name)).collect();
+        names.sort();
+
+        result.map(|_| names)
+    }
+
+    #[test]
+    fn ignored_files_are_removed() {
+        let names: Vec<String> = remove_ignored_schema_files(&[(".runeignore", IGNORE_FILE), ("legacy.rune", STRUCT_SCHEMA), ("app.rune", "struct App {\n    Y: u8 = 1\n}\n")]).unwrap();
+
+        assert_eq!(names, ["app"]);
+    }
+
+    #[test]
+    fn including_an_ignored_file_is_rejected() {
+        let result: Result<Vec<String>, CompilerError> = remove_ignored_schema_files(&[
+            (".runeignore", IGNORE_FILE),
+            ("legacy.rune", STRUCT_SCHEMA),
+            ("app.rune", "include \"legacy.rune\";\n\nstruct App {\n    Last: Value = 1\n}\n")
+        ]);
+
+        assert!(matches!(result, Err(CompilerError::ConfigurationError)));
+    }
+
+    #[test]
+    fn single_stars_match_within_a_directory() {
+        assert!(glob_matches(b"*.rune", b"frame.rune"));
+        assert!(!glob_matches(b"*.rune", b"net/frame.rune"));
+        assert!(glob_matches(b"net/*.rune", b"net/frame.rune"));
+        assert!(!glob_matches(b"net/*.rune", b"net/link/frame.rune"));
+        assert!(glob_matches(b"fr?me.rune", b"frame.rune"));
+        assert!(!glob_matches(b"net?frame.rune", b"net/frame.rune"));
+    }
+
+    #[test]
+    fn double_stars_match_across_directories() {
+        assert!(glob_matches(b"legacy/**/*.rune", b"legacy/old/deep/frame.rune"));
+        assert!(glob_matches(b"legacy/**/*.rune", b"legacy/frame.rune"));
+        assert!(glob_matches(b"**", b"any/path/frame.rune"));
+        assert!(!glob_matches(b"legacy/**/*.rune", b"current/frame.rune"));
+    }
+
+    const EXCLUDED_SCHEMA: [(&str, &str); 3] = [
+        ("legacy/old/value.rune", "struct Value {\n    X: u8 = 1\n}\n"),
+        ("legacy.rune", "struct Legacy {\n    Y: u8 = 1\n}\n"),
+        ("app.rune", "include \"legacy/old/value.rune\";\n\nstruct App {\n    Last: Value = 1\n}\n")
+    ];
+
+    #[test]
+    fn excluded_files_match_by_name_or_by_path() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&EXCLUDED_SCHEMA);
+        let excluded_names = |pattern: &str| -> Vec<String> {
+            let mut names: Vec<String> = file_descriptions
+                .iter()
+                .filter(|file| is_excluded_file(file, &compile_configurations(&["--exclude-files", pattern])))
+                .map(|file| format!("{0}{1}", file.relative_path, file.name))
+                .collect();
+            names.sort();
+
+            names
+        };
+
+        // Patterns without a '/' only match file names
+        assert_eq!(excluded_names("value.rune"), ["legacy/old/value"]);
+        assert_eq!(excluded_names("legacy*"), ["legacy"]);
+        assert_eq!(excluded_names("legacy/**/*.rune"), ["legacy/old/value"]);
+        assert_eq!(excluded_names("*.rune"), ["app", "legacy", "legacy/old/value"]);
+        assert!(excluded_names("legacy/*.rune").is_empty());
+    }
+
+    #[test]
+    fn excluded_files_can_still_be_included() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&EXCLUDED_SCHEMA);
+        let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &compile_configurations(&["--exclude-files", "legacy/**/*.rune"])).unwrap();
+
+        let mut names: Vec<&str> = output_files.iter().map(|(name, _)| name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, ["app.rune.c", "app.rune.h", "legacy.rune.c", "legacy.rune.h", "runic_definitions.h"]);
+
+        let header: &str = &output_files.iter().find(|(name, _)| name == "app.rune.h").unwrap().1;
+        assert!(header.contains("#include \"legacy/old/value.rune.h\""));
+        assert!(header.contains("    value_t last;"));
+    }
+
+    const SELECTION_SCHEMA: &str =
+        "struct Inner {\n    X: u8 = 1\n}\n\nstruct Outer {\n    Nested: [Inner; 2] = 1\n}\n\nstruct Packet {\n    Payload: Outer = 1\n}\n\nstruct Status {\n    Code: u8 = 1\n}\n";
+
+    fn selected_structs(arguments: &[&str]) -> Result<Vec<String>, CompilerError> {
+        select_structs(&parse_schema(&[("schema.rune", SELECTION_SCHEMA)]), &compile_configurations(arguments))
+    }
+
+    #[test]
+    fn all_structs_are_selected_by_default() {
+        let mut selection: Vec<String> = selected_structs(&[]).unwrap();
+        selection.sort();
+
+        assert_eq!(selection, ["Inner", "Outer", "Packet", "Status"]);
+    }
+
+    #[test]
+    fn selected_structs_keep_their_dependencies() {
+        let mut selection: Vec<String> = selected_structs(&["--only", "Packet"]).unwrap();
+        selection.sort();
+        assert_eq!(selection, ["Inner", "Outer", "Packet"]);
+
+        assert_eq!(selected_structs(&["--only", "Status"]).unwrap(), ["Status"]);
+    }
+
+    #[test]
+    fn excluded_structs_are_kept_when_others_depend_on_them() {
+        let mut selection: Vec<String> = selected_structs(&["--exclude", "Status,Outer"]).unwrap();
+        selection.sort();
+
+        assert_eq!(selection, ["Inner", "Outer", "Packet"]);
+        assert_eq!(selected_structs(&["--exclude", "Packet,Outer,Inner"]).unwrap(), ["Status"]);
+    }
+
+    #[test]
+    fn selecting_undeclared_structs_is_rejected() {
+        assert!(matches!(selected_structs(&["--only", "Missing"]), Err(CompilerError::InvalidArgument)));
+        assert!(matches!(selected_structs(&["--exclude", "Missing"]), Err(CompilerError::InvalidArgument)));
+    }
+
+    #[test]
+    fn unselected_structs_are_filtered_out() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("schema.rune", SELECTION_SCHEMA)]);
+        let filtered: Vec<RuneFileDescription> = filter_structs(&file_descriptions, &[String::from("Status")]);
+
+        let names: Vec<&str> = filtered[0].definitions.structs.iter().map(|definition| definition.name.as_str()).collect();
+        assert_eq!(names, ["Status"]);
+    }
+}
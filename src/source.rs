@@ -1,229 +1,513 @@
-use std::path::Path;
-
-use rune_parser::types::{FieldIndex, FieldType, StructMember, UserDefinitionLink};
+use rune_parser::types::{EnumDefinition, FieldIndex, FieldType, StructDefinition, StructMember, UserDefinitionLink};
 
 use crate::{
     RuneFileDescription,
-    c_utilities::{CConfigurations, CStructMember, pascal_to_snake_case, spaces},
+    c_utilities::{CConfigurations, CStructDefinition, CStructMember, offset_member_name, output_base_name, output_disclaimer, pascal_to_snake_case, pascal_to_uppercase, sibling_base_name, spaces},
+    codec::{output_byte_order_function, output_codec, uses_byte_order_function},
     compile_error::CompilerError,
-    output_file::OutputFile
+    named_fields::output_named_fields,
+    output_file::OutputFile,
+    tlv::output_tlv_descriptor,
+    verifier::{output_crc_function, output_verifier, uses_crc_function}
 };
 
-pub fn output_source(file: &RuneFileDescription, configurations: &CConfigurations, output_path: &Path) -> Result<(), CompilerError> {
+/// Outputs a function returning the Rune identifier of an enum value, or "UNKNOWN" for values without a member
+fn output_enum_names(source_file: &mut OutputFile, enum_definition: &EnumDefinition) {
+    let enum_name: String = pascal_to_snake_case(&enum_definition.name);
+
+    source_file.add_line(format!("const char* {0}_to_string({0}_t value) {{", enum_name));
+    source_file.add_indented_line(1, String::from("switch (value) {"));
+
+    for member in &enum_definition.members {
+        source_file.add_indented_line(2, format!("case {0}:", pascal_to_uppercase(&member.identifier)));
+        source_file.add_indented_line(3, format!("return \"{0}\";", member.identifier));
+    }
+
+    source_file.add_indented_line(2, String::from("default:"));
+    source_file.add_indented_line(3, String::from("return \"UNKNOWN\";"));
+    source_file.add_indented_line(1, String::from("}"));
+    source_file.add_line(String::from("}"));
+}
+
+/// Outputs the descriptor of a struct, holding the offset and size of every field, into the source file
+fn output_descriptor(source_file: &mut OutputFile, configurations: &CConfigurations, struct_definition: &StructDefinition) -> Result<(), CompilerError> {
     let c_standard = &configurations.compiler_configurations.c_standard;
+    let unsigned_suffix: &'static str = configurations.compiler_configurations.unsigned_suffix();
 
-    let c_file_string: String = format!(
-        "{0}{1}.rune.c",
-        match file.relative_path.is_empty() {
-            true => String::new(),
-            false => format!("/{0}", file.relative_path)
+    let struct_name: String = pascal_to_snake_case(&struct_definition.name);
+
+    // SORT BY INDEX; DO NOT FORGET
+    // INDEXES MISSING MUST HAVE AN EMPTY DEFINITION --> .size = 0 will cause the field to be skipped
+
+    // Get highest index number (except verification field)
+    let mut highest_index: u64 = 0;
+    let mut has_verification: bool = false;
+
+    for member in &struct_definition.members {
+        let index: u64 = match member.index {
+            FieldIndex::Verifier => {
+                has_verification = true;
+                0
+            },
+            FieldIndex::Numeric(value) => value
+        };
+
+        if index > highest_index {
+            highest_index = index;
+        }
+    }
+
+    let member_count: u64 = highest_index + 1;
+
+    // Index sort all members, adding empty definitions for skipped fields
+    let mut index_sorted_members: Vec<StructMember> = Vec::with_capacity(member_count as usize);
+    let mut descriptor_list: Vec<(String, u64)> = Vec::with_capacity(0x20);
+    // One bit per field index. The parser limits field indexes to below FieldIndex::LIMIT (32), matching the 32 bit flags of the
+    // Rune library descriptors and the 5 bits of RUNE_FIELD_INDEX_BITS on the wire
+    let mut descriptor_flags: u32 = 0;
+
+    // Also get longest member name for spacing reasons
+    let mut longest_member_name_size: usize = 0;
+
+    for i in 0..member_count {
+        // Empty definition that will be used if index not found in struct list
+        let mut member: StructMember = StructMember::index_empty(i)?;
+
+        // Try to find member with index i
+        for listed_member in &struct_definition.members {
+            let listed_index: u64 = match listed_member.index {
+                FieldIndex::Numeric(index) => index,
+                FieldIndex::Verifier => 0
+            };
+
+            if listed_index == i {
+                member = listed_member.clone();
+
+                // Check to see if it's a nested message, and add descriptor if so. Arrays of structs are copied as plain data
+                if let (FieldType::UserDefined(_), UserDefinitionLink::StructLink(link)) = (&member.data_type, &member.user_definition_link) {
+                    descriptor_list.push((pascal_to_snake_case(&link.name), member.index.value()));
+                    descriptor_flags += 1 << member.index.value();
+                }
+            }
+        }
+
+        let not_empty: bool = member.data_type != FieldType::Empty;
+
+        // Check name length for spacing (Done here to include "(empty)" members)
+        if pascal_to_snake_case(&member.identifier).len() + not_empty as usize > longest_member_name_size {
+            longest_member_name_size = pascal_to_snake_case(&member.identifier).len() + not_empty as usize;
+        }
+
+        index_sorted_members.push(member);
+    }
+
+    // Handle field descriptors
+    // —————————————————————————
+
+    let mut descriptor_list_initializer: String = String::from("NULL");
+
+    // Output field descriptors (if any). The list is compact, holding only the nested structs in field index order, so the
+    // descriptor of a nested field is found at the amount of descriptor_flags bits set below its field index
+    if !descriptor_list.is_empty() {
+        descriptor_list_initializer = format!("&{0}_field_descriptors", struct_name);
+
+        source_file.add_line(format!("const rune_descriptor_t* {0}_field_descriptors[{1}] = {{", struct_name, descriptor_list.len()));
+
+        for (i, (descriptor_name, field_index)) in descriptor_list.iter().enumerate() {
+            let comma: String = match i == descriptor_list.len() - 1 {
+                true => String::from(" "),
+                false => String::from(",")
+            };
+            source_file.add_indented_line(1, format!("&{0}_descriptor{1} /* Field {2} */", descriptor_name, comma, field_index));
+        }
+
+        source_file.add_line("};".to_string());
+        source_file.add_newline();
+    }
+
+    // Check that standard allows_designated_initializers, and output accordingly
+    // ———————————————————————————————————————————————————————————————————————————
+
+    let comment_start: &'static str;
+    let comment_end: &'static str;
+    let space: &'static str;
+    let has_verification_string: String;
+
+    match c_standard.allows_designated_initializers() {
+        true => {
+            comment_start = "";
+            comment_end = "";
+            space = "    ";
+            has_verification_string = has_verification.to_string();
         },
-        file.name
+        false => {
+            comment_start = "/* ";
+            comment_end = " */";
+            space = "";
+            has_verification_string = (has_verification as usize).to_string()
+        }
+    }
+
+    source_file.add_line(format!("const rune_descriptor_t RUNIC_PARSER {0}_descriptor = {{", struct_name));
+    // Binary literals are a language extension before C23, which MISRA C does not allow (rule 1.2)
+    let descriptor_flags_string: String = match configurations.compiler_configurations.misra || !configurations.compiler_configurations.c_standard.allows_binary_literals() {
+        true => format!("0x{0:08X}{1}", descriptor_flags, unsigned_suffix),
+        false => format!("0b{0:0members$b}", descriptor_flags, members = member_count as usize)
+    };
+
+    source_file.add_indented_line(1, format!("{0}.descriptor_flags     {1}={2} {3},", comment_start, space, comment_end, descriptor_flags_string));
+    source_file.add_indented_line(1, format!("{0}.field_descriptors    {1}={2} {3},", comment_start, space, comment_end, descriptor_list_initializer));
+    source_file.add_indented_line(1, format!("{0}.size                 {1}={2} sizeof({3}_t),", comment_start, space, comment_end, struct_name));
+    source_file.add_indented_line(
+        1,
+        format!("{0}.largest_field        {1}={2} {3}{4},", comment_start, space, comment_end, highest_index, unsigned_suffix)
+    );
+
+    // Parsing data is omitted entirely when verifier support is disabled
+    if configurations.compiler_configurations.verifier_support {
+        source_file.add_indented_line(1, format!("{0}.parsing_data         {1}={2} {{", comment_start, space, comment_end));
+        source_file.add_indented_line(
+            1,
+            format!(
+                "{0}{1}.has_verification {2}={3} {4},",
+                comment_start,
+                source_file.indent(1),
+                space,
+                comment_end,
+                has_verification_string
+            )
+        );
+        source_file.add_indented_line(1, "},".to_string());
+    }
+
+    source_file.add_indented_line(1, format!("{0}.field_info           {1}={2} {{", comment_start, space, comment_end));
+
+    for (counter, member) in index_sorted_members.iter().enumerate() {
+        let member_name: String = pascal_to_snake_case(&member.identifier);
+        let spacing: usize = longest_member_name_size - member_name.len() - (member.data_type != FieldType::Empty) as usize;
+
+        let init_char: String = match &member.data_type {
+            FieldType::Empty => String::new(),
+            _ => String::from(".")
+        };
+
+        let end: char = match counter == member_count as usize - 1 {
+            false => ',',
+            true => ' '
+        };
+
+        let size_string: String = member
+            .storage_member(&configurations.compiler_configurations)
+            .c_size_definition(&configurations.compiler_configurations)?;
+
+        let verification_string: String = match has_verification && counter == 0 {
+            false => String::from(""),
+            true => String::from("Verifier field - ")
+        };
+
+        let offset_string: String = match &member.data_type {
+            FieldType::Empty => format!("0{0}", unsigned_suffix),
+            _ => format!("offsetof({0}_t, {1})", struct_name, offset_member_name(configurations, &struct_definition.name, member)?)
+        };
+
+        let comment_spacing = match c_standard.allows_designated_initializers() {
+            true => "",
+            false => "   "
+        };
+
+        source_file.add_indented_line(
+            1,
+            format!("/*  {0}{1}{2}: {3}{4}{5} */ {{", comment_spacing, init_char, member_name, spaces(spacing), verification_string, counter)
+        );
+        source_file.add_indented_line(1, format!("{0}{1}.offset ={2} {3},", comment_start, source_file.indent(2), comment_end, offset_string));
+        source_file.add_indented_line(1, format!("{0}{1}.size   ={2} {3},", comment_start, source_file.indent(2), comment_end, size_string));
+
+        source_file.add_indented_line(2, format!("}}{0}", end));
+    }
+
+    source_file.add_indented_line(1, "}".to_string());
+    source_file.add_line("};".to_string());
+
+    Ok(())
+}
+
+/// Outputs a compile time check that the size of a struct matches the size estimated by the layout model, which the sorting,
+/// explicit padding, and size constants rely on. Before C11 a typedef of a negative sized array takes the place of static_assert
+fn output_size_check(source_file: &mut OutputFile, configurations: &CConfigurations, struct_definition: &StructDefinition) -> Result<(), CompilerError> {
+    let compiler_configurations = &configurations.compiler_configurations;
+    let c_standard = &compiler_configurations.c_standard;
+
+    let struct_name: String = pascal_to_snake_case(&struct_definition.name);
+    let size_condition: String = format!(
+        "sizeof({0}_t) == {1}{2}",
+        struct_name,
+        struct_definition.estimate_size(compiler_configurations)?,
+        compiler_configurations.unsigned_suffix()
     );
 
-    let mut source_file: OutputFile = OutputFile::new(String::from(output_path.to_str().unwrap()), c_file_string);
+    match c_standard.allows_static_assert() {
+        true => source_file.add_line(format!(
+            "{0}({1}, \"{2}_t does not have the size estimated by the Rune compiler\");",
+            match c_standard.allows_static_assert_keyword() {
+                true => "static_assert",
+                false => "_Static_assert"
+            },
+            size_condition,
+            struct_name
+        )),
+        false => source_file.add_line(format!("typedef char {0}_size_check[({1}) ? 1 : -1];", struct_name, size_condition))
+    }
+
+    Ok(())
+}
+
+pub fn output_source(file: &RuneFileDescription, configurations: &CConfigurations) -> Result<OutputFile, CompilerError> {
+    let c_file_string: String = format!(
+        "{0}.{1}",
+        output_base_name(file, &configurations.compiler_configurations),
+        configurations.compiler_configurations.output_extension("c")
+    );
+
+    let mut source_file: OutputFile = OutputFile::new(c_file_string, &configurations.compiler_configurations);
 
     // Disclaimers
     // ————————————
 
-    // ...
+    output_disclaimer(&mut source_file, &configurations.compiler_configurations);
 
     // Include own header
     // ———————————————————
 
-    source_file.add_line(format!("#include \"{0}.rune.h\"", file.name));
+    source_file.add_line(format!(
+        "#include \"{0}.{1}\"",
+        sibling_base_name(file, &configurations.compiler_configurations),
+        configurations.compiler_configurations.output_extension("h")
+    ));
     source_file.add_newline();
 
+    // Codec functions copy members with memcpy
+    if configurations.compiler_configurations.codec && !file.definitions.structs.is_empty() {
+        source_file.add_line("#include <string.h>".to_string());
+        source_file.add_newline();
+    }
+
     // Include rune.h
     // ———————————————
 
-    source_file.add_line("#include \"rune.h\"".to_string());
+    // Only the descriptors use the Rune library types
+    if configurations.compiler_configurations.descriptors {
+        source_file.add_line("#include \"rune.h\"".to_string());
 
-    if !&file.definitions.structs.is_empty() {
-        source_file.add_newline();
+        if !&file.definitions.structs.is_empty() {
+            source_file.add_newline();
+        }
     }
 
-    // Struct parsers
-    // ———————————————
+    // Size checks
+    // ————————————
 
-    for struct_definition in &file.definitions.structs {
-        let struct_name: String = pascal_to_snake_case(&struct_definition.name);
-
-        // SORT BY INDEX; DO NOT FORGET
-        // INDEXES MISSING MUST HAVE AN EMPTY DEFINITION --> .size = 0 will cause the field to be skipped
-
-        // Get highest index number (except verification field)
-        let mut highest_index: u64 = 0;
-        let mut has_verification: bool = false;
-
-        for member in &struct_definition.members {
-            let index: u64 = match member.index {
-                FieldIndex::Verifier => {
-                    has_verification = true;
-                    0
-                },
-                FieldIndex::Numeric(value) => value
-            };
+    let compiler_configurations = &configurations.compiler_configurations;
 
-            if index > highest_index {
-                highest_index = index;
-            }
+    // Structs whose size depends on implementation defined enum sizes cannot be checked
+    if compiler_configurations.c_standard.allows_static_assert() || compiler_configurations.strict_size_checks {
+        let checked_structs: Vec<&StructDefinition> = file
+            .definitions
+            .structs
+            .iter()
+            .filter(|struct_definition| struct_definition.has_fixed_layout(compiler_configurations))
+            .collect();
+
+        for struct_definition in &checked_structs {
+            output_size_check(&mut source_file, configurations, struct_definition)?;
         }
 
-        let member_count: u64 = highest_index + 1;
+        let has_parsers: bool = compiler_configurations.descriptors
+            || compiler_configurations.tlv
+            || compiler_configurations.named_fields
+            || compiler_configurations.codec
+            || (compiler_configurations.verifiers && !file.definitions.structs.is_empty())
+            || (compiler_configurations.enum_names && !file.definitions.enums.is_empty());
 
-        // Index sort all members, adding empty definitions for skipped fields
-        let mut index_sorted_members: Vec<StructMember> = Vec::with_capacity(member_count as usize);
-        let mut descriptor_list: Vec<String> = Vec::with_capacity(0x20);
-        let mut descriptor_flags: u32 = 0;
+        if !checked_structs.is_empty() && has_parsers {
+            source_file.add_newline();
+        }
+    }
 
-        // Also get longest member name for spacing reasons
-        let mut longest_member_name_size: usize = 0;
+    // Enum names
+    // ———————————
 
-        for i in 0..member_count {
-            // Empty definition that will be used if index not found in struct list
-            let mut member: StructMember = StructMember::index_empty(i)?;
+    if configurations.compiler_configurations.enum_names {
+        for enum_definition in &file.definitions.enums {
+            output_enum_names(&mut source_file, enum_definition);
+            source_file.add_newline();
+        }
+    }
 
-            // Try to find member with index i
-            for listed_member in &struct_definition.members {
-                let listed_index: u64 = match listed_member.index {
-                    FieldIndex::Numeric(index) => index,
-                    FieldIndex::Verifier => 0
-                };
+    // Checksums
+    // ——————————
 
-                if listed_index == i {
-                    member = listed_member.clone();
+    if configurations.compiler_configurations.verifiers && uses_crc_function(&file.definitions.structs) {
+        output_crc_function(&mut source_file);
+    }
 
-                    // Check to see if it's a nested message, and add descriptor if so
-                    if let UserDefinitionLink::StructLink(link) = &member.user_definition_link {
-                        descriptor_list.push(pascal_to_snake_case(&link.name));
-                        descriptor_flags += 1 << member.index.value();
-                    }
-                }
-            }
+    // Codec byte order
+    // —————————————————
 
-            let not_empty: bool = member.data_type != FieldType::Empty;
+    if configurations.compiler_configurations.codec && uses_byte_order_function(configurations, &file.definitions.structs)? {
+        output_byte_order_function(&mut source_file, configurations);
+    }
 
-            // Check name length for spacing (Done here to include "(empty)" members)
-            if pascal_to_snake_case(&member.identifier).len() + not_empty as usize > longest_member_name_size {
-                longest_member_name_size = pascal_to_snake_case(&member.identifier).len() + not_empty as usize;
+    // Struct parsers
+    // ———————————————
+
+    for struct_definition in &file.definitions.structs {
+        if configurations.compiler_configurations.descriptors {
+            output_descriptor(&mut source_file, configurations, struct_definition)?;
+        }
+
+        if configurations.compiler_configurations.tlv {
+            if configurations.compiler_configurations.descriptors {
+                source_file.add_newline();
             }
 
-            index_sorted_members.push(member);
+            output_tlv_descriptor(&mut source_file, configurations, struct_definition)?;
         }
 
-        // Handle field descriptors
-        // —————————————————————————
+        if configurations.compiler_configurations.named_fields {
+            if configurations.compiler_configurations.descriptors || configurations.compiler_configurations.tlv {
+                source_file.add_newline();
+            }
 
-        let mut descriptor_list_initializer: String = String::from("NULL");
+            output_named_fields(&mut source_file, configurations, struct_definition)?;
+        }
 
-        // Output field descriptors (if any)
-        if !descriptor_list.is_empty() {
-            descriptor_list_initializer = format!("&{0}_field_descriptors", struct_name);
+        if configurations.compiler_configurations.codec {
+            if configurations.compiler_configurations.descriptors || configurations.compiler_configurations.tlv || configurations.compiler_configurations.named_fields {
+                source_file.add_newline();
+            }
 
-            source_file.add_line(format!("const rune_descriptor_t* {0}_field_descriptors[{1}] = {{", struct_name, descriptor_list.len()));
+            output_codec(&mut source_file, configurations, struct_definition)?;
+        }
 
-            for i in 0..descriptor_list.len() {
-                let comma: String = match i == descriptor_list.len() - 1 {
-                    true => String::new(),
-                    false => String::from(",")
-                };
-                source_file.add_line(format!("    &{0}_descriptor{1}", descriptor_list[i], comma));
+        // Codec functions already end with an empty line
+        if configurations.compiler_configurations.verifiers {
+            if (configurations.compiler_configurations.descriptors || configurations.compiler_configurations.tlv || configurations.compiler_configurations.named_fields)
+                && !configurations.compiler_configurations.codec
+            {
+                source_file.add_newline();
             }
 
-            source_file.add_line("};".to_string());
-            source_file.add_newline();
+            output_verifier(&mut source_file, configurations, struct_definition)?;
         }
+    }
 
-        // Check that standard allows_designated_initializers, and output accordingly
-        // ———————————————————————————————————————————————————————————————————————————
+    Ok(source_file)
+}
 
-        let comment_start: &'static str;
-        let comment_end: &'static str;
-        let space: &'static str;
-        let has_verification_string: String;
+#[cfg(test)]
+mod tests {
+    use rune_parser::RuneFileDescription;
 
-        match c_standard.allows_designated_initializers() {
-            true => {
-                comment_start = "";
-                comment_end = "";
-                space = "    ";
-                has_verification_string = has_verification.to_string();
-            },
-            false => {
-                comment_start = "/* ";
-                comment_end = " */";
-                space = "";
-                has_verification_string = (has_verification as usize).to_string()
-            }
-        }
+    use crate::{c_utilities::CConfigurations, header::output_header, source::output_source, test_utilities::parse_single_schema};
 
-        source_file.add_line(format!("const rune_descriptor_t RUNIC_PARSER {0}_descriptor = {{", struct_name));
-        source_file.add_line(format!(
-            "    {0}.descriptor_flags     {1}={2} 0b{3:0members$b},",
-            comment_start,
-            space,
-            comment_end,
-            descriptor_flags,
-            members = member_count as usize
-        ));
-        source_file.add_line(format!("    {0}.field_descriptors    {1}={2} {3},", comment_start, space, comment_end, descriptor_list_initializer));
-        source_file.add_line(format!("    {0}.size                 {1}={2} sizeof({3}_t),", comment_start, space, comment_end, struct_name));
-        source_file.add_line(format!("    {0}.largest_field        {1}={2} {3},", comment_start, space, comment_end, highest_index));
-        source_file.add_line(format!("    {0}.parsing_data         {1}={2} {{", comment_start, space, comment_end));
-        source_file.add_line(format!("    {0}    .has_verification {1}={2} {3},", comment_start, space, comment_end, has_verification_string));
-        source_file.add_line("    },".to_string());
-        source_file.add_line(format!("    {0}.field_info           {1}={2} {{", comment_start, space, comment_end));
-
-        for (counter, member) in index_sorted_members.iter().enumerate() {
-            let member_name: String = pascal_to_snake_case(&member.identifier);
-            let spacing: usize = longest_member_name_size - member_name.len() - (member.data_type != FieldType::Empty) as usize;
-
-            let init_char: String = match &member.data_type {
-                FieldType::Empty => String::new(),
-                _ => String::from(".")
-            };
+    /// Output the source of a single Rune file, compiled with the given command line arguments
+    fn schema_source(contents: &str, arguments: &[&str]) -> String {
+        let (file_descriptions, configurations): (Vec<RuneFileDescription>, CConfigurations) = parse_single_schema(contents, arguments);
 
-            let end: char = match counter == member_count as usize - 1 {
-                false => ',',
-                true => ' '
-            };
+        output_source(&file_descriptions[0], &configurations).unwrap().into_contents()
+    }
 
-            let size_string: String = member.c_size_definition(c_standard)?;
+    #[test]
+    fn descriptors_skip_padding_members() {
+        let schema: &str = "struct Frame {\n    Flag: u8 = 1;\n    Count: u32 = 2;\n    Level: u16 = 3\n}\n";
+        let source: String = schema_source(schema, &["--explicit-padding", "--unsorted"]);
 
-            let verification_string: String = match has_verification && counter == 0 {
-                false => String::from(""),
-                true => String::from("Verifier field - ")
-            };
+        assert!(source.contains(".offset = offsetof(frame_t, level)"));
+        assert!(!source.contains("_pad"));
+    }
 
-            let offset_string: String = match &member.data_type {
-                FieldType::Empty => String::from("0"),
-                _ => format!("offsetof({0}_t, {1})", struct_name, member_name)
-            };
+    #[test]
+    fn descriptors_are_left_out_in_data_only_mode() {
+        let (file_descriptions, configurations): (Vec<RuneFileDescription>, CConfigurations) = parse_single_schema("struct Frame {\n    Flag: u8 = 1\n}\n", &["--no-descriptors"]);
 
-            let comment_spacing = match c_standard.allows_designated_initializers() {
-                true => "",
-                false => "   "
-            };
+        let header: String = output_header(&file_descriptions[0], &file_descriptions, &configurations).unwrap().into_contents();
+        let source: String = output_source(&file_descriptions[0], &configurations).unwrap().into_contents();
 
-            source_file.add_line(format!(
-                "    /*  {0}{1}{2}: {3}{4}{5} */ {{",
-                comment_spacing,
-                init_char,
-                member_name,
-                spaces(spacing),
-                verification_string,
-                counter
-            ));
-            source_file.add_line(format!("    {0}        .offset ={1} {2},", comment_start, comment_end, offset_string));
-            source_file.add_line(format!("    {0}        .size   ={1} {2},", comment_start, comment_end, size_string));
-
-            source_file.add_line(format!("        }}{0}", end));
-        }
+        assert!(header.contains("} frame_t;"));
+        assert!(header.contains("#define FRAME_INIT"));
+        assert!(!header.contains("descriptor") && !header.contains("DESCRIPTOR"));
+        assert!(!source.contains("descriptor"));
+    }
 
-        source_file.add_line("    }".to_string());
-        source_file.add_line("};".to_string());
+    #[test]
+    fn descriptors_are_output_by_default() {
+        let source: String = schema_source("struct Frame {\n    Flag: u8 = 1\n}\n", &[]);
+
+        assert!(source.contains("const rune_descriptor_t RUNIC_PARSER frame_descriptor = {"));
+    }
+
+    #[test]
+    fn wide_integers_are_sized_as_byte_arrays() {
+        let source: String = schema_source("struct Wide {\n    Value: u128 = 1;\n    History: [i128; 3] = 2\n}\n", &[]);
+
+        assert!(source.contains(".size   = sizeof(uint8_t[16]),"));
+        assert!(source.contains(".size   = (sizeof(uint8_t[16]) * 3),"));
+    }
+
+    const HIGH_INDEX_SCHEMA: &str = "struct Point {\n    X: i16 = 1\n}\n\nstruct Frame {\n    Flag: u8 = 1;\n    Origin: Point = 31\n}\n";
+
+    #[test]
+    fn nested_struct_at_highest_index_sets_highest_flag() {
+        let source: String = schema_source(HIGH_INDEX_SCHEMA, &[]);
+
+        assert!(source.contains(".descriptor_flags         = 0b10000000000000000000000000000000,"));
+        assert!(source.contains(".descriptor_flags         = 0b00,"));
+    }
+
+    #[test]
+    fn nested_struct_at_highest_index_sets_highest_flag_in_hexadecimal() {
+        assert!(schema_source(HIGH_INDEX_SCHEMA, &["--misra"]).contains(".descriptor_flags         = 0x80000000U,"));
+    }
+
+    #[test]
+    fn enum_names_are_the_rune_identifiers() {
+        let schema: &str = "enum Mode: u8 {\n    Idle = 0;\n    RunningFast = 1;\n    Error = 7\n}\n";
+        let source: String = schema_source(schema, &["--emit-enum-names"]);
+
+        assert!(source.contains(
+            "const char* mode_to_string(mode_t value) {\n    switch (value) {\n        case IDLE:\n            return \"Idle\";\n        case RUNNING_FAST:\n            return \"RunningFast\";\n        case ERROR:\n            return \"Error\";\n        default:\n            return \"UNKNOWN\";\n    }\n}"
+        ));
+        assert!(!schema_source(schema, &[]).contains("mode_to_string"));
+
+        let (file_descriptions, configurations): (Vec<RuneFileDescription>, CConfigurations) = parse_single_schema(schema, &["--emit-enum-names"]);
+        let header: String = output_header(&file_descriptions[0], &file_descriptions, &configurations).unwrap().into_contents();
+        assert!(header.contains("const char* mode_to_string(mode_t value);"));
     }
 
-    source_file.output_file()
+    const SIZE_CHECK_SCHEMA: &str = "struct Frame {\n    Flag: u8 = 1;\n    Count: u32 = 2\n}\n";
+
+    #[test]
+    fn sizes_are_checked_with_static_assertions_from_c11() {
+        assert!(schema_source(SIZE_CHECK_SCHEMA, &["--c-standard", "C11"]).contains("_Static_assert(sizeof(frame_t) == 8, \"frame_t does not have the size estimated by the Rune compiler\");"));
+        assert!(schema_source(SIZE_CHECK_SCHEMA, &["--c-standard", "C23"]).contains("\nstatic_assert(sizeof(frame_t) == 8, \"frame_t does not have the size estimated by the Rune compiler\");"));
+    }
+
+    #[test]
+    fn sizes_are_checked_with_negative_arrays_before_c11_when_strict() {
+        assert!(!schema_source(SIZE_CHECK_SCHEMA, &["--c-standard", "C99"]).contains("sizeof(frame_t) =="));
+
+        let source: String = schema_source(SIZE_CHECK_SCHEMA, &["--c-standard", "C99", "--strict-size-checks"]);
+        assert!(source.contains("typedef char frame_size_check[(sizeof(frame_t) == 8) ? 1 : -1];"));
+        assert!(!source.contains("static_assert") && !source.contains("_Static_assert"));
+    }
+
+    #[test]
+    fn parsing_data_is_omitted_without_verifier_support() {
+        let source: String = schema_source(SIZE_CHECK_SCHEMA, &[]);
+        assert!(source.contains("    .largest_field            = 2,\n    .parsing_data             = {\n        .has_verification     = false,\n    },"));
+
+        let source: String = schema_source(SIZE_CHECK_SCHEMA, &["--no-verifier-support"]);
+        assert!(!source.contains("parsing_data") && !source.contains("has_verification"));
+        assert!(source.contains("    .largest_field            = 2,\n    .field_info               = {"));
+    }
 }
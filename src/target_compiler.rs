@@ -0,0 +1,31 @@
+use crate::{compile_error::CompilerError, output::*};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TargetCompiler {
+    Gcc,
+    Clang,
+    Msvc
+}
+
+impl TargetCompiler {
+    pub fn from_string(string: &str) -> Result<TargetCompiler, CompilerError> {
+        match string {
+            "gcc" | "GCC" => Ok(TargetCompiler::Gcc),
+            "clang" | "Clang" => Ok(TargetCompiler::Clang),
+            "msvc" | "MSVC" => Ok(TargetCompiler::Msvc),
+            _ => {
+                error!("Invalid compiler passed. Got {0}, and valid values are: {1}", string, TargetCompiler::valid_values());
+                Err(CompilerError::InvalidArgument)
+            }
+        }
+    }
+
+    fn valid_values() -> String {
+        String::from("gcc, clang, msvc")
+    }
+
+    /// Whether the compiler supports GCC style __attribute__ declarations. Otherwise packing is done with #pragma pack
+    pub fn allows_attributes(&self) -> bool {
+        *self != TargetCompiler::Msvc
+    }
+}
@@ -0,0 +1,60 @@
+use std::{
+    fs::{create_dir_all, remove_dir_all, write},
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering}
+};
+
+use clap::Parser;
+use rune_parser::{RuneFileDescription, parser_rune_files};
+
+use crate::{
+    Args,
+    c_utilities::{CConfigurations, CompileConfigurations, link_user_definitions}
+};
+
+static SCHEMA_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// Compile configurations from the given command line arguments, on top of the defaults
+pub fn compile_configurations(arguments: &[&str]) -> CompileConfigurations {
+    let args: Args = Args::parse_from(["rune_c_compiler", "--output-folder", "."].iter().chain(arguments));
+
+    args.compile_configurations(None).expect("Arguments should be valid")
+}
+
+/// Write the given Rune files, as pairs of their path and contents, into a new temporary folder. The caller removes the folder
+pub fn write_schema(files: &[(&str, &str)]) -> PathBuf {
+    let schema_folder: PathBuf = std::env::temp_dir().join(format!("rune_c_compiler_test_{0}_{1}", process::id(), SCHEMA_COUNT.fetch_add(1, Ordering::Relaxed)));
+    create_dir_all(&schema_folder).expect("Schema folder should be created");
+
+    for (name, contents) in files {
+        let path: PathBuf = schema_folder.join(name);
+
+        // Files may be placed in sub folders, given as part of their name
+        create_dir_all(path.parent().unwrap()).expect("Schema sub folder should be created");
+        write(path, contents).expect("Schema file should be written");
+    }
+
+    schema_folder
+}
+
+/// Parse and link the given Rune files, written as pairs of their name and contents
+pub fn parse_schema(files: &[(&str, &str)]) -> Vec<RuneFileDescription> {
+    let schema_folder: PathBuf = write_schema(files);
+
+    let parsed_files = parser_rune_files(&[Path::new(&schema_folder)], true, true);
+    remove_dir_all(&schema_folder).expect("Schema folder should be removed");
+
+    let mut file_descriptions: Vec<RuneFileDescription> = parsed_files.expect("Schema should parse");
+    link_user_definitions(&mut file_descriptions).expect("Schema should link");
+
+    file_descriptions
+}
+
+/// Parse the given Rune file, and the C configurations for compiling it with the given command line arguments
+pub fn parse_single_schema(contents: &str, arguments: &[&str]) -> (Vec<RuneFileDescription>, CConfigurations) {
+    let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("schema.rune", contents)]);
+    let configurations: CConfigurations = CConfigurations::parse(&file_descriptions, &compile_configurations(arguments)).expect("Configurations should parse");
+
+    (file_descriptions, configurations)
+}
@@ -0,0 +1,566 @@
+use rune_parser::types::{FieldType, Primitive, StructDefinition, StructMember, UserDefinitionLink};
+
+use crate::{
+    c_utilities::{CConfigurations, CStructMember, offset_member_name, output_debug_checks, output_disclaimer, pascal_to_snake_case},
+    compile_error::CompilerError,
+    endianness::Endianness,
+    output_file::OutputFile
+};
+
+// TLV wire format
+// ————————————————
+//
+// Every member is encoded as a 1 byte tag holding the field index, a 2 byte little endian length, and the member value. Nested
+// structs are encoded recursively, while all other members are copied as they are stored in memory. Decoders skip tags they do
+// not know, allowing peers with different versions of a schema to communicate, at the cost of 3 bytes of overhead per member.
+//
+// With varint encoding, integer members of 16 bits or more are encoded as unsigned LEB128 instead: 7 bits per byte, least
+// significant group first, with the high bit set on every byte but the last. Signed members are zigzag mapped first, so small
+// negative values stay small. The length prefix holds the amount of bytes used, so the decoders do not need to know the encoding
+// to skip the member.
+
+const TLV_HEADER_LINES: &[&str] = &[
+    "/** Size of the tag and length prefix of every encoded member */",
+    "#define RUNE_TLV_HEADER_SIZE 3",
+    "",
+    "/** Largest encodable member length */",
+    "#define RUNE_TLV_MAX_LENGTH 0xFFFF",
+    "",
+    "/** Member is copied as it is stored in memory */",
+    "#define RUNE_TLV_ENCODING_RAW    0",
+    "",
+    "/** Member is an unsigned integer encoded as LEB128 */",
+    "#define RUNE_TLV_ENCODING_VARINT 1",
+    "",
+    "/** Member is a signed integer, zigzag mapped and then encoded as LEB128 */",
+    "#define RUNE_TLV_ENCODING_ZIGZAG 2",
+    "",
+    "/** TLV encoding information of a single struct member */",
+    "typedef struct rune_tlv_field_s {",
+    "    /** Field index, used as tag */",
+    "    unsigned char tag;",
+    "",
+    "    /** Offset of the member within the struct */",
+    "    RUNE_FIELD_OFFSET_TYPE offset;",
+    "",
+    "    /** Size of the member */",
+    "    RUNE_FIELD_SIZE_TYPE size;",
+    "",
+    "    /** TLV descriptor of a nested struct member, or NULL if the member is copied as it is stored in memory */",
+    "    const struct rune_tlv_descriptor_s* nested;",
+    "",
+    "    /** Encoding of the member value, one of RUNE_TLV_ENCODING_* */",
+    "    unsigned char encoding;",
+    "} rune_tlv_field_t;",
+    "",
+    "/** TLV encoding information of a struct */",
+    "typedef struct rune_tlv_descriptor_s {",
+    "    const rune_tlv_field_t* fields;",
+    "    size_t                  field_count;",
+    "} rune_tlv_descriptor_t;",
+    "",
+    "/** Encode a message into the buffer. Returns 1 and sets written to the encoded size on success, or 0 if the buffer is too small */",
+    "RUNE_NODISCARD int rune_tlv_serialize(const rune_tlv_descriptor_t* descriptor, const void* message, unsigned char* buffer, size_t buffer_size, size_t* written);",
+    "",
+    "/** Decode a message from the buffer, skipping unknown tags. Members absent from the buffer are left untouched. Returns 1 on",
+    " *  success, or 0 if the buffer is malformed or a known member has an unexpected length */",
+    "RUNE_NODISCARD int rune_tlv_deserialize(const rune_tlv_descriptor_t* descriptor, void* message, const unsigned char* buffer, size_t length);",
+    "",
+    "#ifdef __cplusplus",
+    "}",
+    "#endif /* __cplusplus */",
+    "",
+    "#endif /* RUNE_TLV_H */"
+];
+
+const TLV_SOURCE_LINES: &[&str] = &[
+    "static int rune_tlv_encode_varint(const rune_tlv_field_t* field, const unsigned char* source, unsigned char* buffer, size_t buffer_size, size_t* written) {",
+    "    rune_tlv_varint_t value = rune_tlv_load(source, field->size);",
+    "    size_t length = 0;",
+    "",
+    "    if (field->encoding == RUNE_TLV_ENCODING_ZIGZAG) {",
+    "        rune_tlv_varint_t sign = (value >> (8 * field->size - 1)) & 1;",
+    "",
+    "        /* Sign extend, then map 0, -1, 1, -2, ... to 0, 1, 2, 3, ... */",
+    "        if (sign && field->size < sizeof(rune_tlv_varint_t)) {",
+    "            value |= ~(rune_tlv_varint_t)0 << (8 * field->size);",
+    "        }",
+    "",
+    "        value = (value << 1) ^ ((rune_tlv_varint_t)0 - sign);",
+    "",
+    "        if (field->size < sizeof(rune_tlv_varint_t)) {",
+    "            value &= ~(~(rune_tlv_varint_t)0 << (8 * field->size));",
+    "        }",
+    "    }",
+    "",
+    "    do {",
+    "        if (length >= buffer_size) {",
+    "            return 0;",
+    "        }",
+    "",
+    "        buffer[length] = (unsigned char)(value & 0x7F);",
+    "        value >>= 7;",
+    "",
+    "        if (value != 0) {",
+    "            buffer[length] |= 0x80;",
+    "        }",
+    "",
+    "        length++;",
+    "    } while (value != 0);",
+    "",
+    "    *written = length;",
+    "    return 1;",
+    "}",
+    "",
+    "static int rune_tlv_decode_varint(const rune_tlv_field_t* field, unsigned char* destination, const unsigned char* buffer, size_t length) {",
+    "    rune_tlv_varint_t value = 0;",
+    "    size_t shift = 0;",
+    "    size_t i;",
+    "",
+    "    for (i = 0; i < length; i++) {",
+    "        /* Only the last byte may lack the continuation bit */",
+    "        if (((buffer[i] & 0x80) == 0) != (i == length - 1)) {",
+    "            return 0;",
+    "        }",
+    "",
+    "        if (shift >= 8 * sizeof(rune_tlv_varint_t)) {",
+    "            return 0;",
+    "        }",
+    "",
+    "        value |= (rune_tlv_varint_t)(buffer[i] & 0x7F) << shift;",
+    "        shift += 7;",
+    "    }",
+    "",
+    "    /* Reject values which do not fit the member */",
+    "    if (length == 0 || (field->size < sizeof(rune_tlv_varint_t) && (value >> (8 * field->size)) != 0)) {",
+    "        return 0;",
+    "    }",
+    "",
+    "    if (field->encoding == RUNE_TLV_ENCODING_ZIGZAG) {",
+    "        value = (value >> 1) ^ ((rune_tlv_varint_t)0 - (value & 1));",
+    "    }",
+    "",
+    "    rune_tlv_store(destination, field->size, value);",
+    "    return 1;",
+    "}",
+    "",
+    "int rune_tlv_serialize(const rune_tlv_descriptor_t* descriptor, const void* message, unsigned char* buffer, size_t buffer_size, size_t* written) {",
+    "    const unsigned char* source = (const unsigned char*)message;",
+    "    size_t offset = 0;",
+    "    size_t i;",
+    "",
+    "    for (i = 0; i < descriptor->field_count; i++) {",
+    "        const rune_tlv_field_t* field = &descriptor->fields[i];",
+    "        size_t length;",
+    "",
+    "        if ((buffer_size - offset) < RUNE_TLV_HEADER_SIZE) {",
+    "            return 0;",
+    "        }",
+    "",
+    "        if (field->nested != NULL) {",
+    "            if (!rune_tlv_serialize(field->nested, &source[field->offset], &buffer[offset + RUNE_TLV_HEADER_SIZE], buffer_size - offset - RUNE_TLV_HEADER_SIZE, &length)) {",
+    "                return 0;",
+    "            }",
+    "        } else if (field->encoding != RUNE_TLV_ENCODING_RAW) {",
+    "            if (!rune_tlv_encode_varint(field, &source[field->offset], &buffer[offset + RUNE_TLV_HEADER_SIZE], buffer_size - offset - RUNE_TLV_HEADER_SIZE, &length)) {",
+    "                return 0;",
+    "            }",
+    "        } else {",
+    "            if ((buffer_size - offset - RUNE_TLV_HEADER_SIZE) < field->size) {",
+    "                return 0;",
+    "            }",
+    "",
+    "            memcpy(&buffer[offset + RUNE_TLV_HEADER_SIZE], &source[field->offset], field->size);",
+    "            length = field->size;",
+    "        }",
+    "",
+    "        if (length > RUNE_TLV_MAX_LENGTH) {",
+    "            return 0;",
+    "        }",
+    "",
+    "        buffer[offset]     = field->tag;",
+    "        buffer[offset + 1] = (unsigned char)(length & 0xFF);",
+    "        buffer[offset + 2] = (unsigned char)((length >> 8) & 0xFF);",
+    "",
+    "        offset += RUNE_TLV_HEADER_SIZE + length;",
+    "    }",
+    "",
+    "    *written = offset;",
+    "    return 1;",
+    "}",
+    "",
+    "int rune_tlv_deserialize(const rune_tlv_descriptor_t* descriptor, void* message, const unsigned char* buffer, size_t length) {",
+    "    unsigned char* destination = (unsigned char*)message;",
+    "    size_t offset = 0;",
+    "",
+    "    while (offset < length) {",
+    "        unsigned char tag;",
+    "        size_t field_length;",
+    "        size_t i;",
+    "",
+    "        if ((length - offset) < RUNE_TLV_HEADER_SIZE) {",
+    "            return 0;",
+    "        }",
+    "",
+    "        tag          = buffer[offset];",
+    "        field_length = (size_t)buffer[offset + 1] | ((size_t)buffer[offset + 2] << 8);",
+    "        offset      += RUNE_TLV_HEADER_SIZE;",
+    "",
+    "        if ((length - offset) < field_length) {",
+    "            return 0;",
+    "        }",
+    "",
+    "        /* Unknown tags are skipped, as they were added by a peer with a newer schema */",
+    "        for (i = 0; i < descriptor->field_count; i++) {",
+    "            const rune_tlv_field_t* field = &descriptor->fields[i];",
+    "",
+    "            if (field->tag != tag) {",
+    "                continue;",
+    "            }",
+    "",
+    "            if (field->nested != NULL) {",
+    "                if (!rune_tlv_deserialize(field->nested, &destination[field->offset], &buffer[offset], field_length)) {",
+    "                    return 0;",
+    "                }",
+    "            } else if (field->encoding != RUNE_TLV_ENCODING_RAW) {",
+    "                if (!rune_tlv_decode_varint(field, &destination[field->offset], &buffer[offset], field_length)) {",
+    "                    return 0;",
+    "                }",
+    "            } else {",
+    "                if (field_length != field->size) {",
+    "                    return 0;",
+    "                }",
+    "",
+    "                memcpy(&destination[field->offset], &buffer[offset], field_length);",
+    "            }",
+    "",
+    "            break;",
+    "        }",
+    "",
+    "        offset += field_length;",
+    "    }",
+    "",
+    "    return 1;",
+    "}"
+];
+
+/// Precondition checks of the TLV functions, output after the declarations of the function starting with the given line
+const TLV_DEBUG_CHECKS: &[(&str, &[&str])] = &[
+    ("static int rune_tlv_encode_varint(", &["field->size <= sizeof(rune_tlv_varint_t)"]),
+    ("static int rune_tlv_decode_varint(", &["field->size <= sizeof(rune_tlv_varint_t)"]),
+    (
+        "int rune_tlv_serialize(",
+        &[
+            "descriptor != NULL",
+            "descriptor->field_count == 0 || descriptor->fields != NULL",
+            "message != NULL",
+            "buffer != NULL",
+            "written != NULL"
+        ]
+    ),
+    (
+        "int rune_tlv_deserialize(",
+        &[
+            "descriptor != NULL",
+            "descriptor->field_count == 0 || descriptor->fields != NULL",
+            "message != NULL",
+            "length == 0 || buffer != NULL"
+        ]
+    )
+];
+
+/// Outputs the header declaring the TLV descriptor types and the serialize and deserialize functions
+pub fn output_tlv_header(configurations: &CConfigurations) -> OutputFile {
+    let mut header_file: OutputFile = OutputFile::new(String::from("rune_tlv.h"), &configurations.compiler_configurations);
+
+    output_disclaimer(&mut header_file, &configurations.compiler_configurations);
+
+    header_file.add_line(String::from("#ifndef RUNE_TLV_H"));
+    header_file.add_line(String::from("#define RUNE_TLV_H"));
+    header_file.add_newline();
+
+    header_file.add_line(String::from("#ifdef __cplusplus"));
+    header_file.add_line(String::from("extern \"C\" {"));
+    header_file.add_line(String::from("#endif /* __cplusplus */"));
+    header_file.add_newline();
+
+    header_file.add_line(String::from("#include <stddef.h>"));
+
+    // Varint members are decoded through the widest available integer. 64 bit members are only available from C99
+    let varint_type: &'static str = match configurations.compiler_configurations.c_standard.allows_integer_types() {
+        true => {
+            header_file.add_line(String::from("#include <stdint.h>"));
+            "uint64_t"
+        },
+        false => "unsigned long"
+    };
+    header_file.add_newline();
+
+    header_file.add_line(format!("#include \"{0}\"", configurations.compiler_configurations.definitions_output));
+    header_file.add_newline();
+
+    header_file.add_line(String::from("/** Integer holding varint encoded member values while they are converted */"));
+    header_file.add_line(format!("typedef {0} rune_tlv_varint_t;", varint_type));
+    header_file.add_newline();
+
+    for line in TLV_HEADER_LINES {
+        header_file.add_reindented_line(line);
+    }
+
+    header_file
+}
+
+/// Outputs the statement of the byte order chosen at generation time, or both selected by the C compiler if it is native
+fn output_byte_order_statement(source_file: &mut OutputFile, configurations: &CConfigurations, little_endian_statement: &str, big_endian_statement: &str) {
+    match configurations.compiler_configurations.endianness {
+        Endianness::Native => {
+            // GCC and Clang only define __LITTLE_ENDIAN__ on some targets, but always define __BYTE_ORDER__
+            source_file.add_line(String::from(
+                "#if defined __LITTLE_ENDIAN__ || (defined __BYTE_ORDER__ && defined __ORDER_LITTLE_ENDIAN__ && __BYTE_ORDER__ == __ORDER_LITTLE_ENDIAN__)"
+            ));
+            source_file.add_indented_line(2, String::from(little_endian_statement));
+            source_file.add_line(String::from("#else"));
+            source_file.add_indented_line(2, String::from(big_endian_statement));
+            source_file.add_line(String::from("#endif"));
+        },
+        Endianness::Little => source_file.add_indented_line(2, String::from(little_endian_statement)),
+        Endianness::Big => source_file.add_indented_line(2, String::from(big_endian_statement))
+    }
+}
+
+/// Outputs the source implementing the serialize and deserialize functions, which are driven by the TLV descriptors
+pub fn output_tlv_source(configurations: &CConfigurations) -> OutputFile {
+    let mut source_file: OutputFile = OutputFile::new(String::from("rune_tlv.c"), &configurations.compiler_configurations);
+
+    output_disclaimer(&mut source_file, &configurations.compiler_configurations);
+
+    source_file.add_line(String::from("#include \"rune_tlv.h\""));
+    source_file.add_newline();
+    source_file.add_line(String::from("#include <string.h>"));
+    source_file.add_newline();
+
+    // Integer members are loaded and stored in host byte order
+    source_file.add_line(String::from("/* Load an integer member stored in host byte order */"));
+    source_file.add_line(String::from("static rune_tlv_varint_t rune_tlv_load(const unsigned char* source, size_t size) {"));
+    source_file.add_indented_line(1, String::from("rune_tlv_varint_t value = 0;"));
+    source_file.add_indented_line(1, String::from("size_t i;"));
+    source_file.add_newline();
+    source_file.add_indented_line(1, String::from("for (i = 0; i < size; i++) {"));
+    output_byte_order_statement(
+        &mut source_file,
+        configurations,
+        "value |= (rune_tlv_varint_t)source[i] << (8 * i);",
+        "value |= (rune_tlv_varint_t)source[i] << (8 * (size - 1 - i));"
+    );
+    source_file.add_indented_line(1, String::from("}"));
+    source_file.add_newline();
+    source_file.add_indented_line(1, String::from("return value;"));
+    source_file.add_line(String::from("}"));
+    source_file.add_newline();
+
+    source_file.add_line(String::from("/* Store an integer member in host byte order */"));
+    source_file.add_line(String::from("static void rune_tlv_store(unsigned char* destination, size_t size, rune_tlv_varint_t value) {"));
+    source_file.add_indented_line(1, String::from("size_t i;"));
+    source_file.add_newline();
+    source_file.add_indented_line(1, String::from("for (i = 0; i < size; i++) {"));
+    output_byte_order_statement(
+        &mut source_file,
+        configurations,
+        "destination[i] = (unsigned char)((value >> (8 * i)) & 0xFF);",
+        "destination[i] = (unsigned char)((value >> (8 * (size - 1 - i))) & 0xFF);"
+    );
+    source_file.add_indented_line(1, String::from("}"));
+    source_file.add_line(String::from("}"));
+    source_file.add_newline();
+
+    let mut pending_checks: Option<&[&str]> = None;
+
+    for line in TLV_SOURCE_LINES {
+        source_file.add_reindented_line(line);
+
+        if !configurations.compiler_configurations.debug_checks {
+            continue;
+        }
+
+        if let Some((_, conditions)) = TLV_DEBUG_CHECKS.iter().find(|(start, _)| line.starts_with(start)) {
+            pending_checks = Some(conditions);
+        }
+
+        // Checks are placed after the declarations, which end with the first empty line, as C89 requires declarations first
+        if line.is_empty()
+            && let Some(conditions) = pending_checks.take()
+        {
+            output_debug_checks(&mut source_file, conditions);
+            source_file.add_newline();
+        }
+    }
+
+    source_file
+}
+
+/// Outputs the TLV descriptor of a struct into the source file
+pub fn output_tlv_descriptor(source_file: &mut OutputFile, configurations: &CConfigurations, struct_definition: &StructDefinition) -> Result<(), CompilerError> {
+    let struct_name: String = pascal_to_snake_case(&struct_definition.name);
+
+    // Fields are listed in index order
+    let mut members: Vec<StructMember> = struct_definition.members.iter().filter(|member| member.data_type != FieldType::Empty).cloned().collect();
+    members.sort_by_key(|member| member.index.value());
+
+    if members.is_empty() {
+        source_file.add_line(format!("const rune_tlv_descriptor_t {0}_tlv_descriptor = {{ NULL, 0 }};", struct_name));
+        source_file.add_newline();
+        return Ok(());
+    }
+
+    source_file.add_line(format!("static const rune_tlv_field_t {0}_tlv_fields[{1}] = {{", struct_name, members.len()));
+
+    for (counter, member) in members.iter().enumerate() {
+        let member_name: String = offset_member_name(configurations, &struct_definition.name, member)?;
+
+        // Nested structs are encoded recursively, allowing them to evolve as well
+        let nested: String = match (&member.data_type, &member.user_definition_link) {
+            (FieldType::UserDefined(_), UserDefinitionLink::StructLink(definition)) => format!("&{0}_tlv_descriptor", pascal_to_snake_case(&definition.name)),
+            _ => String::from("NULL")
+        };
+
+        let storage_member: StructMember = member.storage_member(&configurations.compiler_configurations);
+
+        // Integers narrower than 16 bits gain nothing from varint encoding
+        let encoding: &'static str = match (&storage_member.data_type, configurations.compiler_configurations.varint) {
+            (FieldType::Primitive(Primitive::U16 | Primitive::U32 | Primitive::U64), true) => "RUNE_TLV_ENCODING_VARINT",
+            (FieldType::Primitive(Primitive::I16 | Primitive::I32 | Primitive::I64), true) => "RUNE_TLV_ENCODING_ZIGZAG",
+            _ => "RUNE_TLV_ENCODING_RAW"
+        };
+
+        let end: &'static str = match counter == members.len() - 1 {
+            false => ",",
+            true => ""
+        };
+
+        source_file.add_indented_line(
+            1,
+            format!(
+                "{{ {0}, offsetof({1}_t, {2}), {3}, {4}, {5} }}{6}",
+                member.index.value(),
+                struct_name,
+                member_name,
+                storage_member.c_size_definition(&configurations.compiler_configurations)?,
+                nested,
+                encoding,
+                end
+            )
+        );
+    }
+
+    source_file.add_line(String::from("};"));
+    source_file.add_newline();
+
+    source_file.add_line(format!("const rune_tlv_descriptor_t {0}_tlv_descriptor = {{ {0}_tlv_fields, {1} }};", struct_name, members.len()));
+    source_file.add_newline();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rune_parser::RuneFileDescription;
+
+    use crate::{
+        generate_to_memory,
+        test_utilities::{compile_configurations, parse_schema}
+    };
+
+    const SCHEMA: &str = "struct Point {\n    X: i16 = 1\n}\n\nstruct Frame {\n    Count: u32 = 2;\n    Flag: u8 = 1;\n    Origin: Point = 3;\n    Level: i32 = 4\n}\n";
+
+    /// Output the files generated from the schema in TLV mode with the given command line arguments
+    fn tlv_files(arguments: &[&str]) -> Vec<(String, String)> {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("frame.rune", SCHEMA)]);
+
+        generate_to_memory(&file_descriptions, &compile_configurations(&[&["--tlv-mode"], arguments].concat())).unwrap()
+    }
+
+    fn tlv_file(arguments: &[&str], name: &str) -> String {
+        tlv_files(arguments).into_iter().find(|(output_name, _)| output_name == name).expect("File should be generated").1
+    }
+
+    #[test]
+    fn descriptor_tables_list_the_members_in_index_order() {
+        let source: String = tlv_file(&[], "frame.rune.c");
+
+        assert!(source.contains(
+            "static const rune_tlv_field_t frame_tlv_fields[4] = {\n    { 1, offsetof(frame_t, flag), sizeof(uint8_t), NULL, RUNE_TLV_ENCODING_RAW },\n    { 2, offsetof(frame_t, count), sizeof(uint32_t), NULL, RUNE_TLV_ENCODING_RAW },\n    { 3, offsetof(frame_t, origin), sizeof(point_t), &point_tlv_descriptor, RUNE_TLV_ENCODING_RAW },\n    { 4, offsetof(frame_t, level), sizeof(int32_t), NULL, RUNE_TLV_ENCODING_RAW }\n};"
+        ));
+        assert!(source.contains("const rune_tlv_descriptor_t frame_tlv_descriptor = { frame_tlv_fields, 4 };"));
+
+        let header: String = tlv_file(&[], "frame.rune.h");
+        assert!(header.contains("#include \"rune_tlv.h\""));
+        assert!(header.contains("extern const rune_tlv_descriptor_t frame_tlv_descriptor;"));
+        assert!(header.contains("extern const rune_tlv_descriptor_t point_tlv_descriptor;"));
+    }
+
+    #[test]
+    fn serializers_are_only_output_in_tlv_mode() {
+        let header: String = tlv_file(&[], "rune_tlv.h");
+        assert!(header.contains("RUNE_NODISCARD int rune_tlv_serialize(const rune_tlv_descriptor_t* descriptor, const void* message, unsigned char* buffer, size_t buffer_size, size_t* written);"));
+        assert!(header.contains("RUNE_NODISCARD int rune_tlv_deserialize(const rune_tlv_descriptor_t* descriptor, void* message, const unsigned char* buffer, size_t length);"));
+
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("frame.rune", SCHEMA)]);
+        let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &compile_configurations(&[])).unwrap();
+        assert!(output_files.iter().all(|(name, contents)| !name.starts_with("rune_tlv") && !contents.contains("tlv")));
+    }
+
+    #[test]
+    fn deserializers_skip_unknown_tags() {
+        let source: String = tlv_file(&[], "rune_tlv.c");
+
+        assert!(source.contains("field_length = (size_t)buffer[offset + 1] | ((size_t)buffer[offset + 2] << 8);"));
+        assert!(source.contains(
+            "        for (i = 0; i < descriptor->field_count; i++) {\n            const rune_tlv_field_t* field = &descriptor->fields[i];\n\n            if (field->tag != tag) {\n                continue;\n            }"
+        ));
+        assert!(source.contains("            break;\n        }\n\n        offset += field_length;\n    }\n\n    return 1;"));
+    }
+
+    #[test]
+    fn only_integers_of_16_bits_or_more_are_varint_encoded() {
+        let source: String = tlv_file(&["--varint"], "frame.rune.c");
+
+        assert!(source.contains("{ 1, offsetof(frame_t, flag), sizeof(uint8_t), NULL, RUNE_TLV_ENCODING_RAW }"));
+        assert!(source.contains("{ 2, offsetof(frame_t, count), sizeof(uint32_t), NULL, RUNE_TLV_ENCODING_VARINT }"));
+        assert!(source.contains("{ 3, offsetof(frame_t, origin), sizeof(point_t), &point_tlv_descriptor, RUNE_TLV_ENCODING_RAW }"));
+        assert!(source.contains("{ 4, offsetof(frame_t, level), sizeof(int32_t), NULL, RUNE_TLV_ENCODING_ZIGZAG }"));
+        assert!(source.contains("{ 1, offsetof(point_t, x), sizeof(int16_t), NULL, RUNE_TLV_ENCODING_ZIGZAG }"));
+    }
+
+    #[test]
+    fn varints_are_held_in_the_widest_available_integer() {
+        assert!(
+            tlv_file(&["--varint"], "rune_tlv.h").contains(
+                "#include <stdint.h>\n\n#include \"runic_definitions.h\"\n\n/** Integer holding varint encoded member values while they are converted */\ntypedef uint64_t rune_tlv_varint_t;"
+            )
+        );
+
+        let header: String = tlv_file(&["--varint", "--c-standard", "C89"], "rune_tlv.h");
+        assert!(header.contains("typedef unsigned long rune_tlv_varint_t;"));
+        assert!(!header.contains("#include <stdint.h>"));
+    }
+
+    #[test]
+    fn varint_members_are_loaded_in_the_byte_order_of_the_target() {
+        let source: String = tlv_file(&["--varint"], "rune_tlv.c");
+        assert!(source.contains(
+            "#if defined __LITTLE_ENDIAN__ || (defined __BYTE_ORDER__ && defined __ORDER_LITTLE_ENDIAN__ && __BYTE_ORDER__ == __ORDER_LITTLE_ENDIAN__)\n        value |= (rune_tlv_varint_t)source[i] << (8 * i);\n#else\n        value |= (rune_tlv_varint_t)source[i] << (8 * (size - 1 - i));\n#endif"
+        ));
+
+        let source: String = tlv_file(&["--varint", "--endianness", "big"], "rune_tlv.c");
+        assert!(source.contains("    for (i = 0; i < size; i++) {\n        value |= (rune_tlv_varint_t)source[i] << (8 * (size - 1 - i));\n    }"));
+        assert!(!source.contains("__LITTLE_ENDIAN__"));
+    }
+
+    #[test]
+    fn oversized_varints_are_rejected() {
+        let source: String = tlv_file(&["--varint"], "rune_tlv.c");
+
+        assert!(source.contains("if (shift >= 8 * sizeof(rune_tlv_varint_t)) {"));
+        assert!(source.contains("if (length == 0 || (field->size < sizeof(rune_tlv_varint_t) && (value >> (8 * field->size)) != 0)) {"));
+    }
+}
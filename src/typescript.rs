@@ -0,0 +1,217 @@
+use rune_parser::{
+    RuneFileDescription,
+    scanner::{NumeralSystem, NumericLiteral},
+    types::{ArrayType, BitSize, BitfieldDefinition, DefineValue, EnumDefinition, FieldType, Primitive, StructDefinition, StructMember}
+};
+
+use crate::{
+    c_utilities::{CConfigurations, output_disclaimer},
+    compile_error::CompilerError,
+    output_file::OutputFile
+};
+
+/// Convert NamedVariable to namedVariable, following TypeScript member naming
+fn pascal_to_camel_case(pascal: &str) -> String {
+    let mut characters = pascal.chars();
+
+    match characters.next() {
+        None => String::new(),
+        Some(first) => format!("{0}{1}", first.to_ascii_lowercase(), characters.as_str())
+    }
+}
+
+/// Get the TypeScript literal of a numeric value, keeping the numeral system it was declared in
+fn ts_literal(value: &NumericLiteral) -> String {
+    match value {
+        NumericLiteral::Boolean(value) => value.to_string(),
+        NumericLiteral::PositiveInteger(value, numeral_system) => match numeral_system {
+            NumeralSystem::Binary => format!("0b{0:b}", value),
+            NumeralSystem::Decimal => value.to_string(),
+            NumeralSystem::Hexadecimal => format!("0x{0:X}", value)
+        },
+        NumericLiteral::NegativeInteger(value, _) => value.to_string(),
+        NumericLiteral::Float(value) => format!("{0:?}", value)
+    }
+}
+
+/// Get the TypeScript type of a primitive. Integers wider than 32 bits do not fit a number exactly, and are thus a bigint
+fn ts_primitive_type(primitive: &Primitive) -> &'static str {
+    match primitive {
+        Primitive::Bool => "boolean",
+        Primitive::Char => "string",
+        Primitive::I8 | Primitive::U8 | Primitive::I16 | Primitive::U16 | Primitive::I32 | Primitive::U32 | Primitive::F32 | Primitive::F64 => "number",
+        Primitive::I64 | Primitive::U64 | Primitive::I128 | Primitive::U128 => "bigint"
+    }
+}
+
+/// Get the TypeScript type of a struct member. Char arrays are strings
+fn ts_field_type(data_type: &FieldType) -> Option<String> {
+    match data_type {
+        FieldType::Empty => None,
+        FieldType::Primitive(primitive) => Some(String::from(ts_primitive_type(primitive))),
+        FieldType::UserDefined(name) => Some(name.clone()),
+        FieldType::Array(array_type, _) => Some(match array_type {
+            ArrayType::Primitive(Primitive::Char) => String::from("string"),
+            ArrayType::Primitive(primitive) => format!("{0}[]", ts_primitive_type(primitive)),
+            ArrayType::UserDefined(name) => format!("{0}[]", name)
+        })
+    }
+}
+
+fn output_ts_enum(ts_file: &mut OutputFile, enum_definition: &EnumDefinition) {
+    if let Some(comment) = &enum_definition.comment {
+        ts_file.add_doc_comment(0, comment);
+    }
+
+    ts_file.add_line(format!("export enum {0} {{", enum_definition.name));
+
+    for (i, member) in enum_definition.members.iter().enumerate() {
+        if let Some(comment) = &member.comment {
+            ts_file.add_doc_comment(1, comment);
+        }
+
+        let comma: &'static str = match i == enum_definition.members.len() - 1 {
+            true => "",
+            false => ","
+        };
+
+        ts_file.add_indented_line(1, format!("{0} = {1}{2}", member.identifier, ts_literal(&member.value), comma));
+    }
+
+    ts_file.add_line(String::from("}"));
+    ts_file.add_newline();
+}
+
+fn output_ts_bitfield(ts_file: &mut OutputFile, bitfield_definition: &BitfieldDefinition) {
+    if let Some(comment) = &bitfield_definition.comment {
+        ts_file.add_doc_comment(0, comment);
+    }
+
+    ts_file.add_line(format!("export interface {0} {{", bitfield_definition.name));
+
+    for member in &bitfield_definition.members {
+        if let Some(comment) = &member.comment {
+            ts_file.add_doc_comment(1, comment);
+        }
+
+        // Single bit members are flags
+        let member_type: &'static str = match member.size {
+            BitSize::Unsigned(1) => "boolean",
+            BitSize::Signed(size) | BitSize::Unsigned(size) if size > 32 => "bigint",
+            _ => "number"
+        };
+
+        ts_file.add_indented_line(1, format!("{0}: {1};", pascal_to_camel_case(&member.identifier), member_type));
+    }
+
+    ts_file.add_line(String::from("}"));
+    ts_file.add_newline();
+}
+
+fn output_ts_struct(ts_file: &mut OutputFile, struct_definition: &StructDefinition) {
+    if let Some(comment) = &struct_definition.comment {
+        ts_file.add_doc_comment(0, comment);
+    }
+
+    ts_file.add_line(format!("export interface {0} {{", struct_definition.name));
+
+    let mut members: Vec<StructMember> = struct_definition.members.clone();
+    members.sort_by_key(|member| member.index.value());
+
+    for member in &members {
+        let member_type: String = match ts_field_type(&member.data_type) {
+            None => continue,
+            Some(member_type) => member_type
+        };
+
+        if let Some(comment) = &member.comment {
+            ts_file.add_doc_comment(1, comment);
+        }
+
+        ts_file.add_indented_line(1, format!("{0}: {1};", pascal_to_camel_case(&member.identifier), member_type));
+    }
+
+    ts_file.add_line(String::from("}"));
+    ts_file.add_newline();
+}
+
+/// Outputs a TypeScript module with the defines as constants, the enums as enums, and the bitfields and structs as interfaces
+pub fn output_typescript(file_descriptions: &Vec<RuneFileDescription>, configurations: &CConfigurations, file_name: &str) -> Result<OutputFile, CompilerError> {
+    let mut ts_file: OutputFile = OutputFile::new(String::from(file_name), &configurations.compiler_configurations);
+
+    // Disclaimers
+    // ————————————
+
+    output_disclaimer(&mut ts_file, &configurations.compiler_configurations);
+
+    ts_file.add_line(String::from("// TypeScript definitions of the Rune schema. Integers wider than 32 bits are represented as bigint"));
+    ts_file.add_newline();
+
+    for file in file_descriptions {
+        ts_file.add_line(format!("// {0}{1}.rune", file.relative_path, file.name));
+        ts_file.add_newline();
+
+        // Defines
+        // ————————
+
+        for define in &file.definitions.defines {
+            let value: &DefineValue = match &define.redefinition {
+                Some(redefine) => &redefine.value,
+                None => &define.value
+            };
+
+            if let DefineValue::NumericLiteral(value) = value {
+                if let Some(comment) = &define.comment {
+                    ts_file.add_doc_comment(0, comment);
+                }
+
+                ts_file.add_line(format!("export const {0} = {1};", define.name, ts_literal(value)));
+                ts_file.add_newline();
+            }
+        }
+
+        // Enums, bitfields & structs
+        // ———————————————————————————
+
+        for enum_definition in &file.definitions.enums {
+            output_ts_enum(&mut ts_file, enum_definition);
+        }
+
+        for bitfield_definition in &file.definitions.bitfields {
+            output_ts_bitfield(&mut ts_file, bitfield_definition);
+        }
+
+        for struct_definition in &file.definitions.structs {
+            output_ts_struct(&mut ts_file, struct_definition);
+        }
+    }
+
+    Ok(ts_file)
+}
+
+#[cfg(test)]
+mod tests {
+    use rune_parser::RuneFileDescription;
+
+    use crate::{
+        generate_to_memory,
+        test_utilities::{compile_configurations, parse_schema}
+    };
+
+    const SCHEMA: &str = "define MaxLength 0x10;\n\nenum Mode: u8 {\n    Idle = 0;\n    Running = 2\n}\n\nbitfield Flags: u8 {\n    Enabled: u1 = 0;\n    Level: u3 = 1\n}\n\nstruct Point {\n    X: f32 = 1\n}\n\n// Frame sent every cycle\nstruct Frame {\n    Stamp: u64 = 2;\n    Valid: bool = 1;\n    Offset: i32 = 3;\n    Name: [char; 8] = 4;\n    Path: [Point; 2] = 5;\n    Current: Mode = 6;\n    State: Flags = 7;\n    Raw: [u8; 3] = 8;\n    Big: i64 = 9;\n    Origin: Point = 10\n}\n";
+
+    #[test]
+    fn schema_types_are_mapped_to_typescript() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("schema.rune", SCHEMA)]);
+        let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &compile_configurations(&["--emit-ts", "schema.ts"])).unwrap();
+        let ts: &str = &output_files.iter().find(|(name, _)| name == "schema.ts").expect("TypeScript definitions should be generated").1;
+
+        assert!(ts.contains("export const MaxLength = 0x10;"));
+        assert!(ts.contains("export enum Mode {\n    Idle = 0,\n    Running = 2\n}"));
+        assert!(ts.contains("export interface Flags {\n    enabled: boolean;\n    level: number;\n}"));
+        assert!(ts.contains("export interface Point {\n    x: number;\n}"));
+        assert!(ts.contains(
+            "/** Frame sent every cycle */\nexport interface Frame {\n    valid: boolean;\n    stamp: bigint;\n    offset: number;\n    name: string;\n    path: Point[];\n    current: Mode;\n    state: Flags;\n    raw: number[];\n    big: bigint;\n    origin: Point;\n}"
+        ));
+    }
+}
@@ -0,0 +1,1198 @@
+use std::{
+    fs::{ReadDir, read_dir},
+    path::Path
+};
+
+use rune_parser::{
+    RuneFileDescription,
+    types::{ArrayType, BitSize, FieldType, Primitive, StructMember, UserDefinitionLink}
+};
+
+use crate::{
+    c_utilities::{CPrimitive, CStructMember, CompileConfigurations, FixedPointFormat, collect_dependencies, declares_type, output_base_name, pascal_to_snake_case, pascal_to_uppercase},
+    compile_error::CompilerError,
+    endianness::Endianness,
+    output::*,
+    selection::is_ignored_path
+};
+
+/// Check that an identifier only consists of ASCII letters, digits and underscores. The Rune parser accepts any Unicode letter,
+/// but those are not portable in C identifiers, and the case conversions used to derive C names only handle ASCII letters
+fn validate_identifier(file: &RuneFileDescription, kind: &str, identifier: &str) -> Result<(), CompilerError> {
+    match identifier.chars().find(|character| !character.is_ascii_alphanumeric() && *character != '_') {
+        None => Ok(()),
+        Some(character) => {
+            error!(
+                "{0} \"{1}\" in {2}{3}.rune contains the non-ASCII character '{4}' (U+{5:04X}). Only ASCII letters, digits and underscores are allowed in identifiers",
+                kind, identifier, file.relative_path, file.name, character, character as u32
+            );
+            Err(CompilerError::MalformedSource)
+        }
+    }
+}
+
+/// Validate that all identifiers of the parsed files can be output as valid C identifiers
+pub fn validate_identifiers(file_descriptions: &Vec<RuneFileDescription>) -> Result<(), CompilerError> {
+    for file in file_descriptions {
+        // File names are used for header guards
+        validate_identifier(file, "File name", &file.name)?;
+
+        for define in &file.definitions.defines {
+            validate_identifier(file, "Define", &define.name)?;
+        }
+
+        for enum_definition in &file.definitions.enums {
+            validate_identifier(file, "Enum", &enum_definition.name)?;
+
+            for member in &enum_definition.members {
+                validate_identifier(file, "Enum member", &member.identifier)?;
+            }
+        }
+
+        for bitfield_definition in &file.definitions.bitfields {
+            validate_identifier(file, "Bitfield", &bitfield_definition.name)?;
+
+            for member in &bitfield_definition.members {
+                validate_identifier(file, "Bitfield member", &member.identifier)?;
+            }
+        }
+
+        for struct_definition in &file.definitions.structs {
+            validate_identifier(file, "Struct", &struct_definition.name)?;
+
+            for member in &struct_definition.members {
+                validate_identifier(file, "Struct member", &member.identifier)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that no two names become the same C identifier once converted
+fn validate_unique_names(kind: &str, location: &str, names: &[&String], convert: fn(&str) -> String) -> Result<(), CompilerError> {
+    let mut converted_names: Vec<(String, &String)> = Vec::with_capacity(names.len());
+
+    for name in names {
+        let converted: String = convert(name);
+
+        if let Some((_, original)) = converted_names.iter().find(|(converted_name, _)| *converted_name == converted) {
+            error!(
+                "{0}s \"{1}\" and \"{2}\" in {3} both become \"{4}\" in C. Rename one of them",
+                kind, original, name, location, converted
+            );
+            return Err(CompilerError::MalformedSource);
+        }
+
+        converted_names.push((converted, name));
+    }
+
+    Ok(())
+}
+
+/// Validate that names which are distinct in the Rune files remain distinct after being converted to C naming
+pub fn validate_name_collisions(file_descriptions: &Vec<RuneFileDescription>) -> Result<(), CompilerError> {
+    // Types are declared globally, as <name>_t
+    let mut type_names: Vec<&String> = Vec::with_capacity(0x40);
+
+    for file in file_descriptions {
+        let location: String = format!("{0}{1}.rune", file.relative_path, file.name);
+
+        type_names.extend(file.definitions.enums.iter().map(|definition| &definition.name));
+        type_names.extend(file.definitions.bitfields.iter().map(|definition| &definition.name));
+        type_names.extend(file.definitions.structs.iter().map(|definition| &definition.name));
+
+        // Enum members are not prefixed, so they must be unique across all enums of the header
+        let enum_members: Vec<&String> = file
+            .definitions
+            .enums
+            .iter()
+            .flat_map(|definition| definition.members.iter().map(|member| &member.identifier))
+            .collect();
+        validate_unique_names("Enum member", &location, &enum_members, pascal_to_uppercase)?;
+
+        for bitfield_definition in &file.definitions.bitfields {
+            let members: Vec<&String> = bitfield_definition.members.iter().map(|member| &member.identifier).collect();
+            validate_unique_names("Bitfield member", &format!("bitfield {0} of {1}", bitfield_definition.name, location), &members, pascal_to_snake_case)?;
+        }
+
+        for struct_definition in &file.definitions.structs {
+            let members: Vec<&String> = struct_definition.members.iter().map(|member| &member.identifier).collect();
+            validate_unique_names("Struct member", &format!("struct {0} of {1}", struct_definition.name, location), &members, pascal_to_snake_case)?;
+        }
+    }
+
+    validate_unique_names("Type", "the Rune files", &type_names, pascal_to_snake_case)
+}
+
+/// Check that a name does not become a keyword of the C standard once converted
+fn validate_not_keyword(kind: &str, location: &str, name: &str, converted: &str, configurations: &CompileConfigurations) -> Result<(), CompilerError> {
+    if configurations.c_standard.keywords().contains(&converted) {
+        error!(
+            "{0} \"{1}\" in {2} becomes \"{3}\" in C, which is reserved in {4}. Rename it",
+            kind, name, location, converted, configurations.c_standard
+        );
+        return Err(CompilerError::MalformedSource);
+    }
+
+    Ok(())
+}
+
+/// Validate that names output as they are, or in snake case, do not become keywords of the selected C standard. Type names get a _t
+/// suffix and enum members are uppercase, so they can never collide
+pub fn validate_keywords(file_descriptions: &Vec<RuneFileDescription>, configurations: &CompileConfigurations) -> Result<(), CompilerError> {
+    for file in file_descriptions {
+        let location: String = format!("{0}{1}.rune", file.relative_path, file.name);
+
+        for define in &file.definitions.defines {
+            validate_not_keyword("Define", &location, &define.name, &define.name, configurations)?;
+        }
+
+        for bitfield_definition in &file.definitions.bitfields {
+            for member in &bitfield_definition.members {
+                let location: String = format!("bitfield {0} of {1}", bitfield_definition.name, location);
+                validate_not_keyword("Bitfield member", &location, &member.identifier, &pascal_to_snake_case(&member.identifier), configurations)?;
+            }
+        }
+
+        for struct_definition in &file.definitions.structs {
+            for member in &struct_definition.members {
+                let location: String = format!("struct {0} of {1}", struct_definition.name, location);
+                validate_not_keyword("Struct member", &location, &member.identifier, &pascal_to_snake_case(&member.identifier), configurations)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate that every bitfield member can hold a meaningful value, and has a place within the backing type. The parser only checks
+/// that the members fit the backing type together, so a named zero width member, which is invalid C, or a one bit signed member,
+/// which can only hold 0 and -1, get through. Members are laid out in index order, so the indexes must run from 0 without gaps, as
+/// a member past the last position is left out of the output
+pub fn validate_bitfield_sizes(file_descriptions: &Vec<RuneFileDescription>) -> Result<(), CompilerError> {
+    for file in file_descriptions {
+        for bitfield_definition in &file.definitions.bitfields {
+            let backing_size: u64 = bitfield_definition.backing_type.c_size() * 8;
+            let member_count: u64 = bitfield_definition.members.len() as u64;
+
+            for member in &bitfield_definition.members {
+                let minimum_size: u64 = match member.size {
+                    BitSize::Signed(_) => 2,
+                    BitSize::Unsigned(_) => 1
+                };
+
+                if member.size.absolute() < minimum_size {
+                    error!(
+                        "Member {0} of bitfield {1} in {2}{3}.rune is {4} bits wide, but {5} members must be at least {6} bits wide",
+                        member.identifier,
+                        bitfield_definition.name,
+                        file.relative_path,
+                        file.name,
+                        member.size.absolute(),
+                        match member.size {
+                            BitSize::Signed(_) => "signed",
+                            BitSize::Unsigned(_) => "unsigned"
+                        },
+                        minimum_size
+                    );
+                    return Err(CompilerError::MalformedSource);
+                }
+
+                if member.size.absolute() > backing_size {
+                    error!(
+                        "Member {0} of bitfield {1} in {2}{3}.rune is {4} bits wide, which does not fit within its {5} bit backing type",
+                        member.identifier,
+                        bitfield_definition.name,
+                        file.relative_path,
+                        file.name,
+                        member.size.absolute(),
+                        backing_size
+                    );
+                    return Err(CompilerError::MalformedSource);
+                }
+
+                if member.index >= member_count {
+                    error!(
+                        "Member {0} of bitfield {1} in {2}{3}.rune has index {4}, but the indexes of a bitfield with {5} members must run from 0 to {6}",
+                        member.identifier,
+                        bitfield_definition.name,
+                        file.relative_path,
+                        file.name,
+                        member.index,
+                        member_count,
+                        member_count - 1
+                    );
+                    return Err(CompilerError::MalformedSource);
+                }
+
+                if let Some(other_member) = bitfield_definition.members.iter().find(|other| other.index == member.index && other.identifier != member.identifier) {
+                    error!(
+                        "Members {0} and {1} of bitfield {2} in {3}{4}.rune overlap, as both have index {5}",
+                        member.identifier, other_member.identifier, bitfield_definition.name, file.relative_path, file.name, member.index
+                    );
+                    return Err(CompilerError::MalformedSource);
+                }
+            }
+
+            let total_size: u64 = bitfield_definition.members.iter().map(|member| member.size.absolute()).sum();
+
+            if total_size > backing_size {
+                error!(
+                    "Members of bitfield {0} in {1}{2}.rune take up {3} bits, which does not fit within its {4} bit backing type",
+                    bitfield_definition.name, file.relative_path, file.name, total_size, backing_size
+                );
+                return Err(CompilerError::MalformedSource);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate that every struct has at least one member with a size. Zero-size members are left out of the output, and C does not
+/// allow structs without members, so such structs cannot be output
+pub fn validate_struct_sizes(file_descriptions: &Vec<RuneFileDescription>, configurations: &CompileConfigurations) -> Result<(), CompilerError> {
+    for file in file_descriptions {
+        for struct_definition in &file.definitions.structs {
+            let mut has_size: bool = false;
+
+            for member in &struct_definition.members {
+                if member.layout_size(configurations)? != 0 {
+                    has_size = true;
+                    break;
+                }
+            }
+
+            if !has_size {
+                error!(
+                    "Struct {0} in {1}{2}.rune has no members with a size, and C does not allow empty structs. Give it a member or remove it",
+                    struct_definition.name, file.relative_path, file.name
+                );
+                return Err(CompilerError::MalformedSource);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Collect the paths of all Rune files in a folder and its subfolders, relative to the input folder and separated by '/', as the
+/// parser searches them
+fn find_rune_files(folder_path: &Path, relative_path: &str, rune_files: &mut Vec<String>) -> Result<(), CompilerError> {
+    let entries: ReadDir = match read_dir(folder_path) {
+        Err(error) => {
+            error!("Could not read directory {0:?}. Got error {1}", folder_path, error);
+            return Err(CompilerError::FileSystemError(error));
+        },
+        Ok(entries) => entries
+    };
+
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let Ok(file_name) = entry.file_name().into_string() else {
+            continue;
+        };
+
+        if file_type.is_dir() {
+            find_rune_files(&entry.path(), &format!("{0}{1}/", relative_path, file_name), rune_files)?;
+        } else if file_type.is_file() && file_name.ends_with(".rune") {
+            rune_files.push(format!("{0}{1}", relative_path, file_name));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate that every Rune file in the input folders was parsed. The parser reports errors within a file, such as an enum value
+/// out of the range of its backing type, but then leaves the file out and carries on, which would silently drop its output
+pub fn validate_all_files_parsed(file_descriptions: &Vec<RuneFileDescription>, input_paths: &[&Path], ignore_patterns: &[Vec<String>]) -> Result<(), CompilerError> {
+    let mut unparsed_files: Vec<String> = Vec::with_capacity(file_descriptions.len());
+
+    for input_path in input_paths {
+        find_rune_files(input_path, "", &mut unparsed_files)?;
+    }
+
+    // Ignored files are dropped anyway, so they do not need to parse
+    unparsed_files.retain(|unparsed_file| !is_ignored_path(unparsed_file, input_paths, ignore_patterns));
+
+    for file in file_descriptions {
+        let parsed_file: String = format!("{0}{1}.rune", file.relative_path, file.name);
+
+        if let Some(position) = unparsed_files.iter().position(|unparsed_file| *unparsed_file == parsed_file) {
+            unparsed_files.remove(position);
+        }
+    }
+
+    match unparsed_files.is_empty() {
+        true => Ok(()),
+        false => {
+            error!("Could not parse the following Rune files: {0}. See the parser errors above", unparsed_files.join(", "));
+            Err(CompilerError::MalformedSource)
+        }
+    }
+}
+
+/// Validate that no two files are output under the same name with --flatten, such as sub/dir_foo.rune and sub_dir/foo.rune
+pub fn validate_flattened_names(file_descriptions: &[RuneFileDescription], configurations: &CompileConfigurations) -> Result<(), CompilerError> {
+    if !configurations.flatten || configurations.amalgamate {
+        return Ok(());
+    }
+
+    for (index, file) in file_descriptions.iter().enumerate() {
+        let base_name: String = output_base_name(file, configurations);
+
+        if let Some(other_file) = file_descriptions[index + 1..].iter().find(|other_file| output_base_name(other_file, configurations) == base_name) {
+            error!(
+                "Files {0}{1}.rune and {2}{3}.rune would both be output as {4} with --flatten!",
+                file.relative_path, file.name, other_file.relative_path, other_file.name, base_name
+            );
+            return Err(CompilerError::ConfigurationError);
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate that every verifier field can hold a checksum, when verifier functions are output
+pub fn validate_verifiers(file_descriptions: &[RuneFileDescription], configurations: &CompileConfigurations) -> Result<(), CompilerError> {
+    if !configurations.verifiers {
+        return Ok(());
+    }
+
+    for struct_definition in file_descriptions.iter().flat_map(|file| file.definitions.structs.iter()) {
+        let verifiers: Vec<&StructMember> = struct_definition.members.iter().filter(|member| member.index.is_verifier()).collect();
+
+        // The parser rejects these, but check anyway, as the verify function compares a single field
+        if verifiers.len() > 1 {
+            error!("Struct {0} declares {1} verifier fields, while only one is allowed!", struct_definition.name, verifiers.len());
+            return Err(CompilerError::MalformedSource);
+        }
+
+        for member in verifiers {
+            if !matches!(member.data_type, FieldType::Primitive(Primitive::U8 | Primitive::U16 | Primitive::U32 | Primitive::U64)) {
+                error!(
+                    "Verifier field {0} of struct {1} must be an unsigned integer of at most 64 bits to hold its checksum with --emit-verifiers!",
+                    member.identifier, struct_definition.name
+                );
+                return Err(CompilerError::MalformedSource);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate that the type name prefix, if any, can start a C identifier
+pub fn validate_prefix(configurations: &CompileConfigurations) -> Result<(), CompilerError> {
+    let Some(prefix) = &configurations.prefix else {
+        return Ok(());
+    };
+
+    let starts_with_letter: bool = prefix.chars().next().is_some_and(|character| character.is_ascii_alphabetic());
+    let valid_characters: bool = prefix.chars().all(|character| character.is_ascii_alphanumeric() || character == '_');
+
+    match starts_with_letter && valid_characters {
+        true => Ok(()),
+        false => {
+            error!("Prefix \"{0}\" must start with an ASCII letter, followed only by ASCII letters, digits and underscores", prefix);
+            Err(CompilerError::InvalidArgument)
+        }
+    }
+}
+
+/// Validate that the type and _INIT macro of every user defined struct member are available where the struct is output. The parser
+/// links types across all files, but the generated header only sees the types of its own file and of the files it includes
+pub fn validate_type_dependencies(file_descriptions: &Vec<RuneFileDescription>) -> Result<(), CompilerError> {
+    for file in file_descriptions {
+        let mut dependencies: Vec<&RuneFileDescription> = Vec::with_capacity(0x10);
+        collect_dependencies(file, file_descriptions, &mut dependencies);
+
+        for (position, struct_definition) in file.definitions.structs.iter().enumerate() {
+            for member in &struct_definition.members {
+                let type_name: &String = match &member.data_type {
+                    FieldType::UserDefined(name) | FieldType::Array(ArrayType::UserDefined(name), _) => name,
+                    _ => continue
+                };
+
+                if dependencies.iter().any(|dependency| !std::ptr::eq(*dependency, file) && declares_type(dependency, type_name)) {
+                    continue;
+                }
+
+                // Enums and bitfields are output before all structs, while structs are output in declaration order
+                if file.definitions.structs.iter().skip(position).any(|definition| &definition.name == type_name) {
+                    error!(
+                        "Member {0} of struct {1} in {2}{3}.rune uses struct {4}, which must be declared before {1}",
+                        member.identifier, struct_definition.name, file.relative_path, file.name, type_name
+                    );
+                    return Err(CompilerError::MalformedSource);
+                }
+
+                if declares_type(file, type_name) {
+                    continue;
+                }
+
+                match file_descriptions.iter().find(|declaring_file| declares_type(declaring_file, type_name)) {
+                    Some(declaring_file) => error!(
+                        "Member {0} of struct {1} in {2}{3}.rune uses {4} from {5}{6}.rune, which is not included, leaving {7}_t and {8}_INIT undefined. Add include \"{5}{6}.rune\";",
+                        member.identifier,
+                        struct_definition.name,
+                        file.relative_path,
+                        file.name,
+                        type_name,
+                        declaring_file.relative_path,
+                        declaring_file.name,
+                        pascal_to_snake_case(type_name),
+                        pascal_to_uppercase(type_name)
+                    ),
+                    None => error!("Member {0} of struct {1} uses undeclared type {2}", member.identifier, struct_definition.name, type_name)
+                }
+                return Err(CompilerError::MalformedSource);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Find the struct member passed to an option as <Struct>.<Member>
+fn find_struct_member<'a>(option: &str, struct_name: &str, member_name: &str, file_descriptions: &'a [RuneFileDescription]) -> Result<&'a StructMember, CompilerError> {
+    let struct_definition = match file_descriptions
+        .iter()
+        .flat_map(|file| file.definitions.structs.iter())
+        .find(|definition| definition.name == struct_name)
+    {
+        Some(struct_definition) => struct_definition,
+        None => {
+            error!("Struct {0} passed to {1} is not declared in any Rune file!", struct_name, option);
+            return Err(CompilerError::InvalidArgument);
+        }
+    };
+
+    match struct_definition.members.iter().find(|member| member.identifier == member_name) {
+        Some(member) => Ok(member),
+        None => {
+            error!("Struct {0} passed to {1} has no member {2}!", struct_name, option, member_name);
+            Err(CompilerError::InvalidArgument)
+        }
+    }
+}
+
+/// Get the names of the members an inlined member adds to its parent, including those of nested inlined members
+fn inlined_member_names<'a>(configurations: &CompileConfigurations, member: &'a StructMember, names: &mut Vec<&'a String>) {
+    if let UserDefinitionLink::StructLink(struct_definition) = &member.user_definition_link {
+        for inlined_member in &struct_definition.members {
+            match configurations.is_inlined(&struct_definition.name, inlined_member) {
+                true => inlined_member_names(configurations, inlined_member, names),
+                false => names.push(&inlined_member.identifier)
+            }
+        }
+    }
+}
+
+/// Validate that every inlined member names a single struct member of a declared struct, and that the members it adds to its parent
+/// do not collide with the members already there
+pub fn validate_inline_members(file_descriptions: &Vec<RuneFileDescription>, configurations: &CompileConfigurations) -> Result<(), CompilerError> {
+    if configurations.inline_members.is_empty() {
+        return Ok(());
+    }
+
+    // Anonymous structs are only available from C11
+    if !configurations.c_standard.allows_anonymous_members() {
+        error!(
+            "Inlined members are output as anonymous structs, which are only available from C11, but {0} was selected!",
+            configurations.c_standard
+        );
+        return Err(CompilerError::SourceAndCStandardMismatch);
+    }
+
+    // Generated functions access nested structs through the member name, which the anonymous struct does not have
+    if configurations.codec || configurations.getters || configurations.verifiers || configurations.cpp || configurations.safe_packed_access() {
+        error!("Inlined members cannot be combined with --emit-codec, --emit-getters, --emit-verifiers, --emit-cpp or --safe-packed-access, which access nested structs by member name!");
+        return Err(CompilerError::ConfigurationError);
+    }
+
+    for inline_member in &configurations.inline_members {
+        let (struct_name, member_name): (&str, &str) = match inline_member.split_once('.') {
+            Some(names) => names,
+            None => {
+                error!("Inlined member \"{0}\" must be written as <Struct>.<Member>!", inline_member);
+                return Err(CompilerError::InvalidArgument);
+            }
+        };
+
+        let member: &StructMember = find_struct_member("--inline-members", struct_name, member_name, file_descriptions)?;
+
+        // Arrays of structs cannot be anonymous
+        if !matches!((&member.data_type, &member.user_definition_link), (FieldType::UserDefined(_), UserDefinitionLink::StructLink(_))) {
+            error!(
+                "Member {0} of struct {1} passed to --inline-members is not a single struct, and cannot be inlined!",
+                member_name, struct_name
+            );
+            return Err(CompilerError::InvalidArgument);
+        }
+    }
+
+    // The members of all inlined members share the namespace of the parent
+    for file in file_descriptions {
+        for struct_definition in &file.definitions.structs {
+            let mut member_names: Vec<(String, &String)> = Vec::with_capacity(struct_definition.members.len());
+
+            for member in &struct_definition.members {
+                let mut names: Vec<&String> = Vec::with_capacity(0x10);
+
+                match configurations.is_inlined(&struct_definition.name, member) {
+                    true => inlined_member_names(configurations, member, &mut names),
+                    false => names.push(&member.identifier)
+                }
+
+                for name in names {
+                    let converted: String = pascal_to_snake_case(name);
+
+                    if let Some((_, original)) = member_names.iter().find(|(converted_name, _)| *converted_name == converted) {
+                        error!(
+                            "Members \"{0}\" and \"{1}\" of struct {2} in {3}{4}.rune both become \"{5}\" once inlined members are expanded. Rename one of them",
+                            original, name, struct_definition.name, file.relative_path, file.name, converted
+                        );
+                        return Err(CompilerError::MalformedSource);
+                    }
+
+                    member_names.push((converted, name));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate that every forced alignment names a struct member of a declared struct, and is a power of two at least as strict as the
+/// natural alignment of the member, as C does not allow weakening it
+pub fn validate_member_alignments(file_descriptions: &[RuneFileDescription], configurations: &CompileConfigurations) -> Result<(), CompilerError> {
+    if configurations.member_alignments.is_empty() {
+        return Ok(());
+    }
+
+    if !configurations.c_standard.allows_alignas() {
+        error!(
+            "Member alignments are output as alignment specifiers, which are only available from C11, but {0} was selected!",
+            configurations.c_standard
+        );
+        return Err(CompilerError::SourceAndCStandardMismatch);
+    }
+
+    // Packing removes all alignment, which an alignment specifier would add back in a compiler specific way
+    if configurations.pack_data {
+        error!("Member alignments cannot be combined with --pack-data!");
+        return Err(CompilerError::ConfigurationError);
+    }
+
+    for member_alignment in &configurations.member_alignments {
+        let (struct_name, member_name, alignment): (&str, &str, &str) = match member_alignment
+            .split_once('=')
+            .and_then(|(path, alignment)| path.split_once('.').map(|(struct_name, member_name)| (struct_name, member_name, alignment)))
+        {
+            Some(parts) => parts,
+            None => {
+                error!("Member alignment \"{0}\" must be written as <Struct>.<Member>=<Alignment>!", member_alignment);
+                return Err(CompilerError::InvalidArgument);
+            }
+        };
+
+        let alignment: u64 = match alignment.parse::<u64>() {
+            Ok(alignment) if alignment.is_power_of_two() => alignment,
+            _ => {
+                error!("Alignment \"{0}\" of member {1}.{2} must be a power of two!", alignment, struct_name, member_name);
+                return Err(CompilerError::InvalidArgument);
+            }
+        };
+
+        let member: &StructMember = find_struct_member("--align-members", struct_name, member_name, file_descriptions)?;
+
+        // Anonymous structs have no declarator to put the alignment specifier on
+        if configurations.is_inlined(struct_name, member) {
+            error!("Member {0}.{1} is inlined, and cannot be aligned!", struct_name, member_name);
+            return Err(CompilerError::InvalidArgument);
+        }
+
+        let natural_alignment: u64 = member.layout_alignment(configurations)?;
+
+        if alignment < natural_alignment {
+            error!(
+                "Alignment {0} of member {1}.{2} is less strict than its natural alignment of {3}, which C does not allow!",
+                alignment, struct_name, member_name, natural_alignment
+            );
+            return Err(CompilerError::InvalidArgument);
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate the fixed-point members passed with --fixed-point-members, which must be integers, or arrays of integers, whose size and
+/// signedness match their Q format
+pub fn validate_fixed_point_members(file_descriptions: &[RuneFileDescription], configurations: &CompileConfigurations) -> Result<(), CompilerError> {
+    for fixed_point_member in &configurations.fixed_point_members {
+        let (struct_name, member_name, format): (&str, &str, &str) = match fixed_point_member
+            .split_once('=')
+            .and_then(|(path, format)| path.split_once('.').map(|(struct_name, member_name)| (struct_name, member_name, format)))
+        {
+            Some(parts) => parts,
+            None => {
+                error!("Fixed-point member \"{0}\" must be written as <Struct>.<Member>=Q<Integer bits>.<Fractional bits>!", fixed_point_member);
+                return Err(CompilerError::InvalidArgument);
+            }
+        };
+
+        let format: FixedPointFormat = match FixedPointFormat::from_string(format) {
+            Some(format) => format,
+            None => {
+                error!(
+                    "Format \"{0}\" of member {1}.{2} must be written as Q<Integer bits>.<Fractional bits>, or UQ for unsigned values!",
+                    format, struct_name, member_name
+                );
+                return Err(CompilerError::InvalidArgument);
+            }
+        };
+
+        let member: &StructMember = find_struct_member("--fixed-point-members", struct_name, member_name, file_descriptions)?;
+
+        let primitive: &Primitive = match &member.data_type {
+            FieldType::Primitive(primitive) | FieldType::Array(ArrayType::Primitive(primitive), _)
+                if matches!(
+                    primitive,
+                    Primitive::I8 | Primitive::I16 | Primitive::I32 | Primitive::I64 | Primitive::U8 | Primitive::U16 | Primitive::U32 | Primitive::U64
+                ) =>
+            {
+                primitive
+            },
+            _ => {
+                error!(
+                    "Member {0}.{1} must be an integer of at most 64 bits, or an array of them, to hold fixed-point values!",
+                    struct_name, member_name
+                );
+                return Err(CompilerError::InvalidArgument);
+            }
+        };
+
+        if primitive.is_signed() != format.signed || primitive.c_size() * 8 != format.bit_size() {
+            error!(
+                "Member {0}.{1} is a {2} bit {3} integer, which cannot hold {4} values of {5} bits. Use Q for signed and UQ for unsigned integers, with the sign bit counted as an integer bit",
+                struct_name,
+                member_name,
+                primitive.c_size() * 8,
+                match primitive.is_signed() {
+                    true => "signed",
+                    false => "unsigned"
+                },
+                format,
+                format.bit_size()
+            );
+            return Err(CompilerError::InvalidArgument);
+        }
+    }
+
+    Ok(())
+}
+
+/// Size of a single element of the member, or 0 for nested structs, whose members are checked on their own
+fn element_size(member: &StructMember) -> u64 {
+    match (&member.data_type, &member.user_definition_link) {
+        (FieldType::Primitive(primitive), _) | (FieldType::Array(ArrayType::Primitive(primitive), _), _) => primitive.c_size(),
+        (_, UserDefinitionLink::EnumLink(enum_definition)) => enum_definition.backing_type.c_size(),
+        (_, UserDefinitionLink::BitfieldLink(bitfield_definition)) => bitfield_definition.backing_type.c_size(),
+        _ => 0
+    }
+}
+
+/// Validate that the output does not rely on implementation defined behavior, reporting every hazard found
+pub fn validate_strict(file_descriptions: &Vec<RuneFileDescription>, configurations: &CompileConfigurations) -> Result<(), CompilerError> {
+    let mut violations: usize = 0;
+
+    for file in file_descriptions {
+        // Enums are only guaranteed to have the size of their backing type from C23
+        if !configurations.c_standard.allows_enum_backing_type() {
+            for enum_definition in &file.definitions.enums {
+                if !configurations.strict_enum_width {
+                    error!(
+                        "Enum {0} in {1}{2}.rune: before C23 the size of an enum is implementation defined. Pass --strict-enum-width to store enum fields as their backing type, or use C23",
+                        enum_definition.name, file.relative_path, file.name
+                    );
+                    violations += 1;
+                }
+
+                // The value coercing the enum to the size of its backing type does not fit an int from 4 bytes
+                if enum_definition.backing_type.c_size() >= 4 {
+                    error!(
+                        "Enum {0} in {1}{2}.rune: before C23 enumeration constants must fit an int, which {3} backed values are not guaranteed to. Use a smaller backing type, or C23",
+                        enum_definition.name,
+                        file.relative_path,
+                        file.name,
+                        enum_definition.backing_type.to_c_type(&configurations.c_standard)?
+                    );
+                    violations += 1;
+                }
+            }
+        }
+
+        // The order and packing of bits within a bitfield is implementation defined
+        for bitfield_definition in &file.definitions.bitfields {
+            error!(
+                "Bitfield {0} in {1}{2}.rune: the layout of C bitfields is implementation defined. Use an integer field with masks instead",
+                bitfield_definition.name, file.relative_path, file.name
+            );
+            violations += 1;
+        }
+
+        for struct_definition in &file.definitions.structs {
+            for member in &struct_definition.members {
+                let primitive: &Primitive = match &member.data_type {
+                    FieldType::Primitive(primitive) | FieldType::Array(ArrayType::Primitive(primitive), _) => primitive,
+                    _ => continue
+                };
+
+                match primitive {
+                    // Whether char is signed is implementation defined
+                    Primitive::Char => {
+                        error!(
+                            "Member {0} of struct {1} in {2}{3}.rune: the signedness of char is implementation defined. Use i8 or u8 instead",
+                            member.identifier, struct_definition.name, file.relative_path, file.name
+                        );
+                        violations += 1;
+                    },
+                    // There is no native 128 bit integer, so they are output as byte arrays in host byte order
+                    Primitive::I128 | Primitive::U128 => {
+                        error!(
+                            "Member {0} of struct {1} in {2}{3}.rune: C has no standard 128 bit integer, so the value is stored as a byte array in host byte order. Use two 64 bit fields instead",
+                            member.identifier, struct_definition.name, file.relative_path, file.name
+                        );
+                        violations += 1;
+                    },
+                    _ => ()
+                }
+            }
+        }
+    }
+
+    // Multi-byte members are stored in the byte order of the host unless it is fixed at generation time
+    if configurations.endianness == Endianness::Native
+        && let Some((file, struct_definition, member)) = file_descriptions
+            .iter()
+            .flat_map(|file| file.definitions.structs.iter().map(move |struct_definition| (file, struct_definition)))
+            .find_map(|(file, struct_definition)| struct_definition.members.iter().find(|member| element_size(member) > 1).map(|member| (file, struct_definition, member)))
+    {
+        error!(
+            "Member {0} of struct {1} in {2}{3}.rune: multi-byte members are stored in the byte order of the host, which is implementation defined. Pass --endianness little or big to fix it",
+            member.identifier, struct_definition.name, file.relative_path, file.name
+        );
+        violations += 1;
+    }
+
+    match violations {
+        0 => Ok(()),
+        _ => {
+            error!("Found {0} uses of implementation defined behavior, which are not allowed in strict mode!", violations);
+            Err(CompilerError::ImplementationDefinedBehavior)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::remove_dir_all,
+        path::{Path, PathBuf}
+    };
+
+    use rune_parser::{RuneFileDescription, parser_rune_files, types::BitSize};
+
+    use crate::{
+        compile_error::CompilerError,
+        test_utilities::{compile_configurations, parse_schema, write_schema},
+        validation::{
+            validate_all_files_parsed, validate_bitfield_sizes, validate_fixed_point_members, validate_flattened_names, validate_identifiers, validate_inline_members, validate_keywords,
+            validate_name_collisions, validate_strict, validate_struct_sizes, validate_type_dependencies, validate_verifiers
+        }
+    };
+
+    const BITFIELD_SCHEMA: &str = "bitfield Control: u8 {\n    Enabled: u1 = 0;\n    Level: i3 = 1;\n    Mode: u2 = 2\n}\n";
+
+    fn bitfield_schema() -> Vec<RuneFileDescription> {
+        parse_schema(&[("control.rune", BITFIELD_SCHEMA)])
+    }
+
+    #[test]
+    fn valid_bitfield_is_accepted() {
+        assert!(validate_bitfield_sizes(&bitfield_schema()).is_ok());
+    }
+
+    #[test]
+    fn zero_width_member_is_rejected() {
+        let mut file_descriptions: Vec<RuneFileDescription> = bitfield_schema();
+        file_descriptions[0].definitions.bitfields[0].members[0].size = BitSize::Unsigned(0);
+
+        assert!(matches!(validate_bitfield_sizes(&file_descriptions), Err(CompilerError::MalformedSource)));
+    }
+
+    #[test]
+    fn one_bit_signed_member_is_rejected() {
+        let mut file_descriptions: Vec<RuneFileDescription> = bitfield_schema();
+        file_descriptions[0].definitions.bitfields[0].members[1].size = BitSize::Signed(1);
+
+        assert!(matches!(validate_bitfield_sizes(&file_descriptions), Err(CompilerError::MalformedSource)));
+    }
+
+    #[test]
+    fn over_wide_member_is_rejected() {
+        let mut file_descriptions: Vec<RuneFileDescription> = bitfield_schema();
+        file_descriptions[0].definitions.bitfields[0].members[2].size = BitSize::Unsigned(9);
+
+        assert!(matches!(validate_bitfield_sizes(&file_descriptions), Err(CompilerError::MalformedSource)));
+    }
+
+    #[test]
+    fn members_exceeding_backing_type_together_are_rejected() {
+        let mut file_descriptions: Vec<RuneFileDescription> = bitfield_schema();
+        file_descriptions[0].definitions.bitfields[0].members[2].size = BitSize::Unsigned(5);
+
+        assert!(matches!(validate_bitfield_sizes(&file_descriptions), Err(CompilerError::MalformedSource)));
+    }
+
+    #[test]
+    fn index_past_last_member_is_rejected() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("control.rune", "bitfield Control: u16 {\n    Enabled: u2 = 0;\n    Level: u2 = 1;\n    Offset: i3 = 5\n}\n")]);
+
+        assert!(matches!(validate_bitfield_sizes(&file_descriptions), Err(CompilerError::MalformedSource)));
+    }
+
+    #[test]
+    fn overlapping_members_are_rejected() {
+        let mut file_descriptions: Vec<RuneFileDescription> = bitfield_schema();
+        file_descriptions[0].definitions.bitfields[0].members[2].index = 1;
+
+        assert!(matches!(validate_bitfield_sizes(&file_descriptions), Err(CompilerError::MalformedSource)));
+    }
+
+    #[test]
+    fn strict_rejects_native_byte_order_with_multi_byte_members() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("reading.rune", "struct Reading {\n    Value: u16 = 1\n}\n")]);
+
+        assert!(matches!(
+            validate_strict(&file_descriptions, &compile_configurations(&[])),
+            Err(CompilerError::ImplementationDefinedBehavior)
+        ));
+        assert!(validate_strict(&file_descriptions, &compile_configurations(&["--endianness", "little"])).is_ok());
+    }
+
+    #[test]
+    fn strict_allows_native_byte_order_with_single_byte_members() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("reading.rune", "struct Reading {\n    Value: u8 = 1;\n    Valid: bool = 2\n}\n")]);
+
+        assert!(validate_strict(&file_descriptions, &compile_configurations(&[])).is_ok());
+    }
+
+    #[test]
+    fn ascii_identifiers_are_accepted() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("sensor.rune", "struct Sensor {\n    Temperature_2: u8 = 1\n}\n")]);
+
+        assert!(validate_identifiers(&file_descriptions).is_ok());
+    }
+
+    #[test]
+    fn accented_identifiers_are_rejected() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("sensor.rune", "struct Sensor {\n    Température: u8 = 1\n}\n")]);
+
+        assert!(matches!(validate_identifiers(&file_descriptions), Err(CompilerError::MalformedSource)));
+    }
+
+    const COMMON_SCHEMA: &str = "enum Mode: u8 {\n    Idle = 0;\n    Running = 1\n}\n\nstruct Point {\n    X: i16 = 1\n}\n";
+
+    #[test]
+    fn types_of_included_files_are_accepted() {
+        let device: &str = "include \"common.rune\";\n\nstruct Device {\n    Current: Mode = 1;\n    Origin: Point = 2\n}\n";
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("common.rune", COMMON_SCHEMA), ("device.rune", device)]);
+
+        assert!(validate_type_dependencies(&file_descriptions).is_ok());
+    }
+
+    #[test]
+    fn types_of_files_not_included_are_rejected() {
+        let device: &str = "struct Device {\n    Origin: Point = 1\n}\n";
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("common.rune", COMMON_SCHEMA), ("device.rune", device)]);
+
+        assert!(matches!(validate_type_dependencies(&file_descriptions), Err(CompilerError::MalformedSource)));
+    }
+
+    #[test]
+    fn structs_used_before_their_declaration_are_rejected() {
+        let schema: &str = "struct Device {\n    Origin: Point = 1\n}\n\nstruct Point {\n    X: i16 = 1\n}\n";
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("device.rune", schema)]);
+
+        assert!(matches!(validate_type_dependencies(&file_descriptions), Err(CompilerError::MalformedSource)));
+    }
+
+    #[test]
+    fn distinct_names_are_accepted() {
+        let schema: &str = "enum Mode: u8 {\n    Idle = 0;\n    Running = 1\n}\n\nstruct Status {\n    MyValue: u8 = 1;\n    MyOtherValue: u8 = 2\n}\n";
+
+        assert!(validate_name_collisions(&parse_schema(&[("status.rune", schema)])).is_ok());
+    }
+
+    #[test]
+    fn struct_members_colliding_in_snake_case_are_rejected() {
+        let schema: &str = "struct Status {\n    MyValue: u8 = 1;\n    myValue: u8 = 2\n}\n";
+
+        assert!(matches!(validate_name_collisions(&parse_schema(&[("status.rune", schema)])), Err(CompilerError::MalformedSource)));
+    }
+
+    #[test]
+    fn enum_members_colliding_in_uppercase_are_rejected() {
+        let schema: &str = "enum Mode: u8 {\n    Idle = 0;\n    Running = 1\n}\n\nenum State: u8 {\n    idle = 0\n}\n";
+
+        assert!(matches!(validate_name_collisions(&parse_schema(&[("status.rune", schema)])), Err(CompilerError::MalformedSource)));
+    }
+
+    #[test]
+    fn type_names_colliding_across_files_are_rejected() {
+        let first: &str = "struct StatusFrame {\n    Value: u8 = 1\n}\n";
+        let second: &str = "struct statusFrame {\n    Value: u8 = 1\n}\n";
+
+        assert!(matches!(
+            validate_name_collisions(&parse_schema(&[("first.rune", first), ("second.rune", second)])),
+            Err(CompilerError::MalformedSource)
+        ));
+    }
+
+    /// Parse the given Rune files as the compiler does, leaving out those the parser reports errors in, and validate that all
+    /// of them were parsed
+    fn validate_parsed_schema(files: &[(&str, &str)]) -> Result<(), CompilerError> {
+        let schema_folder: PathBuf = write_schema(files);
+
+        let file_descriptions: Vec<RuneFileDescription> = parser_rune_files(&[Path::new(&schema_folder)], true, true).expect("Schema should parse");
+        let result: Result<(), CompilerError> = validate_all_files_parsed(&file_descriptions, &[Path::new(&schema_folder)], &[]);
+        remove_dir_all(&schema_folder).expect("Schema folder should be removed");
+
+        result
+    }
+
+    #[test]
+    fn schemas_parsing_fully_are_accepted() {
+        assert!(validate_parsed_schema(&[("common.rune", COMMON_SCHEMA), ("sub/status.rune", "enum Status: u8 {\n    Ok = 0;\n    Failed = 1\n}\n")]).is_ok());
+    }
+
+    #[test]
+    fn enum_values_out_of_range_fail_compilation() {
+        let status: &str = "enum Status: u8 {\n    Ok = 0;\n    Failed = 256\n}\n";
+
+        assert!(matches!(
+            validate_parsed_schema(&[("common.rune", COMMON_SCHEMA), ("status.rune", status)]),
+            Err(CompilerError::MalformedSource)
+        ));
+    }
+
+    #[test]
+    fn colliding_enum_values_are_rejected_by_the_parser() {
+        let status: &str = "enum Status: u8 {\n    Ok = 0;\n    Failed = 0x10;\n    Broken = 16\n}\n";
+        let schema_folder: PathBuf = write_schema(&[("common.rune", COMMON_SCHEMA), ("status.rune", status)]);
+
+        let parsed_files = parser_rune_files(&[Path::new(&schema_folder)], true, true);
+        remove_dir_all(&schema_folder).expect("Schema folder should be removed");
+
+        assert!(parsed_files.is_err());
+    }
+
+    /// Validate the keywords of the given Rune file, compiled for the given C standard
+    fn validate_schema_keywords(contents: &str, c_standard: &str) -> Result<(), CompilerError> {
+        validate_keywords(&parse_schema(&[("schema.rune", contents)]), &compile_configurations(&["--c-standard", c_standard]))
+    }
+
+    #[test]
+    fn struct_member_becoming_a_keyword_is_rejected() {
+        let schema: &str = "struct Device {\n    Register: u8 = 1\n}\n";
+
+        assert!(matches!(validate_schema_keywords(schema, "C89"), Err(CompilerError::MalformedSource)));
+        assert!(matches!(validate_schema_keywords(schema, "C23"), Err(CompilerError::MalformedSource)));
+    }
+
+    #[test]
+    fn bitfield_member_becoming_a_keyword_is_rejected() {
+        let schema: &str = "bitfield Options: u8 {\n    Default: u1 = 0\n}\n";
+
+        assert!(matches!(validate_schema_keywords(schema, "C99"), Err(CompilerError::MalformedSource)));
+    }
+
+    #[test]
+    fn keywords_depend_on_the_c_standard() {
+        let alignas: &str = "struct Device {\n    Alignas: u8 = 1\n}\n";
+        let bool_member: &str = "struct Device {\n    Bool: u8 = 1\n}\n";
+
+        assert!(validate_schema_keywords(alignas, "C11").is_ok());
+        assert!(matches!(validate_schema_keywords(alignas, "C23"), Err(CompilerError::MalformedSource)));
+        assert!(validate_schema_keywords(bool_member, "C89").is_ok());
+        assert!(matches!(validate_schema_keywords(bool_member, "C99"), Err(CompilerError::MalformedSource)));
+    }
+
+    #[test]
+    fn members_only_containing_keywords_are_accepted() {
+        let schema: &str = "struct Device {\n    RegisterValue: u8 = 1;\n    IntValue: i32 = 2;\n    Doubled: f64 = 3\n}\n";
+
+        assert!(validate_schema_keywords(schema, "C23").is_ok());
+    }
+
+    #[test]
+    fn structs_with_sized_members_are_accepted() {
+        let schema: &str = "struct Reading {\n    Reserved: [u8; 0] = 1;\n    Value: u8 = 2\n}\n";
+
+        assert!(validate_struct_sizes(&parse_schema(&[("reading.rune", schema)]), &compile_configurations(&[])).is_ok());
+    }
+
+    #[test]
+    fn structs_without_sized_members_are_rejected() {
+        let schema: &str = "struct Reserved {\n    Data: [u8; 0] = 1\n}\n";
+
+        assert!(matches!(
+            validate_struct_sizes(&parse_schema(&[("reserved.rune", schema)]), &compile_configurations(&[])),
+            Err(CompilerError::MalformedSource)
+        ));
+    }
+
+    #[test]
+    fn structs_without_sized_members_are_rejected_when_nested() {
+        let schema: &str = "struct Reserved {\n    Data: [u8; 0] = 1\n}\n\nstruct Reading {\n    Value: u8 = 1;\n    Spare: Reserved = 2\n}\n";
+
+        assert!(matches!(
+            validate_struct_sizes(&parse_schema(&[("reading.rune", schema)]), &compile_configurations(&[])),
+            Err(CompilerError::MalformedSource)
+        ));
+    }
+
+    #[test]
+    fn structs_without_members_fail_compilation() {
+        // The parser leaves out files with structs written without members, which is then reported as a file that did not parse
+        assert!(matches!(
+            validate_parsed_schema(&[("common.rune", COMMON_SCHEMA), ("empty.rune", "struct Empty {\n}\n")]),
+            Err(CompilerError::MalformedSource)
+        ));
+    }
+
+    /// Validate the given fixed-point members of a struct holding integers of different widths and signedness
+    fn validate_sensor_fixed_point(fixed_point_members: &str) -> Result<(), CompilerError> {
+        let schema: &str = "struct Sensor {\n    Temperature: i32 = 1;\n    Level: u16 = 2;\n    History: [i16; 4] = 3;\n    Ratio: f32 = 4\n}\n";
+
+        validate_fixed_point_members(&parse_schema(&[("sensor.rune", schema)]), &compile_configurations(&["--fixed-point-members", fixed_point_members]))
+    }
+
+    #[test]
+    fn fixed_point_members_matching_their_integer_are_accepted() {
+        assert!(validate_sensor_fixed_point("Sensor.Temperature=Q16.16,Sensor.Level=UQ8.8,Sensor.History=Q4.12").is_ok());
+    }
+
+    #[test]
+    fn fixed_point_members_of_another_width_are_rejected() {
+        assert!(matches!(validate_sensor_fixed_point("Sensor.Temperature=Q8.8"), Err(CompilerError::InvalidArgument)));
+        assert!(matches!(validate_sensor_fixed_point("Sensor.History=Q16.16"), Err(CompilerError::InvalidArgument)));
+    }
+
+    #[test]
+    fn fixed_point_members_of_another_signedness_are_rejected() {
+        assert!(matches!(validate_sensor_fixed_point("Sensor.Temperature=UQ16.16"), Err(CompilerError::InvalidArgument)));
+        assert!(matches!(validate_sensor_fixed_point("Sensor.Level=Q8.8"), Err(CompilerError::InvalidArgument)));
+    }
+
+    #[test]
+    fn fixed_point_members_must_be_integers() {
+        assert!(matches!(validate_sensor_fixed_point("Sensor.Ratio=Q16.16"), Err(CompilerError::InvalidArgument)));
+    }
+
+    #[test]
+    fn flattened_name_collisions_are_rejected() {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[
+            ("sub/dir_foo.rune", "struct First {\n    Value: u8 = 1\n}\n"),
+            ("sub_dir/foo.rune", "struct Second {\n    Value: u8 = 1\n}\n")
+        ]);
+
+        assert!(matches!(
+            validate_flattened_names(&file_descriptions, &compile_configurations(&["--flatten"])),
+            Err(CompilerError::ConfigurationError)
+        ));
+
+        // Without flattening the outputs keep their directories, and amalgamated files are output as one
+        assert!(validate_flattened_names(&file_descriptions, &compile_configurations(&[])).is_ok());
+        assert!(validate_flattened_names(&file_descriptions, &compile_configurations(&["--flatten", "--amalgamate"])).is_ok());
+    }
+
+    const INLINE_SCHEMA: &str = "struct Point {\n    X: i16 = 1;\n    Y: i16 = 2\n}\n\nstruct Sample {\n    Origin: Point = 1;\n    Points: [Point; 2] = 2;\n    Count: u8 = 3\n}\n";
+
+    fn validate_inline_schema(schema: &str, arguments: &[&str]) -> Result<(), CompilerError> {
+        validate_inline_members(&parse_schema(&[("sample.rune", schema)]), &compile_configurations(arguments))
+    }
+
+    #[test]
+    fn single_struct_members_can_be_inlined() {
+        assert!(validate_inline_schema(INLINE_SCHEMA, &["--inline-members", "Sample.Origin"]).is_ok());
+    }
+
+    #[test]
+    fn inlined_members_colliding_with_their_parent_are_rejected() {
+        let schema: &str = "struct Point {\n    X: i16 = 1;\n    Y: i16 = 2\n}\n\nstruct Sample {\n    Origin: Point = 1;\n    X: u8 = 2\n}\n";
+
+        assert!(matches!(validate_inline_schema(schema, &["--inline-members", "Sample.Origin"]), Err(CompilerError::MalformedSource)));
+    }
+
+    #[test]
+    fn inlined_members_which_are_not_single_structs_are_rejected() {
+        assert!(matches!(
+            validate_inline_schema(INLINE_SCHEMA, &["--inline-members", "Sample.Points"]),
+            Err(CompilerError::InvalidArgument)
+        ));
+        assert!(matches!(
+            validate_inline_schema(INLINE_SCHEMA, &["--inline-members", "Sample.Count"]),
+            Err(CompilerError::InvalidArgument)
+        ));
+        assert!(matches!(
+            validate_inline_schema(INLINE_SCHEMA, &["--inline-members", "SampleOrigin"]),
+            Err(CompilerError::InvalidArgument)
+        ));
+    }
+
+    #[test]
+    fn inlined_members_require_c11_and_no_member_access() {
+        assert!(matches!(
+            validate_inline_schema(INLINE_SCHEMA, &["--inline-members", "Sample.Origin", "--c-standard", "C99"]),
+            Err(CompilerError::SourceAndCStandardMismatch)
+        ));
+        assert!(matches!(
+            validate_inline_schema(INLINE_SCHEMA, &["--inline-members", "Sample.Origin", "--emit-codec"]),
+            Err(CompilerError::ConfigurationError)
+        ));
+    }
+
+    fn validate_schema_verifiers(verifier_type: &str, arguments: &[&str]) -> Result<(), CompilerError> {
+        let schema: String = format!("struct Packet {{\n    Crc: {0} = verifier;\n    Value: u8 = 1\n}}\n", verifier_type);
+
+        validate_verifiers(&parse_schema(&[("packet.rune", &schema)]), &compile_configurations(arguments))
+    }
+
+    #[test]
+    fn unsigned_verifier_fields_are_accepted() {
+        for verifier_type in ["u8", "u16", "u32", "u64"] {
+            assert!(validate_schema_verifiers(verifier_type, &["--emit-verifiers"]).is_ok(), "{0} should be accepted", verifier_type);
+        }
+    }
+
+    #[test]
+    fn verifier_fields_which_cannot_hold_a_checksum_are_rejected() {
+        for verifier_type in ["i32", "bool", "f32", "[u8; 4]"] {
+            assert!(
+                matches!(validate_schema_verifiers(verifier_type, &["--emit-verifiers"]), Err(CompilerError::MalformedSource)),
+                "{0} should be rejected",
+                verifier_type
+            );
+        }
+
+        // Without verifier functions the field is not given a meaning
+        assert!(validate_schema_verifiers("i32", &[]).is_ok());
+    }
+}
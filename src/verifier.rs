@@ -0,0 +1,231 @@
+use rune_parser::types::{ArrayType, FieldType, Primitive, StructDefinition, StructMember, UserDefinitionLink};
+
+use crate::{
+    c_utilities::{CArraySize, CConfigurations, CPrimitive, output_debug_checks, pascal_to_snake_case},
+    compile_error::CompilerError,
+    output::*,
+    output_file::OutputFile
+};
+
+// Verifier checksum
+// ——————————————————
+//
+// The verifier field of a struct holds the CRC-32 (IEEE 802.3) of all its other members, fed in field index order, truncated to
+// the width of the verifier field. Members are fed one by one as they are stored in memory, so struct padding never affects the
+// checksum. Nested structs, also within arrays, are fed member by member as well, including their own verifier field.
+
+/// The verifier field of the struct, if it declares one
+fn verifier_member(struct_definition: &StructDefinition) -> Option<&StructMember> {
+    struct_definition.members.iter().find(|member| member.index.is_verifier())
+}
+
+/// Type of the verifier field, which must be an unsigned integer. Checked by validate_verifiers, but checked again here as the
+/// truncating cast would otherwise be invalid
+fn verifier_type(configurations: &CConfigurations, struct_definition: &StructDefinition, member: &StructMember) -> Result<String, CompilerError> {
+    match &member.data_type {
+        FieldType::Primitive(primitive @ (Primitive::U8 | Primitive::U16 | Primitive::U32 | Primitive::U64)) => primitive.to_c_type(&configurations.compiler_configurations.c_standard),
+        _ => {
+            error!(
+                "Verifier field {0} of struct {1} must be an unsigned integer of at most 64 bits!",
+                member.identifier, struct_definition.name
+            );
+            Err(CompilerError::MalformedSource)
+        }
+    }
+}
+
+/// Outputs the declarations of the checksum function of a struct, and of its verifier functions if it declares a verifier field,
+/// into the header file
+pub fn output_verifier_declarations(header_file: &mut OutputFile, configurations: &CConfigurations, struct_definition: &StructDefinition) -> Result<(), CompilerError> {
+    let struct_name: String = pascal_to_snake_case(&struct_definition.name);
+
+    header_file.add_line(format!("RUNE_NODISCARD unsigned long {0}_checksum(unsigned long crc, const {0}_t* message);", struct_name));
+
+    if let Some(member) = verifier_member(struct_definition) {
+        header_file.add_line(format!(
+            "RUNE_NODISCARD {0} {1}_compute_verifier(const {1}_t* message);",
+            verifier_type(configurations, struct_definition, member)?,
+            struct_name
+        ));
+        header_file.add_line(format!(
+            "RUNE_NODISCARD {0} {1}_verify(const {1}_t* message);",
+            Primitive::Bool.to_c_type(&configurations.compiler_configurations.c_standard)?,
+            struct_name
+        ));
+    }
+
+    Ok(())
+}
+
+/// Whether any checksum function of the structs feeds members to the CRC-32 helper directly, rather than through the checksum
+/// function of a nested struct. The helper is static, so it is only output where used
+pub fn uses_crc_function(struct_definitions: &[StructDefinition]) -> bool {
+    struct_definitions
+        .iter()
+        .flat_map(|definition| definition.members.iter())
+        .any(|member| member.data_type != FieldType::Empty && !member.index.is_verifier() && !matches!(member.user_definition_link, UserDefinitionLink::StructLink(_)))
+}
+
+/// Outputs the CRC-32 helper used by the checksum functions of a source file
+pub fn output_crc_function(source_file: &mut OutputFile) {
+    source_file.add_line(String::from("/* CRC-32 (IEEE 802.3) of the bytes, continuing from the given value */"));
+    source_file.add_line(String::from("static unsigned long rune_crc32(unsigned long crc, const void* data, size_t length) {"));
+    source_file.add_indented_line(1, String::from("const unsigned char* bytes = (const unsigned char*)data;"));
+    source_file.add_indented_line(1, String::from("size_t i;"));
+    source_file.add_indented_line(1, String::from("int bit;"));
+    source_file.add_newline();
+    source_file.add_indented_line(1, String::from("for (i = 0; i < length; i++) {"));
+    source_file.add_indented_line(2, String::from("crc ^= (unsigned long)bytes[i];"));
+    source_file.add_indented_line(2, String::from("for (bit = 0; bit < 8; bit++) {"));
+    source_file.add_indented_line(3, String::from("crc = ((crc & 1UL) != 0UL) ? ((crc >> 1) ^ 0xEDB88320UL) : (crc >> 1);"));
+    source_file.add_indented_line(2, String::from("}"));
+    source_file.add_indented_line(1, String::from("}"));
+    source_file.add_newline();
+    source_file.add_indented_line(1, String::from("return crc;"));
+    source_file.add_line(String::from("}"));
+    source_file.add_newline();
+}
+
+/// Outputs the checksum function of a struct, and its verifier functions if it declares a verifier field, into the source file
+pub fn output_verifier(source_file: &mut OutputFile, configurations: &CConfigurations, struct_definition: &StructDefinition) -> Result<(), CompilerError> {
+    let struct_name: String = pascal_to_snake_case(&struct_definition.name);
+
+    // Checksum
+    // —————————
+
+    let mut members: Vec<&StructMember> = struct_definition
+        .members
+        .iter()
+        .filter(|member| member.data_type != FieldType::Empty && !member.index.is_verifier())
+        .collect();
+    members.sort_by_key(|member| member.index.value());
+
+    source_file.add_line(format!("unsigned long {0}_checksum(unsigned long crc, const {0}_t* message) {{", struct_name));
+
+    if members.is_empty() {
+        source_file.add_indented_line(1, String::from("(void)message;"));
+    } else {
+        if members.iter().any(|member| matches!(member.data_type, FieldType::Array(ArrayType::UserDefined(_), _))) {
+            source_file.add_indented_line(1, String::from("size_t i;"));
+            source_file.add_newline();
+        }
+
+        if configurations.compiler_configurations.debug_checks {
+            output_debug_checks(source_file, &["message != NULL"]);
+            source_file.add_newline();
+        }
+    }
+
+    for member in &members {
+        let member_name: String = pascal_to_snake_case(&member.identifier);
+
+        match (&member.data_type, &member.user_definition_link) {
+            (FieldType::UserDefined(_), UserDefinitionLink::StructLink(definition)) => {
+                source_file.add_indented_line(1, format!("crc = {0}_checksum(crc, &message->{1});", pascal_to_snake_case(&definition.name), member_name));
+            },
+            (FieldType::Array(ArrayType::UserDefined(_), array_size), UserDefinitionLink::StructLink(definition)) => {
+                source_file.add_indented_line(1, format!("for (i = 0; i < {0}; i++) {{", array_size.to_c_literal(&configurations.compiler_configurations)));
+                source_file.add_indented_line(2, format!("crc = {0}_checksum(crc, &message->{1}[i]);", pascal_to_snake_case(&definition.name), member_name));
+                source_file.add_indented_line(1, String::from("}"));
+            },
+            _ => source_file.add_indented_line(1, format!("crc = rune_crc32(crc, &message->{0}, sizeof(message->{0}));", member_name))
+        }
+    }
+
+    if !members.is_empty() {
+        source_file.add_newline();
+    }
+
+    source_file.add_indented_line(1, String::from("return crc;"));
+    source_file.add_line(String::from("}"));
+    source_file.add_newline();
+
+    // Verifier
+    // —————————
+
+    let Some(member) = verifier_member(struct_definition) else {
+        return Ok(());
+    };
+
+    let member_type: String = verifier_type(configurations, struct_definition, member)?;
+
+    source_file.add_line(format!("{0} {1}_compute_verifier(const {1}_t* message) {{", member_type, struct_name));
+    source_file.add_indented_line(1, format!("return ({0})({1}_checksum(0xFFFFFFFFUL, message) ^ 0xFFFFFFFFUL);", member_type, struct_name));
+    source_file.add_line(String::from("}"));
+    source_file.add_newline();
+
+    source_file.add_line(format!(
+        "{0} {1}_verify(const {1}_t* message) {{",
+        Primitive::Bool.to_c_type(&configurations.compiler_configurations.c_standard)?,
+        struct_name
+    ));
+    if configurations.compiler_configurations.debug_checks {
+        output_debug_checks(source_file, &["message != NULL"]);
+    }
+    source_file.add_indented_line(
+        1,
+        format!("return message->{0} == {1}_compute_verifier(message);", pascal_to_snake_case(&member.identifier), struct_name)
+    );
+    source_file.add_line(String::from("}"));
+    source_file.add_newline();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rune_parser::RuneFileDescription;
+
+    use crate::{
+        generate_to_memory,
+        test_utilities::{compile_configurations, parse_schema}
+    };
+
+    const SCHEMA: &str = "struct Packet {\n    Crc: u16 = verifier;\n    Value: u8 = 1;\n    Count: u32 = 2\n}\n\nstruct Plain {\n    Value: u8 = 1\n}\n";
+
+    /// Output the header and source generated from the schema with the given command line arguments
+    fn generated_files(arguments: &[&str]) -> (String, String) {
+        let file_descriptions: Vec<RuneFileDescription> = parse_schema(&[("packet.rune", SCHEMA)]);
+        let output_files: Vec<(String, String)> = generate_to_memory(&file_descriptions, &compile_configurations(arguments)).unwrap();
+
+        let file = |name: &str| -> String { output_files.iter().find(|(output_name, _)| output_name == name).expect("File should be generated").1.clone() };
+
+        (file("packet.rune.h"), file("packet.rune.c"))
+    }
+
+    #[test]
+    fn structs_with_a_verifier_field_can_be_verified() {
+        let (header, source): (String, String) = generated_files(&["--emit-verifiers"]);
+
+        assert!(header.contains("RUNE_NODISCARD uint16_t packet_compute_verifier(const packet_t* message);"));
+        assert!(header.contains("RUNE_NODISCARD bool packet_verify(const packet_t* message);"));
+        assert!(source.contains("uint16_t packet_compute_verifier(const packet_t* message) {\n    return (uint16_t)(packet_checksum(0xFFFFFFFFUL, message) ^ 0xFFFFFFFFUL);\n}"));
+        assert!(source.contains("bool packet_verify(const packet_t* message) {\n    return message->crc == packet_compute_verifier(message);\n}"));
+    }
+
+    #[test]
+    fn checksums_skip_the_verifier_field() {
+        let (_, source): (String, String) = generated_files(&["--emit-verifiers"]);
+
+        assert!(source.contains(
+            "unsigned long packet_checksum(unsigned long crc, const packet_t* message) {\n    crc = rune_crc32(crc, &message->value, sizeof(message->value));\n    crc = rune_crc32(crc, &message->count, sizeof(message->count));\n\n    return crc;\n}"
+        ));
+    }
+
+    #[test]
+    fn structs_without_a_verifier_field_only_get_a_checksum() {
+        let (header, _): (String, String) = generated_files(&["--emit-verifiers"]);
+
+        assert!(header.contains("RUNE_NODISCARD unsigned long plain_checksum(unsigned long crc, const plain_t* message);"));
+        assert!(!header.contains("plain_compute_verifier"));
+        assert!(!header.contains("plain_verify"));
+    }
+
+    #[test]
+    fn no_verifier_functions_are_generated_by_default() {
+        let (header, source): (String, String) = generated_files(&[]);
+
+        assert!(!header.contains("_checksum") && !source.contains("_checksum"));
+        assert!(!header.contains("packet_verify") && !source.contains("packet_verify"));
+    }
+}